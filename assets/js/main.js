@@ -18,6 +18,28 @@ function isStringFiniteNumeric(v) {
 /// Trim input string. Return empty string when input is not a string.
 function trimAsString(v) { return isString(v) ? v.trim() : ""; }
 
+/// Shortcuts bound in the keymap, set with `setShortcuts()`.
+let shortcutKeys = new Set();
+
+function setShortcuts(keys) {
+    shortcutKeys = new Set(keys);
+}
+
+/// Same as `KeyboardEvent::shortcut` in `keymap.rs`, e.g. "Ctrl+Shift+C".
+function shortcutKey(event) {
+    if (event.isComposing || ["Control", "Shift", "Alt", "Meta"].includes(event.key)) return null;
+
+    const isMac = navigator.platform.toUpperCase().startsWith("MAC");
+    let key = event.key === " " ? "Space" : event.key;
+    if ([...key].length === 1) key = key.toUpperCase();
+    return [
+        (isMac ? event.metaKey : event.ctrlKey) ? "Ctrl+" : "",
+        event.altKey ? "Alt+" : "",
+        event.shiftKey ? "Shift+" : "",
+        key,
+    ].join("");
+}
+
 function setupKeydownEvent(tx) {
     if (!tx) {
         console.error("Missing tx, keydown event won't be handled");
@@ -25,6 +47,11 @@ function setupKeydownEvent(tx) {
     }
 
     document.addEventListener("keydown", function(event) {
+        // Bound shortcuts run their action instead of whatever the webview binds them to.
+        if (shortcutKeys.has(shortcutKey(event))) {
+            event.preventDefault();
+        }
+
         // From: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent
         const rustEvent = {
             "altKey": event.altKey,
@@ -41,6 +68,64 @@ function setupKeydownEvent(tx) {
     });
 }
 
+/// Main input or output of the selected tool, marked with `data-role` in its view.
+/// Panels hidden by a tool keep their elements around, those are skipped.
+function toolElement(role) {
+    const elements = document.querySelectorAll(`[data-role="${role}"]`);
+    return [...elements].find((element) => !element.closest("[hidden]")) ?? null;
+}
+
+function elementText(element) {
+    return "value" in element ? element.value : element.innerText;
+}
+
+function copyText(text) {
+    const fallback = () => {
+        const textarea = document.createElement("textarea");
+        textarea.value = text;
+        document.body.appendChild(textarea);
+        textarea.select();
+        document.execCommand("copy");
+        textarea.remove();
+    };
+    if (navigator.clipboard) {
+        navigator.clipboard.writeText(text).catch(fallback);
+    } else {
+        fallback();
+    }
+}
+
+function copyOutput() {
+    const output = toolElement("output");
+    if (output) copyText(elementText(output));
+}
+
+/// Replace the input, letting the view know through an `input` event.
+function setInput(text) {
+    const input = toolElement("input");
+    if (!input) return;
+    input.value = text;
+    input.dispatchEvent(new Event("input", { bubbles: true }));
+    input.focus();
+}
+
+function swapInputOutput() {
+    const output = toolElement("output");
+    if (output) setInput(elementText(output));
+}
+
+function focusInput() {
+    const input = toolElement("input");
+    if (input) input.focus();
+}
+
+function selectInput(start, end) {
+    const input = toolElement("input");
+    if (!input) return;
+    input.focus();
+    input.setSelectionRange(start, end);
+}
+
 function setTheme(lightMode) {
     console.log("Change theme: ", lightMode)
     if (lightMode) {
@@ -50,6 +135,22 @@ function setTheme(lightMode) {
     }
 }
 
+/// Empty families and a zero size restore the Bootstrap defaults.
+function setFont(family, monospaceFamily, size) {
+    const style = document.documentElement.style;
+    if (isNonBlankString(family)) {
+        style.setProperty('--bs-body-font-family', family);
+    } else {
+        style.removeProperty('--bs-body-font-family');
+    }
+    if (isNonBlankString(monospaceFamily)) {
+        style.setProperty('--bs-font-monospace', monospaceFamily);
+    } else {
+        style.removeProperty('--bs-font-monospace');
+    }
+    style.fontSize = size > 0 ? `${size}px` : '';
+}
+
 $(document).ready(function() {
 
     // Resize flex elements.
@@ -0,0 +1,270 @@
+use std::{cmp::Reverse, fmt};
+
+use dioxus::{logger::tracing, prelude::*};
+use strum::IntoEnumIterator;
+
+use crate::{
+    Tools,
+    core::fuzzy::fuzzy_score,
+    session::Session,
+    settings::{Settings, Theme},
+    views::{
+        tool_base64_hash::HashSource, tool_datetime::DateTimeMode, tool_ip::IpMode,
+        tool_jwt::JwtMode,
+    },
+};
+
+/// Smallest zoom level zooming out goes to, the minimum of the zoom field in the settings.
+const MIN_ZOOM: f64 = 0.1;
+
+/// Which overlay covers the tools, if any.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overlay {
+    CommandPalette,
+    Shortcuts,
+}
+
+/// Signals the actions act on, provided as context by `app()`.
+#[derive(Clone, Copy)]
+pub struct AppState {
+    pub settings: Signal<Settings>,
+    pub session: Signal<Session>,
+    pub overlay: Signal<Option<Overlay>>,
+}
+
+/// Something the user can run from the command palette or a keyboard shortcut.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Action {
+    OpenTool(Tools),
+    /// Open the N-th tool of the sidebar, counting from 1.
+    SwitchToTool(usize),
+    NextTool,
+    PreviousTool,
+    CopyOutput,
+    SwapInputOutput,
+    ClearInput,
+    FocusInput,
+    OpenCommandPalette,
+    ShowShortcuts,
+    DecodeJwt,
+    EncodeJwt,
+    ConvertDateTime,
+    DateTimeArithmetic,
+    IpAddressDetails,
+    PlanIpSubnets,
+    HashText,
+    HashFiles,
+    ToggleTheme,
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::OpenTool(tool) => write!(f, "{tool}"),
+            Action::SwitchToTool(n) => write!(f, "Switch to tool {n}"),
+            Action::NextTool => write!(f, "Next tool"),
+            Action::PreviousTool => write!(f, "Previous tool"),
+            Action::CopyOutput => write!(f, "Copy output"),
+            Action::SwapInputOutput => write!(f, "Swap input and output"),
+            Action::ClearInput => write!(f, "Clear input"),
+            Action::FocusInput => write!(f, "Focus input"),
+            Action::OpenCommandPalette => write!(f, "Command palette"),
+            Action::ShowShortcuts => write!(f, "Keyboard shortcuts"),
+            Action::DecodeJwt => write!(f, "Decode JWT"),
+            Action::EncodeJwt => write!(f, "Encode JWT"),
+            Action::ConvertDateTime => write!(f, "Convert date/time"),
+            Action::DateTimeArithmetic => write!(f, "Date/time arithmetic"),
+            Action::IpAddressDetails => write!(f, "IP address details"),
+            Action::PlanIpSubnets => write!(f, "Plan IP subnets"),
+            Action::HashText => write!(f, "Hash text"),
+            Action::HashFiles => write!(f, "Hash files"),
+            Action::ToggleTheme => write!(f, "Toggle theme"),
+            Action::ZoomIn => write!(f, "Zoom in"),
+            Action::ZoomOut => write!(f, "Zoom out"),
+            Action::ResetZoom => write!(f, "Reset zoom"),
+        }
+    }
+}
+
+impl Action {
+    /// Every tool followed by every other action listed in the command palette.
+    pub fn all() -> Vec<Action> {
+        Tools::iter()
+            .map(Action::OpenTool)
+            .chain([
+                Action::NextTool,
+                Action::PreviousTool,
+                Action::CopyOutput,
+                Action::SwapInputOutput,
+                Action::ClearInput,
+                Action::FocusInput,
+                Action::ShowShortcuts,
+                Action::DecodeJwt,
+                Action::EncodeJwt,
+                Action::ConvertDateTime,
+                Action::DateTimeArithmetic,
+                Action::IpAddressDetails,
+                Action::PlanIpSubnets,
+                Action::HashText,
+                Action::HashFiles,
+                Action::ToggleTheme,
+                Action::ZoomIn,
+                Action::ZoomOut,
+                Action::ResetZoom,
+            ])
+            .collect()
+    }
+
+    pub fn is_tool(&self) -> bool {
+        matches!(self, Action::OpenTool(_))
+    }
+
+    pub fn run(&self, state: AppState) {
+        let AppState {
+            mut settings,
+            mut session,
+            mut overlay,
+        } = state;
+        tracing::debug!("run action {self}");
+        match self {
+            Action::OpenTool(tool) => session.write().selected_tool = tool.clone(),
+            Action::SwitchToTool(n) => {
+                if let Some(tool) = n
+                    .checked_sub(1)
+                    .and_then(|i| visible_tools(settings).nth(i))
+                {
+                    session.write().selected_tool = tool;
+                }
+            }
+            Action::NextTool | Action::PreviousTool => {
+                let tools = visible_tools(settings).collect::<Vec<_>>();
+                let selected = session.peek().selected_tool.clone();
+                let next = match tools.iter().position(|v| *v == selected) {
+                    Some(i) if *self == Action::NextTool => (i + 1) % tools.len(),
+                    Some(i) => (i + tools.len() - 1) % tools.len(),
+                    None => 0,
+                };
+                if let Some(tool) = tools.get(next) {
+                    session.write().selected_tool = tool.clone();
+                }
+            }
+            Action::CopyOutput => run_js("copyOutput()"),
+            Action::SwapInputOutput => run_js("swapInputOutput()"),
+            Action::ClearInput => run_js("setInput('')"),
+            Action::FocusInput => run_js("focusInput()"),
+            Action::OpenCommandPalette | Action::ShowShortcuts => {
+                let target = match self {
+                    Action::OpenCommandPalette => Overlay::CommandPalette,
+                    _ => Overlay::Shortcuts,
+                };
+                let current = *overlay.peek();
+                overlay.set((current != Some(target)).then_some(target));
+            }
+            Action::DecodeJwt | Action::EncodeJwt => {
+                let mut session = session.write();
+                session.selected_tool = Tools::Jwt;
+                session.jwt.set_mode(match self {
+                    Action::DecodeJwt => JwtMode::Decode,
+                    _ => JwtMode::Encode,
+                });
+            }
+            Action::ConvertDateTime | Action::DateTimeArithmetic => {
+                let mut session = session.write();
+                session.selected_tool = Tools::DateTime;
+                session.datetime.set_mode(match self {
+                    Action::ConvertDateTime => DateTimeMode::Convert,
+                    _ => DateTimeMode::Arithmetic,
+                });
+            }
+            Action::IpAddressDetails | Action::PlanIpSubnets => {
+                let mut session = session.write();
+                session.selected_tool = Tools::IP;
+                session.ip.set_mode(match self {
+                    Action::IpAddressDetails => IpMode::Address,
+                    _ => IpMode::Planner,
+                });
+            }
+            Action::HashText | Action::HashFiles => {
+                let mut session = session.write();
+                session.selected_tool = Tools::Hash;
+                session.hash.set_source(match self {
+                    Action::HashText => HashSource::Text,
+                    _ => HashSource::Files,
+                });
+            }
+            Action::ToggleTheme => {
+                let theme = match settings.peek().theme {
+                    Theme::Dark => Theme::Light,
+                    Theme::Light => Theme::Dark,
+                };
+                settings.write().theme = theme;
+            }
+            Action::ZoomIn => {
+                let step = settings.peek().zoom_step;
+                session.write().zoom_level += step;
+            }
+            Action::ZoomOut => {
+                let step = settings.peek().zoom_step;
+                let mut session = session.write();
+                session.zoom_level = (session.zoom_level - step).max(MIN_ZOOM);
+            }
+            Action::ResetZoom => {
+                let zoom = settings.peek().default_zoom;
+                session.write().zoom_level = zoom;
+            }
+        }
+    }
+}
+
+/// Tools shown in the sidebar, in its order.
+fn visible_tools(settings: Signal<Settings>) -> impl Iterator<Item = Tools> {
+    let settings = settings.peek().clone();
+    Tools::iter().filter(move |v| !settings.is_hidden(v))
+}
+
+/// Run a helper of `main.js` working on the `data-role` elements of the selected tool.
+fn run_js(js: &'static str) {
+    spawn(async move {
+        if let Err(e) = document::eval(js).await {
+            tracing::error!("running javascript failed: {e}");
+        }
+    });
+}
+
+/// Put `text` on the clipboard, for the copy buttons of a view.
+pub fn copy_text(text: String) {
+    spawn(async move {
+        let js = format!("copyText({})", serde_json::Value::from(text));
+        if let Err(e) = document::eval(&js).await {
+            tracing::error!("running javascript failed: {e}");
+        }
+    });
+}
+
+/// Focus the input of the selected tool and select `start..end` of it, in UTF-16 code units.
+pub fn select_input(start: usize, end: usize) {
+    spawn(async move {
+        if let Err(e) = document::eval(&format!("selectInput({start}, {end})")).await {
+            tracing::error!("running javascript failed: {e}");
+        }
+    });
+}
+
+/// Actions matching `query`, recently used ones first, then the best matches.
+pub fn rank_actions(query: &str, recent: &[Action]) -> Vec<Action> {
+    let mut ranked = Action::all()
+        .into_iter()
+        .filter_map(|action| Some((fuzzy_score(query, &action.to_string())?, action)))
+        .collect::<Vec<_>>();
+    ranked.sort_by_key(|(score, action)| {
+        let recency = recent
+            .iter()
+            .position(|v| v == action)
+            .unwrap_or(usize::MAX);
+        (recency, Reverse(*score))
+    });
+    ranked.into_iter().map(|(_, action)| action).collect()
+}
@@ -0,0 +1,491 @@
+use std::{
+    io::{Read, Write},
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::LazyLock,
+};
+
+use anyhow::{Context, anyhow};
+use clap::{Parser, Subcommand, ValueEnum, builder::PossibleValue};
+use strum::IntoEnumIterator;
+
+use crate::core::{
+    InputKind,
+    base64::{Base64Alphabet, DecodedFormat, decode, encode, format_decoded},
+    datetime::{datetime_rows, parse_datetime},
+    hash::{HashAlgorithm, MultiHasher, hash_reader},
+    ip::{classify_ip, ip_rows, parse_ip_input},
+    jwt::{
+        DEFAULT_HEADER, JwtAlgorithm, JwtVerification, decode_jwt, encode_jwt,
+        set_header_algorithm, verify_jwt,
+    },
+    manifest::{ManifestFormat, format_manifest_line},
+};
+
+/// Developer utilities. Starts the GUI when no subcommand is given.
+#[derive(Debug, Parser)]
+#[command(name = "utils", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Encode or decode Base64.
+    #[command(subcommand)]
+    Base64(Base64Command),
+
+    /// Hash files (or stdin).
+    Hash {
+        /// Algorithms to use, all of them if not given.
+        #[arg(long, short, value_enum)]
+        algo: Vec<HashAlgorithm>,
+
+        /// Files to hash, `-` for stdin.
+        files: Vec<PathBuf>,
+    },
+
+    /// Decode, verify or sign JSON Web Tokens.
+    #[command(subcommand)]
+    Jwt(JwtCommand),
+
+    /// Convert a date/time (Unix time, RFC 3339, RFC 2822, ...) to every supported format.
+    Datetime {
+        /// Date/time to convert, now if not given.
+        input: Option<String>,
+    },
+
+    /// Show details of an IP address or CIDR.
+    Ip {
+        /// Address or CIDR, e.g. `192.168.1.10/24`.
+        input: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Base64Command {
+    /// Encode a file (or stdin).
+    Encode {
+        /// Use the URL and filename safe alphabet.
+        #[arg(long)]
+        url_safe: bool,
+
+        /// Treat the input as hex text instead of raw bytes.
+        #[arg(long)]
+        hex: bool,
+
+        /// Input file, `-` for stdin.
+        file: Option<PathBuf>,
+    },
+
+    /// Decode a file (or stdin).
+    Decode {
+        /// Output format, raw bytes if not given.
+        #[arg(long, short, value_enum)]
+        output: Option<DecodedFormat>,
+
+        /// Input file, `-` for stdin.
+        file: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum JwtCommand {
+    /// Print the header and payload of a token as JSON.
+    Decode {
+        /// Token, read from stdin if not given.
+        token: Option<String>,
+    },
+
+    /// Check the signature and the `exp`/`nbf` claims of a token.
+    Verify {
+        /// File with the HMAC secret, PEM public key or JWK (Set).
+        #[arg(long, short)]
+        key: PathBuf,
+
+        /// Token, read from stdin if not given.
+        token: Option<String>,
+    },
+
+    /// Sign a JSON payload.
+    Sign {
+        /// File with the HMAC secret, PEM private key or JWK.
+        #[arg(long, short)]
+        key: PathBuf,
+
+        /// Signature algorithm, e.g. `HS256`, `RS256` or `EdDSA`.
+        #[arg(long, default_value = "HS256", value_parser = JwtAlgorithm::from_str)]
+        alg: JwtAlgorithm,
+
+        /// Payload JSON file, `-` for stdin.
+        payload: Option<PathBuf>,
+    },
+}
+
+/// Name of an algorithm on the command line, e.g. `sha3-256`.
+fn hash_algorithm_name(algorithm: HashAlgorithm) -> &'static str {
+    match algorithm {
+        HashAlgorithm::Md5 => "md5",
+        HashAlgorithm::Sha1 => "sha1",
+        HashAlgorithm::Sha224 => "sha224",
+        HashAlgorithm::Sha256 => "sha256",
+        HashAlgorithm::Sha384 => "sha384",
+        HashAlgorithm::Sha512 => "sha512",
+        HashAlgorithm::Sha512_224 => "sha512-224",
+        HashAlgorithm::Sha512_256 => "sha512-256",
+        HashAlgorithm::Sha3_224 => "sha3-224",
+        HashAlgorithm::Sha3_256 => "sha3-256",
+        HashAlgorithm::Sha3_384 => "sha3-384",
+        HashAlgorithm::Sha3_512 => "sha3-512",
+        HashAlgorithm::Keccak256 => "keccak-256",
+        HashAlgorithm::Shake128 => "shake128",
+        HashAlgorithm::Shake256 => "shake256",
+        HashAlgorithm::Blake2b512 => "blake2b-512",
+        HashAlgorithm::Blake2s256 => "blake2s-256",
+        HashAlgorithm::Blake3 => "blake3",
+        HashAlgorithm::Ripemd160 => "ripemd-160",
+        HashAlgorithm::Sm3 => "sm3",
+        HashAlgorithm::Whirlpool => "whirlpool",
+        HashAlgorithm::Crc32 => "crc32",
+        HashAlgorithm::Crc32c => "crc32c",
+        HashAlgorithm::Crc64 => "crc64",
+        HashAlgorithm::Adler32 => "adler32",
+        HashAlgorithm::Xxh32 => "xxh32",
+        HashAlgorithm::Xxh64 => "xxh64",
+        HashAlgorithm::Xxh3_64 => "xxh3-64",
+        HashAlgorithm::Xxh3_128 => "xxh3-128",
+        HashAlgorithm::Fnv1a32 => "fnv1a-32",
+        HashAlgorithm::Fnv1a64 => "fnv1a-64",
+        HashAlgorithm::Murmur3_32 => "murmur3-32",
+        HashAlgorithm::Murmur3_128 => "murmur3-128",
+    }
+}
+
+// The core enums stay free of clap, their command line names are given here.
+impl ValueEnum for HashAlgorithm {
+    fn value_variants<'a>() -> &'a [Self] {
+        static VARIANTS: LazyLock<Vec<HashAlgorithm>> =
+            LazyLock::new(|| HashAlgorithm::iter().collect());
+        &VARIANTS
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(hash_algorithm_name(*self)))
+    }
+}
+
+impl ValueEnum for DecodedFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            DecodedFormat::Utf8,
+            DecodedFormat::Json,
+            DecodedFormat::SimpleHex,
+            DecodedFormat::PrettyHex,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(match self {
+            DecodedFormat::Utf8 => "utf8",
+            DecodedFormat::Json => "json",
+            DecodedFormat::SimpleHex => "hex",
+            DecodedFormat::PrettyHex => "pretty-hex",
+        }))
+    }
+}
+
+/// Read `path`, or stdin when it is missing or `-`.
+fn read_input(path: Option<&Path>) -> anyhow::Result<Vec<u8>> {
+    match path {
+        Some(path) if path != Path::new("-") => {
+            std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))
+        }
+        _ => {
+            let mut buffer = vec![];
+            std::io::stdin().read_to_end(&mut buffer)?;
+            Ok(buffer)
+        }
+    }
+}
+
+/// Digests of `path`, or of stdin for `-`, read once in chunks whatever the number of
+/// algorithms.
+fn hash_input(path: &Path, algorithms: &[HashAlgorithm]) -> anyhow::Result<Vec<Vec<u8>>> {
+    let hasher = MultiHasher::new(algorithms);
+    let keep_going = |_| ControlFlow::Continue(());
+    let digests = if path == Path::new("-") {
+        hash_reader(std::io::stdin().lock(), hasher, keep_going)
+    } else {
+        std::fs::File::open(path).and_then(|file| hash_reader(file, hasher, keep_going))
+    }
+    .with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(digests.unwrap_or_default())
+}
+
+fn read_text(path: Option<&Path>) -> anyhow::Result<String> {
+    String::from_utf8(read_input(path)?).context("input is not valid UTF-8")
+}
+
+/// Key files usually end with a newline that isn't part of an HMAC secret.
+fn read_key(path: &Path) -> anyhow::Result<String> {
+    Ok(read_text(Some(path))?
+        .trim_end_matches(['\r', '\n'])
+        .to_string())
+}
+
+fn token_or_stdin(token: Option<String>) -> anyhow::Result<String> {
+    match token {
+        Some(token) => Ok(token),
+        None => read_text(None),
+    }
+}
+
+fn run_base64(command: Base64Command) -> anyhow::Result<()> {
+    match command {
+        Base64Command::Encode {
+            url_safe,
+            hex,
+            file,
+        } => {
+            let mut input = read_input(file.as_deref())?;
+            if hex {
+                let text = String::from_utf8(input).context("input is not valid hex")?;
+                input = InputKind::Hex.decode(&text)?;
+            }
+            let alphabet = if url_safe {
+                Base64Alphabet::UrlSafe
+            } else {
+                Base64Alphabet::Standard
+            };
+            println!("{}", encode(&input, alphabet));
+        }
+        Base64Command::Decode { output, file } => {
+            let input = read_text(file.as_deref())?;
+            let decoded = decode(input.trim())?;
+            match output {
+                Some(kind) => println!("{}", format_decoded(&decoded, kind)),
+                None => std::io::stdout().write_all(&decoded)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Same layout as `sha256sum` and friends for one algorithm, BSD-style tagged lines otherwise,
+/// both readable by `sha256sum -c` and the manifest verifier.
+fn hash_lines(path: &str, algorithms: &[HashAlgorithm], digests: &[Vec<u8>]) -> Vec<String> {
+    let format = match algorithms {
+        [_] => ManifestFormat::Gnu,
+        _ => ManifestFormat::Bsd,
+    };
+    algorithms
+        .iter()
+        .zip(digests)
+        .map(|(algorithm, digest)| format_manifest_line(format, *algorithm, path, digest))
+        .collect()
+}
+
+fn run_hash(algo: Vec<HashAlgorithm>, files: Vec<PathBuf>) -> anyhow::Result<()> {
+    let files = if files.is_empty() {
+        vec![PathBuf::from("-")]
+    } else {
+        files
+    };
+    let algorithms = if algo.is_empty() {
+        HashAlgorithm::iter().collect()
+    } else {
+        algo
+    };
+    for file in files {
+        let digests = hash_input(&file, &algorithms)?;
+        for line in hash_lines(&file.display().to_string(), &algorithms, &digests) {
+            println!("{line}");
+        }
+    }
+    Ok(())
+}
+
+fn run_jwt(command: JwtCommand) -> anyhow::Result<()> {
+    match command {
+        JwtCommand::Decode { token } => {
+            let decoded = decode_jwt(&token_or_stdin(token)?);
+            if !decoded.valid() {
+                let error = [&decoded.header, &decoded.payload, &decoded.signature]
+                    .into_iter()
+                    .find(|v| !v.valid)
+                    .map(|v| v.text.clone())
+                    .unwrap_or_default();
+                return Err(anyhow!(error));
+            }
+            let output = serde_json::json!({
+                "header": serde_json::from_str::<serde_json::Value>(&decoded.header.text)?,
+                "payload": decoded.claims,
+                "signature": decoded.signature.text,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        JwtCommand::Verify { key, token } => {
+            let verification = verify_jwt(
+                &token_or_stdin(token)?,
+                &read_key(&key)?,
+                jiff::Timestamp::now(),
+            )?;
+            println!("{verification}");
+            if verification != JwtVerification::Valid {
+                std::process::exit(1);
+            }
+        }
+        JwtCommand::Sign { key, alg, payload } => {
+            let header = set_header_algorithm(DEFAULT_HEADER, alg);
+            let payload = read_text(payload.as_deref())?;
+            println!("{}", encode_jwt(&header, &payload, &read_key(&key)?)?);
+        }
+    }
+    Ok(())
+}
+
+/// Print `name: value` rows with the values aligned.
+fn print_rows<N: AsRef<str>>(rows: &[(N, String)]) {
+    let width = rows
+        .iter()
+        .map(|(name, _)| name.as_ref().chars().count())
+        .max();
+    for (name, value) in rows {
+        let name = format!("{}:", name.as_ref());
+        println!(
+            "{name:<width$} {value}",
+            width = width.unwrap_or_default() + 1
+        );
+    }
+}
+
+/// Run a headless subcommand, writing its result to stdout.
+pub fn run(command: Command) -> anyhow::Result<()> {
+    match command {
+        Command::Base64(command) => run_base64(command),
+        Command::Hash { algo, files } => run_hash(algo, files),
+        Command::Jwt(command) => run_jwt(command),
+        Command::Datetime { input } => {
+            let input = input.unwrap_or_else(|| jiff::Timestamp::now().to_string());
+            let (zoned, format) = parse_datetime(&input, &jiff::tz::TimeZone::system())?;
+            let mut rows = vec![("Format".to_string(), format.to_string())];
+            rows.extend(datetime_rows(zoned.timestamp(), &[])?);
+            print_rows(&rows);
+            Ok(())
+        }
+        Command::Ip { input } => {
+            let (address, net) = parse_ip_input(&input)?;
+            let mut rows = vec![("Class", classify_ip(address).join(", "))];
+            rows.extend(ip_rows(address, net));
+            print_rows(&rows);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, clap::Error> {
+        Cli::try_parse_from(["utils"].iter().chain(args)).map(|v| v.command.expect("a subcommand"))
+    }
+
+    #[test]
+    fn definition() {
+        Cli::command().debug_assert();
+        assert!(Cli::try_parse_from(["utils"]).unwrap().command.is_none());
+    }
+
+    #[test]
+    fn value_names() {
+        for algorithm in HashAlgorithm::iter() {
+            let name = hash_algorithm_name(algorithm);
+            assert_eq!(HashAlgorithm::from_str(name, false), Ok(algorithm));
+        }
+        assert_eq!(hash_algorithm_name(HashAlgorithm::Keccak256), "keccak-256");
+    }
+
+    #[test]
+    fn parse_subcommands() {
+        assert!(matches!(
+            parse(&["hash", "-a", "sha256", "-a", "sha3-512", "a.txt"]).unwrap(),
+            Command::Hash { algo, files }
+                if algo == [HashAlgorithm::Sha256, HashAlgorithm::Sha3_512]
+                    && files == [PathBuf::from("a.txt")]
+        ));
+        assert!(matches!(
+            parse(&["base64", "decode", "-o", "pretty-hex"]).unwrap(),
+            Command::Base64(Base64Command::Decode {
+                output: Some(DecodedFormat::PrettyHex),
+                file: None,
+            })
+        ));
+        assert!(matches!(
+            parse(&["jwt", "sign", "-k", "key.pem", "--alg", "EdDSA"]).unwrap(),
+            Command::Jwt(JwtCommand::Sign {
+                alg: JwtAlgorithm::EdDsa,
+                ..
+            })
+        ));
+        assert!(parse(&["jwt", "sign", "-k", "key.pem", "--alg", "none"]).is_err());
+        assert!(parse(&["hash", "-a", "sha0"]).is_err());
+    }
+
+    #[test]
+    fn key_file() {
+        let path = std::env::temp_dir().join(format!("utils-cli-key-{}", std::process::id()));
+        std::fs::write(&path, "secret \r\n\n").unwrap();
+        let key = read_key(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(key.unwrap(), "secret ");
+        assert!(read_key(Path::new("/nonexistent/key")).is_err());
+    }
+
+    #[test]
+    fn hash_files() {
+        use crate::core::hash::hash;
+
+        let path = std::env::temp_dir().join(format!("utils-cli-hash-{}", std::process::id()));
+        std::fs::write(&path, "abc").unwrap();
+        let algorithms = [HashAlgorithm::Sha256, HashAlgorithm::Blake3];
+        let digests = hash_input(&path, &algorithms);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            digests.unwrap(),
+            algorithms.map(|v| hash(v, b"abc")).to_vec()
+        );
+        assert!(hash_input(Path::new("/nonexistent/file"), &algorithms).is_err());
+    }
+
+    #[test]
+    fn hash_lines_parse_back() {
+        use crate::core::{hash::hash, manifest::parse_manifest};
+
+        let algorithms = [HashAlgorithm::Sha256, HashAlgorithm::Sha512_256];
+        let digests = algorithms.map(|v| hash(v, b"abc"));
+        let lines = hash_lines("a b", &algorithms, &digests);
+        assert!(
+            lines[0].starts_with("SHA256 (a b) = ba7816bf"),
+            "{}",
+            lines[0]
+        );
+        let parsed = parse_manifest(&lines.join("\n"));
+        assert_eq!(parsed.len(), 2);
+        for ((_, entry), (algorithm, digest)) in
+            parsed.into_iter().zip(algorithms.iter().zip(&digests))
+        {
+            let entry = entry.unwrap();
+            assert_eq!(entry.path, "a b");
+            assert_eq!(entry.algorithm, Some(*algorithm));
+            assert_eq!(&entry.digest, digest);
+        }
+
+        let lines = hash_lines("-", &algorithms[..1], &digests[..1]);
+        assert_eq!(lines, [format!("{}  -", hex::encode(&digests[0]))]);
+    }
+}
@@ -0,0 +1,435 @@
+use base64::{
+    alphabet::{self, Alphabet},
+    engine::{
+        DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig,
+        general_purpose::{STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD},
+    },
+    prelude::*,
+};
+
+/// Characters per line of MIME, RFC 2045.
+const MIME_LINE_LENGTH: usize = 76;
+
+const NO_PAD: GeneralPurposeConfig = GeneralPurposeConfig::new()
+    .with_encode_padding(false)
+    .with_decode_padding_mode(DecodePaddingMode::RequireNone);
+
+/// `./A-Za-z0-9`, as in bcrypt hashes.
+const BCRYPT: GeneralPurpose = GeneralPurpose::new(&alphabet::BCRYPT, NO_PAD);
+
+/// `./0-9A-Za-z`, as in `crypt(3)` hashes.
+const CRYPT: GeneralPurpose = GeneralPurpose::new(&alphabet::CRYPT, NO_PAD);
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+pub enum Base64Alphabet {
+    Standard,
+
+    #[strum(to_string = "URL")]
+    UrlSafe,
+
+    #[strum(to_string = "Standard (no pad)")]
+    StandardNoPad,
+
+    #[strum(to_string = "URL (no pad)")]
+    UrlSafeNoPad,
+
+    /// Standard, in lines of 76 characters ended by CRLF.
+    #[strum(to_string = "MIME")]
+    Mime,
+
+    #[strum(to_string = "bcrypt")]
+    Bcrypt,
+
+    #[strum(to_string = "crypt")]
+    Crypt,
+}
+
+/// How decoded bytes are shown.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+pub enum DecodedFormat {
+    #[strum(to_string = "UTF-8")]
+    Utf8,
+
+    #[strum(to_string = "JSON")]
+    Json,
+
+    #[strum(to_string = "Hex")]
+    SimpleHex,
+
+    #[strum(to_string = "Pretty Hex")]
+    PrettyHex,
+}
+
+pub fn encode(input: &[u8], alphabet: Base64Alphabet) -> String {
+    match alphabet {
+        Base64Alphabet::Standard => BASE64_STANDARD.encode(input),
+        Base64Alphabet::UrlSafe => URL_SAFE.encode(input),
+        Base64Alphabet::StandardNoPad => STANDARD_NO_PAD.encode(input),
+        Base64Alphabet::UrlSafeNoPad => URL_SAFE_NO_PAD.encode(input),
+        Base64Alphabet::Mime => BASE64_STANDARD
+            .encode(input)
+            .as_bytes()
+            .chunks(MIME_LINE_LENGTH)
+            .map(|v| String::from_utf8_lossy(v))
+            .collect::<Vec<_>>()
+            .join("\r\n"),
+        Base64Alphabet::Bcrypt => BCRYPT.encode(input),
+        Base64Alphabet::Crypt => CRYPT.encode(input),
+    }
+}
+
+pub fn decode(input: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    decode_with(input, Base64Alphabet::Standard)
+}
+
+pub fn decode_with(input: &str, alphabet: Base64Alphabet) -> Result<Vec<u8>, base64::DecodeError> {
+    match alphabet {
+        Base64Alphabet::Standard => BASE64_STANDARD.decode(input),
+        Base64Alphabet::UrlSafe => URL_SAFE.decode(input),
+        Base64Alphabet::StandardNoPad => STANDARD_NO_PAD.decode(input),
+        Base64Alphabet::UrlSafeNoPad => URL_SAFE_NO_PAD.decode(input),
+        Base64Alphabet::Mime => BASE64_STANDARD.decode(
+            input
+                .chars()
+                .filter(|v| !v.is_ascii_whitespace())
+                .collect::<String>(),
+        ),
+        Base64Alphabet::Bcrypt => BCRYPT.decode(input),
+        Base64Alphabet::Crypt => CRYPT.decode(input),
+    }
+}
+
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum CustomAlphabetError {
+    #[display("invalid alphabet: {_0}")]
+    #[from]
+    Alphabet(base64::alphabet::ParseAlphabetError),
+
+    #[display("the pad must be one ASCII character that isn't in the alphabet")]
+    Pad,
+
+    #[display("invalid base64: {_0}")]
+    #[from]
+    Decode(base64::DecodeError),
+}
+
+impl std::error::Error for CustomAlphabetError {}
+
+/// Alphabet and pad character picked by the user.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CustomAlphabet {
+    /// 64 different printable ASCII characters, for the values 0 to 63.
+    pub symbols: String,
+    /// Empty for no padding.
+    pub pad: String,
+}
+
+impl Default for CustomAlphabet {
+    fn default() -> Self {
+        Self {
+            symbols: alphabet::STANDARD.as_str().to_string(),
+            pad: "=".to_string(),
+        }
+    }
+}
+
+impl CustomAlphabet {
+    /// Engine writing `=` where the pad goes, and the pad character.
+    fn engine(&self) -> Result<(GeneralPurpose, Option<char>), CustomAlphabetError> {
+        let alphabet = Alphabet::new(&self.symbols)?;
+        let pad = match self.pad.chars().collect::<Vec<_>>()[..] {
+            [] => None,
+            [pad] if pad.is_ascii_graphic() && !self.symbols.contains(pad) => Some(pad),
+            _ => return Err(CustomAlphabetError::Pad),
+        };
+        let config = match pad {
+            Some(_) => GeneralPurposeConfig::new(),
+            None => NO_PAD,
+        };
+        Ok((GeneralPurpose::new(&alphabet, config), pad))
+    }
+
+    pub fn encode(&self, input: &[u8]) -> Result<String, CustomAlphabetError> {
+        let (engine, pad) = self.engine()?;
+        let encoded = engine.encode(input);
+        Ok(match pad {
+            Some(pad) if pad != '=' => encoded.replace('=', &pad.to_string()),
+            _ => encoded,
+        })
+    }
+
+    pub fn decode(&self, input: &str) -> Result<Vec<u8>, CustomAlphabetError> {
+        let (engine, pad) = self.engine()?;
+        let input = match pad {
+            Some(pad) if pad != '=' => {
+                // `=` isn't in the alphabet, it mustn't pass for the pad.
+                if let Some(offset) = input.find('=') {
+                    return Err(base64::DecodeError::InvalidByte(offset, b'=').into());
+                }
+                input.replace(pad, "=")
+            }
+            _ => input.to_string(),
+        };
+        Ok(engine.decode(input)?)
+    }
+}
+
+/// Base64 as pasted, without whitespace and the header of a data URI, remembering where each
+/// symbol came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Base64Text {
+    pub symbols: String,
+    /// Media type of a `data:<type>;base64,` URI, empty when the URI doesn't give one.
+    pub media_type: Option<String>,
+    /// Char offset in the input of every byte of `symbols`.
+    offsets: Vec<usize>,
+}
+
+/// Why Base64 doesn't decode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Base64Diagnostic {
+    pub message: String,
+    /// Char offset in the input of the symbol at fault.
+    pub offset: usize,
+}
+
+impl Base64Text {
+    pub fn new(input: &str) -> Self {
+        let trimmed = input.trim_start();
+        let mut start = input.len() - trimmed.len();
+        let mut media_type = None;
+        if let Some((header, _)) = trimmed.split_once(',')
+            && header
+                .get(..5)
+                .is_some_and(|v| v.eq_ignore_ascii_case("data:"))
+            && let Some(v) = header[5..].strip_suffix(";base64")
+        {
+            media_type = Some(v.to_string());
+            start += header.len() + 1;
+        }
+
+        let mut symbols = String::new();
+        let mut offsets = Vec::new();
+        let skipped = input[..start].chars().count();
+        for (i, c) in input[start..].chars().enumerate() {
+            if !c.is_whitespace() {
+                symbols.push(c);
+                offsets.extend(std::iter::repeat_n(skipped + i, c.len_utf8()));
+            }
+        }
+        Self {
+            symbols,
+            media_type,
+            offsets,
+        }
+    }
+
+    /// `-` or `_` make it URL-safe, a trailing `=` padded.
+    pub fn detect_alphabet(&self) -> Base64Alphabet {
+        let url_safe = self.symbols.contains(['-', '_']);
+        let padded = self.symbols.ends_with('=');
+        match (url_safe, padded) {
+            (false, true) => Base64Alphabet::Standard,
+            (false, false) => Base64Alphabet::StandardNoPad,
+            (true, true) => Base64Alphabet::UrlSafe,
+            (true, false) => Base64Alphabet::UrlSafeNoPad,
+        }
+    }
+
+    /// Explain `error`, got decoding `symbols`, with offsets in the input.
+    pub fn diagnose(&self, error: &base64::DecodeError) -> Base64Diagnostic {
+        let offset = |i: usize| self.offsets.get(i).or(self.offsets.last()).copied();
+        let symbol = |i: usize| self.symbols.get(i..).and_then(|v| v.chars().next());
+        let last = self.symbols.len().saturating_sub(1);
+        let (message, offset) = match *error {
+            base64::DecodeError::InvalidByte(i, b'=') => {
+                ("unexpected padding".to_string(), offset(i))
+            }
+            base64::DecodeError::InvalidByte(i, _) => (
+                format!("invalid character {:?}", symbol(i).unwrap_or_default()),
+                offset(i),
+            ),
+            base64::DecodeError::InvalidLength(n) => (
+                format!("{n} symbols, the last one doesn't make a whole byte"),
+                offset(n.saturating_sub(1)),
+            ),
+            base64::DecodeError::InvalidLastSymbol(i, _) => (
+                format!(
+                    "the unused trailing bits of {:?} aren't zero",
+                    symbol(i).unwrap_or_default()
+                ),
+                offset(i),
+            ),
+            base64::DecodeError::InvalidPadding => (
+                "missing or extra padding".to_string(),
+                offset(self.symbols.find('=').unwrap_or(last)),
+            ),
+        };
+        let offset = offset.unwrap_or_default();
+        Base64Diagnostic {
+            message: format!("{message} at offset {offset}"),
+            offset,
+        }
+    }
+}
+
+/// Render decoded bytes; invalid UTF-8 is replaced and JSON that doesn't parse is shown as is.
+pub fn format_decoded(bytes: &[u8], format: DecodedFormat) -> String {
+    match format {
+        DecodedFormat::Utf8 => String::from_utf8_lossy(bytes).to_string(),
+        DecodedFormat::Json => {
+            let s = String::from_utf8_lossy(bytes).to_string();
+            serde_json::from_str(&s)
+                .map(|v: serde_json::Value| serde_json::to_string_pretty(&v).unwrap_or(s.clone()))
+                .unwrap_or(s)
+        }
+        DecodedFormat::SimpleHex => hex::encode(bytes),
+        DecodedFormat::PrettyHex => pretty_hex::pretty_hex(&bytes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn encode_alphabets() {
+        assert_eq!(encode(b"hi?>", Base64Alphabet::Standard), "aGk/Pg==");
+        assert_eq!(encode(b"hi?>", Base64Alphabet::UrlSafe), "aGk_Pg==");
+        assert_eq!(encode(b"hi?>", Base64Alphabet::StandardNoPad), "aGk/Pg");
+        assert_eq!(encode(b"hi?>", Base64Alphabet::UrlSafeNoPad), "aGk_Pg");
+        assert_eq!(encode(b"hi?>", Base64Alphabet::Bcrypt), "YEi9Ne");
+        assert_eq!(encode(b"hi?>", Base64Alphabet::Crypt), "O4YzDU");
+
+        let mime = encode(&[0; 60], Base64Alphabet::Mime);
+        assert_eq!(
+            mime.split("\r\n").map(str::len).collect::<Vec<_>>(),
+            [76, 4]
+        );
+        for alphabet in Base64Alphabet::iter() {
+            let encoded = encode(&[0xfb; 100], alphabet);
+            assert_eq!(
+                decode_with(&encoded, alphabet).unwrap(),
+                [0xfb; 100],
+                "{alphabet}"
+            );
+        }
+        assert!(decode_with("aGk/Pg==", Base64Alphabet::StandardNoPad).is_err());
+    }
+
+    #[test]
+    fn lenient_decoding() {
+        let text = Base64Text::new(" data:image/png;base64,aGk/\r\n Pg ");
+        assert_eq!(text.symbols, "aGk/Pg");
+        assert_eq!(text.media_type.as_deref(), Some("image/png"));
+        assert_eq!(text.detect_alphabet(), Base64Alphabet::StandardNoPad);
+        assert_eq!(
+            Base64Text::new("aGk_Pg==").detect_alphabet(),
+            Base64Alphabet::UrlSafe
+        );
+
+        let diagnose = |input: &str| {
+            let text = Base64Text::new(input);
+            let error = decode_with(&text.symbols, text.detect_alphabet()).unwrap_err();
+            text.diagnose(&error)
+        };
+        assert_eq!(
+            diagnose("aGk/\nP!"),
+            Base64Diagnostic {
+                message: "invalid character '!' at offset 6".to_string(),
+                offset: 6,
+            }
+        );
+        assert_eq!(diagnose("aGé").offset, 2);
+        assert_eq!(diagnose("aGk/P").offset, 4);
+        assert_eq!(
+            diagnose("aGl=").message,
+            "the unused trailing bits of 'l' aren't zero at offset 2"
+        );
+        assert_eq!(diagnose("aG=k").message, "unexpected padding at offset 2");
+        assert_eq!(diagnose("aGk==").message, "unexpected padding at offset 3");
+        assert_eq!(
+            diagnose("aG=").message,
+            "missing or extra padding at offset 2"
+        );
+    }
+
+    #[test]
+    fn custom_alphabets() {
+        let reversed = CustomAlphabet {
+            symbols: alphabet::STANDARD.as_str().chars().rev().collect(),
+            pad: "~".to_string(),
+        };
+        let encoded = reversed.encode(b"hi").unwrap();
+        assert_eq!(encoded, "l5b~");
+        assert_eq!(reversed.decode(&encoded).unwrap(), b"hi");
+        assert!(reversed.decode("l5b=").is_err());
+
+        let unpadded = CustomAlphabet {
+            pad: String::new(),
+            ..Default::default()
+        };
+        assert_eq!(unpadded.encode(b"hi").unwrap(), "aGk");
+        assert!(matches!(
+            CustomAlphabet {
+                pad: "A".to_string(),
+                ..Default::default()
+            }
+            .encode(b"hi"),
+            Err(CustomAlphabetError::Pad)
+        ));
+        assert!(matches!(
+            CustomAlphabet {
+                symbols: "abc".to_string(),
+                ..Default::default()
+            }
+            .encode(b"hi"),
+            Err(CustomAlphabetError::Alphabet(_))
+        ));
+    }
+
+    #[test]
+    fn decode_standard() {
+        assert_eq!(decode("aGVsbG8=").unwrap(), b"hello");
+        assert!(decode("aGVsbG8").is_err());
+        assert!(decode("aGk_Pg==").is_err());
+    }
+
+    #[test]
+    fn format_json() {
+        assert_eq!(
+            format_decoded(br#"{"a":1}"#, DecodedFormat::Json),
+            "{\n  \"a\": 1\n}"
+        );
+        assert_eq!(format_decoded(b"{", DecodedFormat::Json), "{");
+        assert_eq!(
+            format_decoded(b"\x01\xff", DecodedFormat::SimpleHex),
+            "01ff"
+        );
+    }
+}
@@ -0,0 +1,781 @@
+use std::{net::IpAddr, path::Path, str::FromStr};
+
+use base64::prelude::*;
+use sha1::Digest;
+use x509_parser::{
+    error::{PEMError, X509Error},
+    extensions::{GeneralName, NameConstraints, ParsedExtension},
+    objects::{oid_registry, oid2sn},
+    prelude::*,
+};
+
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum CertificateError {
+    #[display("invalid PEM: {_0}")]
+    #[from]
+    Pem(PEMError),
+
+    #[display("no CERTIFICATE block found")]
+    NoCertificateBlock,
+
+    #[display("neither PEM nor base64 DER: {_0}")]
+    NotPemOrDer(base64::DecodeError),
+
+    #[display("invalid certificate: {_0}")]
+    #[from]
+    InvalidCertificate(x509_parser::nom::Err<X509Error>),
+
+    #[display("{_0}")]
+    #[from]
+    X509(X509Error),
+
+    #[display("no certificate to validate")]
+    NoCertificate,
+
+    #[display("invalid validation time: {_0}")]
+    #[from]
+    InvalidValidationTime(jiff::Error),
+
+    #[display("{_0} is not a directory")]
+    NotADirectory(String),
+
+    #[display("reading {path}: {error}")]
+    Io { path: String, error: std::io::Error },
+}
+
+impl std::error::Error for CertificateError {}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+pub enum TrustStoreKind {
+    Bundle,
+    Directory,
+}
+
+/// One row of the certificate tree, a field may hold several values (e.g. SANs).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CertificateField {
+    pub name: &'static str,
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CertificateInfo {
+    pub title: String,
+    pub fields: Vec<CertificateField>,
+}
+
+/// Extract DER certificates from PEM blocks, or from a single base64 or raw DER blob.
+pub fn read_certificates(input: &[u8]) -> Result<Vec<Vec<u8>>, CertificateError> {
+    if input.windows(10).any(|v| v == b"-----BEGIN") {
+        let ders = Pem::iter_from_buffer(input)
+            .filter(|v| {
+                v.as_ref()
+                    .map_or(true, |v| v.label.ends_with("CERTIFICATE"))
+            })
+            .map(|v| v.map(|v| v.contents))
+            .collect::<Result<Vec<_>, _>>()?;
+        if ders.is_empty() {
+            return Err(CertificateError::NoCertificateBlock);
+        }
+        return Ok(ders);
+    }
+
+    let compact: Vec<u8> = input
+        .iter()
+        .copied()
+        .filter(|v| !v.is_ascii_whitespace())
+        .collect();
+    match BASE64_STANDARD.decode(&compact) {
+        Ok(der) => Ok(vec![der]),
+        Err(_) if input.first() == Some(&0x30) => Ok(vec![input.to_vec()]),
+        Err(e) => Err(CertificateError::NotPemOrDer(e)),
+    }
+}
+
+pub fn der_to_pem(der: &[u8]) -> String {
+    let body = BASE64_STANDARD.encode(der);
+    let lines: Vec<_> = body
+        .as_bytes()
+        .chunks(64)
+        .map(|v| String::from_utf8_lossy(v).to_string())
+        .collect();
+    format!(
+        "-----BEGIN CERTIFICATE-----\n{}\n-----END CERTIFICATE-----\n",
+        lines.join("\n")
+    )
+}
+
+fn colon_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|v| format!("{v:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn oid_name(oid: &x509_parser::der_parser::oid::Oid) -> String {
+    oid2sn(oid, oid_registry())
+        .map(|v| v.to_string())
+        .unwrap_or_else(|_| oid.to_id_string())
+}
+
+fn format_general_name(name: &GeneralName) -> String {
+    match name {
+        GeneralName::DNSName(v) => format!("DNS: {v}"),
+        GeneralName::RFC822Name(v) => format!("Email: {v}"),
+        GeneralName::URI(v) => format!("URI: {v}"),
+        GeneralName::DirectoryName(v) => format!("DirName: {v}"),
+        GeneralName::IPAddress(v) => {
+            let ip = match v.len() {
+                4 => <[u8; 4]>::try_from(*v).ok().map(IpAddr::from),
+                16 => <[u8; 16]>::try_from(*v).ok().map(IpAddr::from),
+                _ => None,
+            };
+            match ip {
+                Some(ip) => format!("IP: {ip}"),
+                // Name constraints carry an address followed by its mask.
+                None => format!("IP: {}", colon_hex(v)),
+            }
+        }
+        v => v.to_string(),
+    }
+}
+
+fn format_time(time: &ASN1Time) -> String {
+    jiff::Timestamp::from_second(time.timestamp())
+        .map(|v| v.to_string())
+        .unwrap_or_else(|_| time.to_string())
+}
+
+fn format_remaining(not_before: &ASN1Time, not_after: &ASN1Time, now: jiff::Timestamp) -> String {
+    const DAY: i64 = 24 * 60 * 60;
+    let now = now.as_second();
+    if now < not_before.timestamp() {
+        format!(
+            "not valid yet, starts in {} days",
+            (not_before.timestamp() - now) / DAY
+        )
+    } else if now > not_after.timestamp() {
+        format!("expired {} days ago", (now - not_after.timestamp()) / DAY)
+    } else {
+        format!("{} days remaining", (not_after.timestamp() - now) / DAY)
+    }
+}
+
+fn format_public_key(spki: &SubjectPublicKeyInfo) -> String {
+    let algorithm = oid_name(&spki.algorithm.algorithm);
+    match spki.parsed() {
+        Ok(key) if key.key_size() > 0 => format!("{algorithm} ({} bits)", key.key_size()),
+        _ => algorithm,
+    }
+}
+
+fn format_extended_key_usage(eku: &ExtendedKeyUsage) -> Vec<String> {
+    [
+        (eku.any, "Any"),
+        (eku.server_auth, "TLS Web Server Authentication"),
+        (eku.client_auth, "TLS Web Client Authentication"),
+        (eku.code_signing, "Code Signing"),
+        (eku.email_protection, "E-mail Protection"),
+        (eku.time_stamping, "Time Stamping"),
+        (eku.ocsp_signing, "OCSP Signing"),
+    ]
+    .into_iter()
+    .filter(|(set, _)| *set)
+    .map(|(_, name)| name.to_string())
+    .chain(eku.other.iter().map(oid_name))
+    .collect()
+}
+
+/// Critical extensions are marked so they stand out in the tree.
+fn critical_marker(critical: bool) -> &'static str {
+    if critical { " (critical)" } else { "" }
+}
+
+pub fn inspect_certificate(
+    der: &[u8],
+    now: jiff::Timestamp,
+) -> Result<CertificateInfo, CertificateError> {
+    let (_, cert) = X509Certificate::from_der(der)?;
+
+    let field = |name, values: Vec<String>| CertificateField { name, values };
+    let validity = cert.validity();
+    let mut fields = vec![
+        field("Version", vec![(cert.version().0 + 1).to_string()]),
+        field("Subject", vec![cert.subject().to_string()]),
+        field("Issuer", vec![cert.issuer().to_string()]),
+        field("Serial", vec![cert.raw_serial_as_string().to_uppercase()]),
+        field("Not Before", vec![format_time(&validity.not_before)]),
+        field("Not After", vec![format_time(&validity.not_after)]),
+        field(
+            "Validity",
+            vec![format_remaining(
+                &validity.not_before,
+                &validity.not_after,
+                now,
+            )],
+        ),
+        field("Public Key", vec![format_public_key(cert.public_key())]),
+        field(
+            "Signature Algorithm",
+            vec![oid_name(&cert.signature_algorithm.algorithm)],
+        ),
+    ];
+
+    for ext in cert.extensions() {
+        let critical = critical_marker(ext.critical);
+        match ext.parsed_extension() {
+            ParsedExtension::SubjectAlternativeName(san) => fields.push(field(
+                "Subject Alternative Names",
+                san.general_names.iter().map(format_general_name).collect(),
+            )),
+            ParsedExtension::KeyUsage(ku) => {
+                fields.push(field("Key Usage", vec![format!("{ku}{critical}")]))
+            }
+            ParsedExtension::ExtendedKeyUsage(eku) => {
+                fields.push(field("Extended Key Usage", format_extended_key_usage(eku)))
+            }
+            ParsedExtension::BasicConstraints(bc) => fields.push(field(
+                "Basic Constraints",
+                vec![format!(
+                    "CA: {}{}{critical}",
+                    bc.ca,
+                    bc.path_len_constraint
+                        .map(|v| format!(", path length: {v}"))
+                        .unwrap_or_default()
+                )],
+            )),
+            ParsedExtension::AuthorityKeyIdentifier(aki) => {
+                if let Some(id) = &aki.key_identifier {
+                    fields.push(field("Authority Key Identifier", vec![colon_hex(id.0)]));
+                }
+            }
+            ParsedExtension::SubjectKeyIdentifier(ski) => {
+                fields.push(field("Subject Key Identifier", vec![colon_hex(ski.0)]))
+            }
+            _ => {}
+        }
+    }
+
+    fields.push(field(
+        "SHA-1 Fingerprint",
+        vec![colon_hex(&sha1::Sha1::digest(der))],
+    ));
+    fields.push(field(
+        "SHA-256 Fingerprint",
+        vec![colon_hex(&sha2::Sha256::digest(der))],
+    ));
+
+    let title = cert
+        .subject()
+        .iter_common_name()
+        .next()
+        .and_then(|v| v.as_str().ok())
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| cert.subject().to_string());
+
+    Ok(CertificateInfo { title, fields })
+}
+
+/// Load every certificate found in the files of `directory`, skipping anything unparsable.
+fn load_trust_directory(directory: &str) -> Result<Vec<Vec<u8>>, CertificateError> {
+    let mut anchors = vec![];
+    let io_error = |error| CertificateError::Io {
+        path: directory.to_string(),
+        error,
+    };
+    for entry in std::fs::read_dir(directory).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if !path.is_file() {
+            continue;
+        }
+        if let Some(Ok(ders)) = std::fs::read(&path).ok().map(|v| read_certificates(&v)) {
+            anchors.extend(ders);
+        }
+    }
+    // Hash symlinks such as `002c0b4f.0` point to files that are also listed by name.
+    anchors.sort();
+    anchors.dedup();
+    Ok(anchors)
+}
+
+/// Parse the validation instant, either RFC 3339 or a civil date time in the system time zone.
+pub fn parse_validation_time(time: &str) -> Result<jiff::Timestamp, CertificateError> {
+    let time = time.trim();
+    if time.is_empty() {
+        return Ok(jiff::Timestamp::now());
+    }
+    if let Ok(timestamp) = jiff::Timestamp::from_str(time) {
+        return Ok(timestamp);
+    }
+    let datetime = jiff::civil::DateTime::from_str(time)?;
+    Ok(datetime.to_zoned(jiff::tz::TimeZone::system())?.timestamp())
+}
+
+/// Match `hostname` against a SAN DNS name, allowing a wildcard in the left-most label only.
+fn dns_name_matches(pattern: &str, hostname: &str) -> bool {
+    let pattern = pattern.trim_end_matches('.').to_ascii_lowercase();
+    let hostname = hostname.trim_end_matches('.').to_ascii_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => hostname
+            .split_once('.')
+            .is_some_and(|(label, rest)| !label.is_empty() && rest == suffix),
+        None => pattern == hostname,
+    }
+}
+
+/// Whether a DNS name lies within a name constraint subtree (RFC 5280 section 4.2.1.10).
+fn dns_name_in_subtree(name: &str, base: &str) -> bool {
+    let name = name.trim_end_matches('.').to_ascii_lowercase();
+    let base = base.to_ascii_lowercase();
+    if base.is_empty() {
+        return true;
+    }
+    match base.strip_prefix('.') {
+        Some(domain) => name.ends_with(&format!(".{domain}")),
+        None => name == base || name.ends_with(&format!(".{base}")),
+    }
+}
+
+/// Whether an IP address lies within a name constraint subtree given as address followed by mask.
+fn ip_in_subtree(ip: &[u8], subtree: &[u8]) -> bool {
+    let (address, mask) = subtree.split_at(subtree.len() / 2);
+    ip.len() == address.len()
+        && ip
+            .iter()
+            .zip(address.iter().zip(mask))
+            .all(|(ip, (address, mask))| ip & mask == address & mask)
+}
+
+fn subject_alternative_names<'a>(cert: &'a X509Certificate) -> Vec<&'a GeneralName<'a>> {
+    cert.subject_alternative_name()
+        .ok()
+        .flatten()
+        .map(|v| v.value.general_names.iter().collect())
+        .unwrap_or_default()
+}
+
+/// Check the DNS and IP names of `cert` against the constraints of one of its issuers.
+fn check_name_constraints(
+    cert: &X509Certificate,
+    constraints: &NameConstraints,
+) -> Result<(), String> {
+    let in_subtree = |name: &GeneralName, base: &GeneralName| match (name, base) {
+        (GeneralName::DNSName(name), GeneralName::DNSName(base)) => {
+            Some(dns_name_in_subtree(name, base))
+        }
+        (GeneralName::IPAddress(ip), GeneralName::IPAddress(subtree)) => {
+            Some(ip_in_subtree(ip, subtree))
+        }
+        _ => None,
+    };
+
+    for name in subject_alternative_names(cert) {
+        let excluded = constraints.excluded_subtrees.iter().flatten();
+        if excluded
+            .into_iter()
+            .any(|v| in_subtree(name, &v.base) == Some(true))
+        {
+            return Err(format!("{} is excluded", format_general_name(name)));
+        }
+        if let Some(permitted) = &constraints.permitted_subtrees {
+            let applicable: Vec<_> = permitted
+                .iter()
+                .filter_map(|v| in_subtree(name, &v.base))
+                .collect();
+            if !applicable.is_empty() && !applicable.contains(&true) {
+                return Err(format!("{} is not permitted", format_general_name(name)));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Outcome of a single check on a certificate of the validated path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationCheck {
+    pub certificate: String,
+    pub check: &'static str,
+    pub result: Result<String, String>,
+}
+
+fn certificate_title(cert: &X509Certificate) -> String {
+    cert.subject()
+        .iter_common_name()
+        .next()
+        .and_then(|v| v.as_str().ok())
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| cert.subject().to_string())
+}
+
+/// Whether `issuer` issued `cert`, by name and by signature.
+fn is_issued_by(cert: &X509Certificate, issuer: &X509Certificate) -> bool {
+    cert.issuer().as_raw() == issuer.subject().as_raw()
+        && cert.verify_signature(Some(issuer.public_key())).is_ok()
+}
+
+/// Build a path from the leaf (first certificate of `chain`) up to one of `anchors`,
+/// then check every certificate of that path at `time`.
+pub fn validate_chain(
+    chain: &[Vec<u8>],
+    anchors: &[Vec<u8>],
+    time: jiff::Timestamp,
+    hostname: &str,
+) -> Result<Vec<ValidationCheck>, CertificateError> {
+    /// Same limit as OpenSSL's default verify depth.
+    const MAX_DEPTH: usize = 100;
+
+    let parse = |der| X509Certificate::from_der(der).map(|(_, v)| v);
+    let chain = chain
+        .iter()
+        .map(|v| parse(v))
+        .collect::<Result<Vec<_>, _>>()?;
+    let anchors: Vec<_> = anchors.iter().filter_map(|v| parse(v).ok()).collect();
+    let leaf = chain.first().ok_or(CertificateError::NoCertificate)?;
+
+    // Path building: prefer issuers from the pasted chain, stop at the first trust anchor.
+    let mut path = vec![leaf];
+    let mut trusted = anchors.contains(leaf);
+    while !trusted && path.len() < MAX_DEPTH {
+        let current = *path.last().unwrap();
+        if let Some(anchor) = anchors.iter().find(|v| is_issued_by(current, v)) {
+            path.push(anchor);
+            trusted = true;
+        } else if let Some(issuer) = chain
+            .iter()
+            .find(|v| !path.contains(v) && is_issued_by(current, v))
+        {
+            path.push(issuer);
+        } else {
+            break;
+        }
+    }
+
+    let mut checks = vec![];
+    let asn1_time = ASN1Time::from_timestamp(time.as_second())?;
+    for (depth, cert) in path.iter().enumerate() {
+        let title = format!("#{depth} {}", certificate_title(cert));
+        let mut check = |check, result| {
+            checks.push(ValidationCheck {
+                certificate: title.clone(),
+                check,
+                result,
+            })
+        };
+
+        let validity = cert.validity();
+        check(
+            "Validity",
+            if cert.validity().is_valid_at(asn1_time) {
+                Ok(format!("valid at {time}"))
+            } else {
+                Err(format!(
+                    "not valid at {time}, valid from {} to {}",
+                    format_time(&validity.not_before),
+                    format_time(&validity.not_after)
+                ))
+            },
+        );
+
+        match path.get(depth + 1) {
+            Some(issuer) => {
+                check("Issuer", Ok(format!("issued by {}", issuer.subject())));
+                check(
+                    "Signature",
+                    Ok(format!(
+                        "{} verified with the issuer key",
+                        oid_name(&cert.signature_algorithm.algorithm)
+                    )),
+                );
+            }
+            None if trusted => check("Trust", Ok("trust anchor".to_string())),
+            None if cert.issuer().as_raw() == cert.subject().as_raw() => check(
+                "Trust",
+                Err("self-signed certificate not in the trust store".to_string()),
+            ),
+            None => check("Issuer", Err(format!("issuer {} not found", cert.issuer()))),
+        }
+
+        if depth == 0 {
+            if !hostname.trim().is_empty() {
+                let hostname = hostname.trim();
+                let ip = IpAddr::from_str(hostname).ok();
+                let matched = subject_alternative_names(cert)
+                    .into_iter()
+                    .any(|name| match name {
+                        GeneralName::DNSName(pattern) => {
+                            ip.is_none() && dns_name_matches(pattern, hostname)
+                        }
+                        GeneralName::IPAddress(bytes) => ip.is_some_and(|ip| match ip {
+                            IpAddr::V4(v) => v.octets() == **bytes,
+                            IpAddr::V6(v) => v.octets() == **bytes,
+                        }),
+                        _ => false,
+                    });
+                check(
+                    "Hostname",
+                    if matched {
+                        Ok(format!("{hostname} matches a subject alternative name"))
+                    } else {
+                        Err(format!("{hostname} matches no subject alternative name"))
+                    },
+                );
+            }
+            continue;
+        }
+
+        // Everything below only applies to issuing certificates.
+        let is_ca = cert
+            .basic_constraints()
+            .ok()
+            .flatten()
+            .is_some_and(|v| v.value.ca);
+        let can_sign = cert
+            .key_usage()
+            .ok()
+            .flatten()
+            .is_none_or(|v| v.value.key_cert_sign());
+        check(
+            "CA",
+            match (is_ca, can_sign) {
+                (true, true) => Ok("may issue certificates".to_string()),
+                (false, _) => Err("basic constraints do not allow a CA".to_string()),
+                (true, false) => Err("key usage does not allow certificate signing".to_string()),
+            },
+        );
+
+        let path_len = cert
+            .basic_constraints()
+            .ok()
+            .flatten()
+            .and_then(|v| v.value.path_len_constraint);
+        if let Some(path_len) = path_len {
+            // Intermediates between this certificate and the leaf.
+            let intermediates = depth - 1;
+            check(
+                "Path Length",
+                if intermediates <= path_len as usize {
+                    Ok(format!(
+                        "{intermediates} of at most {path_len} intermediates"
+                    ))
+                } else {
+                    Err(format!(
+                        "{intermediates} intermediates exceed the limit of {path_len}"
+                    ))
+                },
+            );
+        }
+
+        if let Ok(Some(constraints)) = cert.name_constraints() {
+            let result = path[..depth]
+                .iter()
+                .try_for_each(|v| check_name_constraints(v, constraints.value));
+            check(
+                "Name Constraints",
+                result.map(|_| "names of subordinate certificates are permitted".to_string()),
+            );
+        }
+    }
+
+    Ok(checks)
+}
+
+/// Read the trust anchors from a pasted PEM bundle or from a directory.
+pub fn load_trust_anchors(
+    kind: TrustStoreKind,
+    bundle: &str,
+    directory: &str,
+) -> Result<Vec<Vec<u8>>, CertificateError> {
+    match kind {
+        TrustStoreKind::Bundle => read_certificates(bundle.as_bytes()),
+        TrustStoreKind::Directory => {
+            if !Path::new(directory).is_dir() {
+                return Err(CertificateError::NotADirectory(directory.to_string()));
+            }
+            load_trust_directory(directory)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Self-signed P-256 CA, valid from 2026-10-18 for 20 years, at most 0 intermediates.
+    const ROOT_CA: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBljCCATygAwIBAgIUGMuEPvybkzYcfyeGYonxpkGlafswCgYIKoZIzj0EAwIw
+FzEVMBMGA1UEAwwMVGVzdCBSb290IENBMB4XDTI2MTAxODA3MDYyMVoXDTQ2MTAx
+MzA3MDYyMVowFzEVMBMGA1UEAwwMVGVzdCBSb290IENBMFkwEwYHKoZIzj0CAQYI
+KoZIzj0DAQcDQgAEJPZCeG4f1CssLVb49Oov067p9/JHKdK+wAgESB+OXEE8I1bo
+szCH7AomgBlTz6MG0jEO6ndauP4v5sCopRXMM6NmMGQwHQYDVR0OBBYEFFoZ9h0c
+ykLXMU36S89MjWXuaaEIMB8GA1UdIwQYMBaAFFoZ9h0cykLXMU36S89MjWXuaaEI
+MBIGA1UdEwEB/wQIMAYBAf8CAQAwDgYDVR0PAQH/BAQDAgEGMAoGCCqGSM49BAMC
+A0gAMEUCIQDBZGlf+OUG3K9N8nUD4YhCQzt9nSlENvDBQ4l1zyec6gIgDB7m0D7t
+LVCQdrfS0EiQZ7Uj00qEi1pKP80qWxWL7mg=
+-----END CERTIFICATE-----
+";
+
+    /// `example.com` issued by `ROOT_CA`, valid from 2026-10-18 for 10 years.
+    const LEAF: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBrjCCAVSgAwIBAgICEjQwCgYIKoZIzj0EAwIwFzEVMBMGA1UEAwwMVGVzdCBS
+b290IENBMB4XDTI2MTAxODA3MDYyMVoXDTM2MTAxNTA3MDYyMVowFjEUMBIGA1UE
+AwwLZXhhbXBsZS5jb20wWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAATBBjq3Oewg
+aBHDf8gQCsa7127z1AoJZrdImHkdWp7kOab7whbLIBEyxLMYr6gr4jYec49dnakg
+7VSGm0evNSGPo4GQMIGNMCsGA1UdEQQkMCKCC2V4YW1wbGUuY29tgg0qLmV4YW1w
+bGUuY29thwR/AAABMAkGA1UdEwQCMAAwEwYDVR0lBAwwCgYIKwYBBQUHAwEwHQYD
+VR0OBBYEFAseK7hfPJYaVIUTA4UBTlIJ6LeyMB8GA1UdIwQYMBaAFFoZ9h0cykLX
+MU36S89MjWXuaaEIMAoGCCqGSM49BAMCA0gAMEUCIQDElocWbfYCTQdsEyAwmKGc
+sucbCGI3lFTYnuxflQ7yuwIgGU60j/6G9Pf+WjViJtLXOiDM5ysG1ImAOTYQlcfp
+F+s=
+-----END CERTIFICATE-----
+";
+
+    fn field<'a>(info: &'a CertificateInfo, name: &str) -> &'a [String] {
+        &info.fields.iter().find(|v| v.name == name).unwrap().values
+    }
+
+    #[test]
+    fn inspect_fields() {
+        let der = read_certificates(LEAF.as_bytes()).unwrap();
+        let now = parse_validation_time("2030-01-01T00:00:00Z").unwrap();
+        let info = inspect_certificate(&der[0], now).unwrap();
+        assert_eq!(info.title, "example.com");
+        assert_eq!(field(&info, "Version"), ["3"]);
+        assert_eq!(field(&info, "Issuer"), ["CN=Test Root CA"]);
+        assert_eq!(field(&info, "Serial"), ["12:34"]);
+        assert_eq!(field(&info, "Not Before"), ["2026-10-18T07:06:21Z"]);
+        assert_eq!(field(&info, "Validity"), ["2479 days remaining"]);
+        assert_eq!(
+            field(&info, "Subject Alternative Names"),
+            ["DNS: example.com", "DNS: *.example.com", "IP: 127.0.0.1"]
+        );
+        assert_eq!(field(&info, "Basic Constraints"), ["CA: false"]);
+        assert_eq!(
+            field(&info, "Extended Key Usage"),
+            ["TLS Web Server Authentication"]
+        );
+        assert_eq!(
+            field(&info, "SHA-256 Fingerprint"),
+            [
+                "A6:79:BA:61:6D:2D:28:CF:D8:39:26:F3:95:11:81:37:0B:12:8F:E8:0F:4F:C9:24:8C:B2:43:D8:F5:03:32:46"
+            ]
+        );
+    }
+
+    #[test]
+    fn read_input() {
+        let der = vec![0x30, 0x03, 0x02, 0x01, 0x01];
+        let pem = der_to_pem(&der);
+        for input in [pem.as_bytes(), b"MAMCAQE=", &der] {
+            assert_eq!(read_certificates(input).unwrap(), [&der[..]]);
+        }
+        assert!(matches!(
+            read_certificates(b"-----BEGIN PUBLIC KEY-----\nMAMCAQE=\n-----END PUBLIC KEY-----\n"),
+            Err(CertificateError::NoCertificateBlock)
+        ));
+        assert!(matches!(
+            read_certificates(b"not a certificate"),
+            Err(CertificateError::NotPemOrDer(_))
+        ));
+    }
+
+    #[test]
+    fn validate_path() {
+        let chain = read_certificates(LEAF.as_bytes()).unwrap();
+        let anchors = read_certificates(ROOT_CA.as_bytes()).unwrap();
+        let validate = |anchors: &[Vec<u8>], time, hostname| {
+            let time = parse_validation_time(time).unwrap();
+            validate_chain(&chain, anchors, time, hostname)
+                .unwrap()
+                .into_iter()
+                .map(|v| (v.certificate, v.check, v.result.is_ok()))
+                .collect::<Vec<_>>()
+        };
+        let leaf = || "#0 example.com".to_string();
+        let root = || "#1 Test Root CA".to_string();
+
+        assert_eq!(
+            validate(&anchors, "2030-01-01T00:00:00Z", "www.example.com"),
+            [
+                (leaf(), "Validity", true),
+                (leaf(), "Issuer", true),
+                (leaf(), "Signature", true),
+                (leaf(), "Hostname", true),
+                (root(), "Validity", true),
+                (root(), "Trust", true),
+                (root(), "CA", true),
+                (root(), "Path Length", true),
+            ]
+        );
+        assert_eq!(
+            validate(&anchors, "2040-01-01T00:00:00Z", "a.b.example.com"),
+            [
+                (leaf(), "Validity", false),
+                (leaf(), "Issuer", true),
+                (leaf(), "Signature", true),
+                (leaf(), "Hostname", false),
+                (root(), "Validity", true),
+                (root(), "Trust", true),
+                (root(), "CA", true),
+                (root(), "Path Length", true),
+            ]
+        );
+        assert_eq!(
+            validate(&[], "2030-01-01T00:00:00Z", "127.0.0.1"),
+            [
+                (leaf(), "Validity", true),
+                (leaf(), "Issuer", false),
+                (leaf(), "Hostname", true),
+            ]
+        );
+        assert!(matches!(
+            validate_chain(&[], &anchors, jiff::Timestamp::UNIX_EPOCH, ""),
+            Err(CertificateError::NoCertificate)
+        ));
+    }
+
+    #[test]
+    fn hostname_wildcards() {
+        assert!(dns_name_matches("*.example.com", "www.Example.com."));
+        assert!(!dns_name_matches("*.example.com", "example.com"));
+        assert!(!dns_name_matches("*.example.com", "a.b.example.com"));
+        assert!(dns_name_matches("example.com", "example.com"));
+    }
+
+    #[test]
+    fn name_constraint_subtrees() {
+        assert!(dns_name_in_subtree("a.example.com", "example.com"));
+        assert!(dns_name_in_subtree("example.com", "example.com"));
+        assert!(!dns_name_in_subtree("example.com", ".example.com"));
+        assert!(!dns_name_in_subtree("badexample.com", "example.com"));
+        assert!(ip_in_subtree(&[10, 1, 2, 3], &[10, 0, 0, 0, 255, 0, 0, 0]));
+        assert!(!ip_in_subtree(&[11, 1, 2, 3], &[10, 0, 0, 0, 255, 0, 0, 0]));
+    }
+
+    #[test]
+    fn validation_time() {
+        assert_eq!(
+            parse_validation_time("2024-01-01T00:00:00Z")
+                .unwrap()
+                .as_second(),
+            1704067200
+        );
+        assert!(matches!(
+            parse_validation_time("soon"),
+            Err(CertificateError::InvalidValidationTime(_))
+        ));
+    }
+}
@@ -0,0 +1,270 @@
+//! Non-cryptographic hashes without a maintained streaming crate, see `core::hash` for the
+//! others.
+
+/// 32-bit FNV-1a.
+#[derive(Debug, Clone)]
+pub struct Fnv1a32(u32);
+
+impl Default for Fnv1a32 {
+    fn default() -> Self {
+        Self(0x811c9dc5)
+    }
+}
+
+impl Fnv1a32 {
+    pub fn update(&mut self, input: &[u8]) {
+        for &byte in input {
+            self.0 = (self.0 ^ byte as u32).wrapping_mul(0x01000193);
+        }
+    }
+
+    pub fn finalize(&self) -> u32 {
+        self.0
+    }
+}
+
+/// 64-bit FNV-1a.
+#[derive(Debug, Clone)]
+pub struct Fnv1a64(u64);
+
+impl Default for Fnv1a64 {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Fnv1a64 {
+    pub fn update(&mut self, input: &[u8]) {
+        for &byte in input {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    pub fn finalize(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Bytes not yet making a whole block, kept between two updates.
+#[derive(Debug, Clone)]
+struct Blocks<const N: usize> {
+    pending: [u8; N],
+    pending_len: usize,
+    total: u64,
+}
+
+impl<const N: usize> Default for Blocks<N> {
+    fn default() -> Self {
+        Self {
+            pending: [0; N],
+            pending_len: 0,
+            total: 0,
+        }
+    }
+}
+
+impl<const N: usize> Blocks<N> {
+    /// Call `block` with every whole block of `input`, after the pending bytes.
+    fn update(&mut self, mut input: &[u8], mut block: impl FnMut(&[u8; N])) {
+        self.total += input.len() as u64;
+        if self.pending_len > 0 {
+            let take = (N - self.pending_len).min(input.len());
+            self.pending[self.pending_len..self.pending_len + take].copy_from_slice(&input[..take]);
+            self.pending_len += take;
+            input = &input[take..];
+            if self.pending_len < N {
+                return;
+            }
+            block(&self.pending);
+            self.pending_len = 0;
+        }
+        let mut chunks = input.chunks_exact(N);
+        for chunk in &mut chunks {
+            block(chunk.try_into().unwrap());
+        }
+        let tail = chunks.remainder();
+        self.pending[..tail.len()].copy_from_slice(tail);
+        self.pending_len = tail.len();
+    }
+
+    fn tail(&self) -> &[u8] {
+        &self.pending[..self.pending_len]
+    }
+}
+
+/// Little-endian integer of up to 8 bytes.
+fn read_le(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .fold(0, |value, &byte| (value << 8) | byte as u64)
+}
+
+/// MurmurHash3 x86_32 with a zero seed.
+#[derive(Debug, Clone, Default)]
+pub struct Murmur3_32 {
+    h: u32,
+    blocks: Blocks<4>,
+}
+
+impl Murmur3_32 {
+    const C1: u32 = 0xcc9e2d51;
+    const C2: u32 = 0x1b873593;
+
+    fn mix(k: u32) -> u32 {
+        k.wrapping_mul(Self::C1)
+            .rotate_left(15)
+            .wrapping_mul(Self::C2)
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        let h = &mut self.h;
+        self.blocks.update(input, |block| {
+            *h ^= Self::mix(u32::from_le_bytes(*block));
+            *h = h.rotate_left(13).wrapping_mul(5).wrapping_add(0xe6546b64);
+        });
+    }
+
+    pub fn finalize(&self) -> u32 {
+        let mut h = self.h;
+        let tail = self.blocks.tail();
+        if !tail.is_empty() {
+            h ^= Self::mix(read_le(tail) as u32);
+        }
+        h ^= self.blocks.total as u32;
+        h ^= h >> 16;
+        h = h.wrapping_mul(0x85ebca6b);
+        h ^= h >> 13;
+        h = h.wrapping_mul(0xc2b2ae35);
+        h ^ (h >> 16)
+    }
+}
+
+/// MurmurHash3 x64_128 with a zero seed.
+#[derive(Debug, Clone, Default)]
+pub struct Murmur3_128 {
+    h1: u64,
+    h2: u64,
+    blocks: Blocks<16>,
+}
+
+impl Murmur3_128 {
+    const C1: u64 = 0x87c37b91114253d5;
+    const C2: u64 = 0x4cf5ad432745937f;
+
+    fn mix_k1(k1: u64) -> u64 {
+        k1.wrapping_mul(Self::C1)
+            .rotate_left(31)
+            .wrapping_mul(Self::C2)
+    }
+
+    fn mix_k2(k2: u64) -> u64 {
+        k2.wrapping_mul(Self::C2)
+            .rotate_left(33)
+            .wrapping_mul(Self::C1)
+    }
+
+    fn fmix(mut k: u64) -> u64 {
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xff51afd7ed558ccd);
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xc4ceb9fe1a85ec53);
+        k ^ (k >> 33)
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        let (h1, h2) = (&mut self.h1, &mut self.h2);
+        self.blocks.update(input, |block| {
+            *h1 ^= Self::mix_k1(read_le(&block[..8]));
+            *h1 = h1
+                .rotate_left(27)
+                .wrapping_add(*h2)
+                .wrapping_mul(5)
+                .wrapping_add(0x52dce729);
+            *h2 ^= Self::mix_k2(read_le(&block[8..]));
+            *h2 = h2
+                .rotate_left(31)
+                .wrapping_add(*h1)
+                .wrapping_mul(5)
+                .wrapping_add(0x38495ab5);
+        });
+    }
+
+    /// `h1` then `h2`, both little-endian, as the reference implementation writes them.
+    pub fn finalize(&self) -> [u8; 16] {
+        let (mut h1, mut h2) = (self.h1, self.h2);
+        let tail = self.blocks.tail();
+        if tail.len() > 8 {
+            h2 ^= Self::mix_k2(read_le(&tail[8..]));
+        }
+        if !tail.is_empty() {
+            h1 ^= Self::mix_k1(read_le(&tail[..tail.len().min(8)]));
+        }
+        h1 ^= self.blocks.total;
+        h2 ^= self.blocks.total;
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+        h1 = Self::fmix(h1);
+        h2 = Self::fmix(h2);
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+
+        let mut output = [0; 16];
+        output[..8].copy_from_slice(&h1.to_le_bytes());
+        output[8..].copy_from_slice(&h2.to_le_bytes());
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOX: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    #[test]
+    fn fnv1a() {
+        let mut fnv32 = Fnv1a32::default();
+        let mut fnv64 = Fnv1a64::default();
+        assert_eq!(fnv32.finalize(), 0x811c9dc5);
+        fnv32.update(b"foo");
+        fnv32.update(b"bar");
+        fnv64.update(b"foobar");
+        assert_eq!(fnv32.finalize(), 0xbf9cf968);
+        assert_eq!(fnv64.finalize(), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn murmur3() {
+        let mut murmur = Murmur3_32::default();
+        assert_eq!(murmur.finalize(), 0);
+        murmur.update(b"hello");
+        assert_eq!(murmur.finalize(), 0x248bfa47);
+
+        let mut murmur = Murmur3_128::default();
+        murmur.update(FOX);
+        assert_eq!(
+            hex::encode(murmur.finalize()),
+            "6c1b07bc7bbc4be347939ac4a93c437a"
+        );
+    }
+
+    #[test]
+    fn murmur3_in_pieces() {
+        let mut whole32 = Murmur3_32::default();
+        let mut whole128 = Murmur3_128::default();
+        whole32.update(FOX);
+        whole128.update(FOX);
+        for split in 0..FOX.len() {
+            let mut murmur32 = Murmur3_32::default();
+            let mut murmur128 = Murmur3_128::default();
+            for piece in [&FOX[..split], &FOX[split..]] {
+                murmur32.update(piece);
+                murmur128.update(piece);
+            }
+            assert_eq!(murmur32.finalize(), whole32.finalize());
+            assert_eq!(murmur128.finalize(), whole128.finalize());
+        }
+        assert_eq!(whole32.finalize(), 0x2e4ff723);
+    }
+}
@@ -0,0 +1,656 @@
+use std::{str::FromStr, sync::LazyLock};
+
+use jiff::{
+    RoundMode, Span, SpanRound, Timestamp, Unit, Zoned, ZonedDifference, ZonedRound,
+    civil::{DateTime, ISOWeekDate, Time, Weekday},
+    fmt::{
+        friendly::{Designator, Direction, Spacing, SpanPrinter},
+        rfc2822,
+    },
+    tz::TimeZone,
+};
+use regex::Regex;
+
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum DateTimeError {
+    #[display("unrecognized date/time format")]
+    UnrecognizedFormat,
+
+    #[display("Unix time out of range")]
+    OutOfRange,
+
+    #[display("invalid ISO 8601 week date")]
+    InvalidWeekDate,
+
+    #[display("{_0}")]
+    Jiff(jiff::Error),
+}
+
+impl std::error::Error for DateTimeError {}
+
+/// `Sun, 06 Nov 1994 08:49:37 GMT`, the preferred format of RFC 9110.
+static HTTP_DATE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[A-Z][a-z]{2}, \d{2} [A-Z][a-z]{2} \d{4} \d{2}:\d{2}:\d{2} GMT$").unwrap()
+});
+
+/// `2025-W02-3` or `2025W023`, optionally followed by `T` and a time of day.
+static ISO_WEEK_DATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(-?\d{4})-?W(\d{2})-?([1-7])(?:[T ](.+))?$").unwrap());
+
+/// Format detected by [`parse_datetime`].
+#[derive(Debug, Clone, Copy, PartialEq, strum_macros::Display)]
+pub enum DateTimeInputFormat {
+    #[strum(to_string = "Unix seconds")]
+    UnixSeconds,
+
+    #[strum(to_string = "Unix milliseconds")]
+    UnixMilliseconds,
+
+    #[strum(to_string = "Unix microseconds")]
+    UnixMicroseconds,
+
+    #[strum(to_string = "Unix nanoseconds")]
+    UnixNanoseconds,
+
+    #[strum(to_string = "RFC 3339")]
+    Rfc3339,
+
+    #[strum(to_string = "RFC 9557")]
+    Rfc9557,
+
+    #[strum(to_string = "RFC 2822")]
+    Rfc2822,
+
+    #[strum(to_string = "HTTP date")]
+    HttpDate,
+
+    #[strum(to_string = "ISO 8601 week date")]
+    IsoWeekDate,
+
+    #[strum(to_string = "Local date time")]
+    Local,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+)]
+pub enum DateTimeUnit {
+    Years,
+    Months,
+    Weeks,
+    Days,
+    Hours,
+    Minutes,
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl From<DateTimeUnit> for Unit {
+    fn from(value: DateTimeUnit) -> Self {
+        match value {
+            DateTimeUnit::Years => Unit::Year,
+            DateTimeUnit::Months => Unit::Month,
+            DateTimeUnit::Weeks => Unit::Week,
+            DateTimeUnit::Days => Unit::Day,
+            DateTimeUnit::Hours => Unit::Hour,
+            DateTimeUnit::Minutes => Unit::Minute,
+            DateTimeUnit::Seconds => Unit::Second,
+            DateTimeUnit::Milliseconds => Unit::Millisecond,
+            DateTimeUnit::Microseconds => Unit::Microsecond,
+            DateTimeUnit::Nanoseconds => Unit::Nanosecond,
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+)]
+pub enum DateTimeRoundMode {
+    #[strum(to_string = "Half expand")]
+    HalfExpand,
+    Trunc,
+    Floor,
+    Ceil,
+    Expand,
+}
+
+impl From<DateTimeRoundMode> for RoundMode {
+    fn from(value: DateTimeRoundMode) -> Self {
+        match value {
+            DateTimeRoundMode::HalfExpand => RoundMode::HalfExpand,
+            DateTimeRoundMode::Trunc => RoundMode::Trunc,
+            DateTimeRoundMode::Floor => RoundMode::Floor,
+            DateTimeRoundMode::Ceil => RoundMode::Ceil,
+            DateTimeRoundMode::Expand => RoundMode::Expand,
+        }
+    }
+}
+
+/// Interpret an integer as Unix time, guessing the unit from its magnitude.
+fn parse_unix(input: &str) -> Option<Result<(Timestamp, DateTimeInputFormat), DateTimeError>> {
+    if let Ok(value) = input.parse::<i128>() {
+        let (format, nanos_per_unit) = match value.unsigned_abs() {
+            0..100_000_000_000 => (DateTimeInputFormat::UnixSeconds, 1_000_000_000),
+            100_000_000_000..100_000_000_000_000 => {
+                (DateTimeInputFormat::UnixMilliseconds, 1_000_000)
+            }
+            100_000_000_000_000..100_000_000_000_000_000 => {
+                (DateTimeInputFormat::UnixMicroseconds, 1_000)
+            }
+            _ => (DateTimeInputFormat::UnixNanoseconds, 1),
+        };
+        let timestamp = value
+            .checked_mul(nanos_per_unit)
+            .ok_or(DateTimeError::OutOfRange)
+            .and_then(|v| Ok(Timestamp::from_nanosecond(v)?));
+        return Some(timestamp.map(|v| (v, format)));
+    }
+
+    // Fractional seconds such as `1736942400.25`.
+    let (seconds, fraction) = input.split_once('.')?;
+    if !fraction.chars().all(|v| v.is_ascii_digit()) || fraction.is_empty() {
+        return None;
+    }
+    let seconds = seconds.parse::<i64>().ok()?;
+    let nanos = format!("{fraction:0<9}")[..9].parse::<i32>().ok()?;
+    let nanos = if input.starts_with('-') {
+        -nanos
+    } else {
+        nanos
+    };
+    Some(
+        Timestamp::new(seconds, nanos)
+            .map(|v| (v, DateTimeInputFormat::UnixSeconds))
+            .map_err(DateTimeError::from),
+    )
+}
+
+fn parse_iso_week_date(input: &str, tz: &TimeZone) -> Option<Result<Zoned, DateTimeError>> {
+    let captures = ISO_WEEK_DATE_REGEX.captures(input)?;
+    let zoned = (|| {
+        let invalid = |_| DateTimeError::InvalidWeekDate;
+        let year = captures[1].parse().map_err(invalid)?;
+        let week = captures[2].parse().map_err(invalid)?;
+        let weekday = Weekday::from_monday_one_offset(captures[3].parse().map_err(invalid)?)?;
+        let date = ISOWeekDate::new(year, week, weekday)?.date();
+        let time = match captures.get(4) {
+            Some(v) => Time::from_str(v.as_str())?,
+            None => Time::midnight(),
+        };
+        Ok(date.to_datetime(time).to_zoned(tz.clone())?)
+    })();
+    Some(zoned)
+}
+
+/// Detect the format of `input` and parse it into an instant.
+///
+/// Inputs without an offset are interpreted in `tz`, and every result except RFC 9557 (which
+/// carries its own time zone) is returned in `tz`.
+pub fn parse_datetime(
+    input: &str,
+    tz: &TimeZone,
+) -> Result<(Zoned, DateTimeInputFormat), DateTimeError> {
+    let input = input.trim();
+    if let Some(result) = parse_unix(input) {
+        return result.map(|(timestamp, format)| (timestamp.to_zoned(tz.clone()), format));
+    }
+    if let Ok(zoned) = Zoned::from_str(input) {
+        return Ok((zoned, DateTimeInputFormat::Rfc9557));
+    }
+    if let Ok(timestamp) = Timestamp::from_str(input) {
+        return Ok((timestamp.to_zoned(tz.clone()), DateTimeInputFormat::Rfc3339));
+    }
+    if let Ok(zoned) = rfc2822::parse(input) {
+        let format = if HTTP_DATE_REGEX.is_match(input) {
+            DateTimeInputFormat::HttpDate
+        } else {
+            DateTimeInputFormat::Rfc2822
+        };
+        return Ok((zoned.with_time_zone(tz.clone()), format));
+    }
+    if let Some(zoned) = parse_iso_week_date(input, tz) {
+        return Ok((zoned?, DateTimeInputFormat::IsoWeekDate));
+    }
+    if let Ok(datetime) = DateTime::from_str(input) {
+        let zoned = datetime.to_zoned(tz.clone())?;
+        return Ok((zoned, DateTimeInputFormat::Local));
+    }
+    Err(DateTimeError::UnrecognizedFormat)
+}
+
+fn format_iso_week_date(zoned: &Zoned) -> String {
+    let week = zoned.date().iso_week_date();
+    format!(
+        "{:04}-W{:02}-{}T{}",
+        week.year(),
+        week.week(),
+        week.weekday().to_monday_one_offset(),
+        zoned.strftime("%H:%M:%S%:z")
+    )
+}
+
+/// Describe `zoned` relative to `now` with its two most significant units, e.g. `3 days 4 hours ago`.
+fn format_relative(zoned: &Zoned, now: &Zoned) -> Result<String, DateTimeError> {
+    const UNITS: [Unit; 7] = [
+        Unit::Year,
+        Unit::Month,
+        Unit::Week,
+        Unit::Day,
+        Unit::Hour,
+        Unit::Minute,
+        Unit::Second,
+    ];
+
+    let span = now.until((Unit::Year, zoned))?;
+    let leading = UNITS
+        .iter()
+        .position(|unit| span_unit(&span, *unit) != 0)
+        .unwrap_or(UNITS.len() - 1);
+    let smallest = UNITS[(leading + 1).min(UNITS.len() - 1)];
+    let span = span.round(
+        SpanRound::new()
+            .largest(UNITS[leading])
+            .smallest(smallest)
+            .mode(RoundMode::HalfExpand)
+            .relative(now),
+    )?;
+
+    let text = SpanPrinter::new()
+        .designator(Designator::Verbose)
+        .direction(Direction::Suffix)
+        .spacing(Spacing::BetweenUnitsAndDesignators)
+        .span_to_string(&span);
+    Ok(if span.is_positive() {
+        format!("in {text}")
+    } else if span.is_zero() {
+        "now".to_string()
+    } else {
+        text
+    })
+}
+
+fn span_unit(span: &Span, unit: Unit) -> i64 {
+    match unit {
+        Unit::Year => span.get_years().into(),
+        Unit::Month => span.get_months().into(),
+        Unit::Week => span.get_weeks().into(),
+        Unit::Day => span.get_days().into(),
+        Unit::Hour => span.get_hours().into(),
+        Unit::Minute => span.get_minutes(),
+        Unit::Second => span.get_seconds(),
+        Unit::Millisecond => span.get_milliseconds(),
+        Unit::Microsecond => span.get_microseconds(),
+        Unit::Nanosecond => span.get_nanoseconds(),
+    }
+}
+
+/// Human readable date and time with offset and time zone abbreviation.
+fn format_zoned(zoned: &Zoned) -> String {
+    zoned
+        .strftime("%Y-%m-%d %H:%M:%S%.f %:z %Z (%A)")
+        .to_string()
+}
+
+/// All representations of `timestamp`, as (name, value) rows.
+pub fn datetime_rows(
+    timestamp: Timestamp,
+    pinned_zones: &[String],
+) -> Result<Vec<(String, String)>, DateTimeError> {
+    let utc = timestamp.to_zoned(TimeZone::UTC);
+    let local = timestamp.to_zoned(TimeZone::system());
+    let now = Zoned::now().with_time_zone(TimeZone::system());
+    let week = local.date().iso_week_date();
+    let local_name = local
+        .time_zone()
+        .iana_name()
+        .map(|v| format!("Local ({v})"))
+        .unwrap_or("Local".to_string());
+
+    let mut rows = vec![
+        (
+            "Unix seconds".to_string(),
+            timestamp.as_second().to_string(),
+        ),
+        (
+            "Unix milliseconds".to_string(),
+            timestamp.as_millisecond().to_string(),
+        ),
+        (
+            "Unix microseconds".to_string(),
+            timestamp.as_microsecond().to_string(),
+        ),
+        (
+            "Unix nanoseconds".to_string(),
+            timestamp.as_nanosecond().to_string(),
+        ),
+        ("RFC 3339".to_string(), timestamp.to_string()),
+        ("RFC 2822".to_string(), rfc2822::to_string(&local)?),
+        ("ISO 8601 week date".to_string(), format_iso_week_date(&utc)),
+        (
+            "HTTP date".to_string(),
+            rfc2822::DateTimePrinter::new().timestamp_to_rfc9110_string(&timestamp)?,
+        ),
+        ("UTC".to_string(), format_zoned(&utc)),
+        (local_name, format_zoned(&local)),
+    ];
+    for zone in pinned_zones {
+        let value = match timestamp.in_tz(zone) {
+            Ok(zoned) => format_zoned(&zoned),
+            Err(e) => e.to_string(),
+        };
+        rows.push((zone.clone(), value));
+    }
+    rows.extend([
+        ("Relative".to_string(), format_relative(&local, &now)?),
+        ("Day of year".to_string(), local.day_of_year().to_string()),
+        (
+            "ISO week".to_string(),
+            format!("{} of {}", week.week(), week.year()),
+        ),
+    ]);
+    Ok(rows)
+}
+
+/// Parse `input` and move it into `zone`, or keep its own time zone if `zone` is empty.
+pub fn parse_zoned(input: &str, zone: &str) -> Result<Zoned, DateTimeError> {
+    let zone = zone.trim();
+    if zone.is_empty() {
+        return Ok(parse_datetime(input, &TimeZone::system())?.0);
+    }
+    let tz = TimeZone::get(zone)?;
+    Ok(parse_datetime(input, &tz)?.0.with_time_zone(tz))
+}
+
+fn format_span(span: &Span) -> String {
+    SpanPrinter::new()
+        .designator(Designator::Verbose)
+        .spacing(Spacing::BetweenUnitsAndDesignators)
+        .span_to_string(span)
+}
+
+/// Note about a UTC offset transition (usually DST) between `start` and `end`.
+fn offset_change_row(start: &Zoned, end: &Zoned) -> Option<(String, String)> {
+    (start.offset() != end.offset()).then(|| {
+        (
+            "Offset change".to_string(),
+            format!("{} → {}", start.strftime("%:z"), end.strftime("%:z")),
+        )
+    })
+}
+
+/// Add (or subtract when `negate` is set) `span` to `start`, rounding the result to `smallest`.
+pub fn add_rows(
+    start: &Zoned,
+    span: Span,
+    negate: bool,
+    smallest: Unit,
+    mode: RoundMode,
+) -> Result<Vec<(String, String)>, DateTimeError> {
+    let span = if negate { span.negate() } else { span };
+    let mut result = start.checked_add(span)?;
+    if smallest != Unit::Nanosecond {
+        result = result.round(ZonedRound::new().smallest(smallest).mode(mode))?;
+    }
+
+    let mut rows = vec![
+        ("RFC 9557".to_string(), result.to_string()),
+        ("Result".to_string(), format_zoned(&result)),
+        (
+            "UTC".to_string(),
+            format_zoned(&result.with_time_zone(TimeZone::UTC)),
+        ),
+        (
+            "Unix seconds".to_string(),
+            result.timestamp().as_second().to_string(),
+        ),
+    ];
+    rows.extend(offset_change_row(start, &result));
+    Ok(rows)
+}
+
+/// Calendar-aware difference from `start` to `end`, balanced up to `largest`.
+pub fn difference_rows(
+    start: &Zoned,
+    end: &Zoned,
+    largest: Unit,
+    smallest: Unit,
+    mode: RoundMode,
+) -> Result<Vec<(String, String)>, DateTimeError> {
+    let end = end.with_time_zone(start.time_zone().clone());
+    let span = start.until(
+        ZonedDifference::new(&end)
+            .largest(largest)
+            .smallest(smallest)
+            .mode(mode),
+    )?;
+
+    let mut rows = vec![
+        ("ISO 8601".to_string(), span.to_string()),
+        ("Friendly".to_string(), format_span(&span)),
+    ];
+    for (name, unit) in [
+        ("Total days", Unit::Day),
+        ("Total hours", Unit::Hour),
+        ("Total minutes", Unit::Minute),
+        ("Total seconds", Unit::Second),
+    ] {
+        rows.push((name.to_string(), span.total((unit, start))?.to_string()));
+    }
+    rows.extend(offset_change_row(start, &end));
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value<'a>(rows: &'a [(String, String)], name: &str) -> &'a str {
+        &rows.iter().find(|(n, _)| n == name).unwrap().1
+    }
+
+    #[test]
+    fn parse_formats() {
+        let cases = [
+            ("1700000000", DateTimeInputFormat::UnixSeconds),
+            ("1700000000000", DateTimeInputFormat::UnixMilliseconds),
+            ("2023-11-14T22:13:20Z", DateTimeInputFormat::Rfc3339),
+            (
+                "2023-11-14T17:13:20-05:00[America/New_York]",
+                DateTimeInputFormat::Rfc9557,
+            ),
+            (
+                "Tue, 14 Nov 2023 22:13:20 +0000",
+                DateTimeInputFormat::Rfc2822,
+            ),
+            (
+                "Tue, 14 Nov 2023 22:13:20 GMT",
+                DateTimeInputFormat::HttpDate,
+            ),
+        ];
+        for (input, format) in cases {
+            let (zoned, detected) = parse_datetime(input, &TimeZone::UTC).unwrap();
+            assert_eq!(detected, format, "{input}");
+            assert_eq!(zoned.timestamp().as_second(), 1700000000, "{input}");
+        }
+        assert!(matches!(
+            parse_datetime("yesterday-ish", &TimeZone::UTC),
+            Err(DateTimeError::UnrecognizedFormat)
+        ));
+    }
+
+    #[test]
+    fn parse_unix_units() {
+        let parse = |input| {
+            let (timestamp, format) = parse_unix(input).unwrap().unwrap();
+            (timestamp.as_nanosecond(), format)
+        };
+        assert_eq!(
+            parse("1700000000123"),
+            (
+                1_700_000_000_123_000_000,
+                DateTimeInputFormat::UnixMilliseconds
+            )
+        );
+        assert_eq!(
+            parse("1700000000123456"),
+            (
+                1_700_000_000_123_456_000,
+                DateTimeInputFormat::UnixMicroseconds
+            )
+        );
+        assert_eq!(
+            parse("1700000000123456789"),
+            (
+                1_700_000_000_123_456_789,
+                DateTimeInputFormat::UnixNanoseconds
+            )
+        );
+        assert_eq!(
+            parse("1736942400.25"),
+            (1_736_942_400_250_000_000, DateTimeInputFormat::UnixSeconds)
+        );
+        assert_eq!(parse("-1.5").0, -1_500_000_000);
+        assert!(parse_unix("1.").is_none());
+        assert!(
+            parse_unix("99999999999999999999999999999999999999")
+                .unwrap()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn representations() {
+        let timestamp = Timestamp::from_second(1700000000).unwrap();
+        let rows = datetime_rows(
+            timestamp,
+            &["Asia/Tokyo".to_string(), "Nowhere".to_string()],
+        )
+        .unwrap();
+        assert_eq!(value(&rows, "Unix milliseconds"), "1700000000000");
+        assert_eq!(value(&rows, "RFC 3339"), "2023-11-14T22:13:20Z");
+        assert_eq!(
+            value(&rows, "ISO 8601 week date"),
+            "2023-W46-2T22:13:20+00:00"
+        );
+        assert_eq!(value(&rows, "HTTP date"), "Tue, 14 Nov 2023 22:13:20 GMT");
+        assert_eq!(
+            value(&rows, "UTC"),
+            "2023-11-14 22:13:20 +00:00 UTC (Tuesday)"
+        );
+        assert_eq!(
+            value(&rows, "Asia/Tokyo"),
+            "2023-11-15 07:13:20 +09:00 JST (Wednesday)"
+        );
+        assert!(value(&rows, "Nowhere").contains("Nowhere"));
+        assert!(value(&rows, "Relative").ends_with(" ago"));
+    }
+
+    #[test]
+    fn relative() {
+        let now = parse_zoned("2024-01-10T12:00", "UTC").unwrap();
+        let relative = |input| format_relative(&parse_zoned(input, "UTC").unwrap(), &now).unwrap();
+        assert_eq!(relative("2024-01-10T12:00"), "now");
+        assert_eq!(relative("2024-01-07T08:00"), "3 days 4 hours ago");
+        assert_eq!(relative("2024-03-10T12:29"), "in 2 months");
+    }
+
+    #[test]
+    fn parse_week_date() {
+        let (zoned, format) = parse_datetime("2025-W02-3", &TimeZone::UTC).unwrap();
+        assert_eq!(format, DateTimeInputFormat::IsoWeekDate);
+        assert_eq!(zoned.date(), jiff::civil::date(2025, 1, 8));
+        assert!(parse_datetime("2025-W54-1", &TimeZone::UTC).is_err());
+    }
+
+    #[test]
+    fn add_across_dst() {
+        let start = parse_zoned("2024-03-09T12:00", "America/New_York").unwrap();
+        let rows = add_rows(
+            &start,
+            "1 day".parse().unwrap(),
+            false,
+            Unit::Nanosecond,
+            RoundMode::HalfExpand,
+        )
+        .unwrap();
+        assert_eq!(
+            value(&rows, "RFC 9557"),
+            "2024-03-10T12:00:00-04:00[America/New_York]"
+        );
+        assert_eq!(value(&rows, "Offset change"), "-05:00 → -04:00");
+    }
+
+    #[test]
+    fn add_rounded() {
+        let start = parse_zoned("2024-01-31T10:40", "UTC").unwrap();
+        let add = |span: &str, negate, smallest, mode| {
+            let rows = add_rows(&start, span.parse().unwrap(), negate, smallest, mode).unwrap();
+            value(&rows, "RFC 9557").to_string()
+        };
+        // Adding a month clamps to the end of February.
+        assert_eq!(
+            add("1 month", false, Unit::Nanosecond, RoundMode::HalfExpand),
+            "2024-02-29T10:40:00+00:00[UTC]"
+        );
+        assert_eq!(
+            add("PT1H", true, Unit::Hour, RoundMode::Floor),
+            "2024-01-31T09:00:00+00:00[UTC]"
+        );
+        assert_eq!(
+            add("PT1H", true, Unit::Hour, RoundMode::Ceil),
+            "2024-01-31T10:00:00+00:00[UTC]"
+        );
+    }
+
+    #[test]
+    fn difference_units() {
+        let start = parse_zoned("2024-01-01T00:00", "UTC").unwrap();
+        let end = parse_zoned("2024-03-01T06:30:00+01:00", "").unwrap();
+        let rows = |largest, smallest, mode| {
+            difference_rows(&start, &end, largest, smallest, mode).unwrap()
+        };
+        let months = rows(Unit::Year, Unit::Nanosecond, RoundMode::HalfExpand);
+        assert_eq!(value(&months, "ISO 8601"), "P2MT5H30M");
+        assert_eq!(value(&months, "Total days"), "60.229166666666664");
+        let hours = rows(Unit::Hour, Unit::Hour, RoundMode::HalfExpand);
+        assert_eq!(value(&hours, "ISO 8601"), "PT1446H");
+        assert!(matches!(
+            parse_zoned("2024-01-01T00:00", "Mars/Olympus"),
+            Err(DateTimeError::Jiff(_))
+        ));
+    }
+
+    #[test]
+    fn difference_across_dst() {
+        let start = parse_zoned("2024-03-09T12:00", "America/New_York").unwrap();
+        let end = parse_zoned("2024-03-10T12:00", "America/New_York").unwrap();
+        let rows = difference_rows(
+            &start,
+            &end,
+            Unit::Year,
+            Unit::Nanosecond,
+            RoundMode::HalfExpand,
+        )
+        .unwrap();
+        assert_eq!(value(&rows, "ISO 8601"), "P1D");
+        assert_eq!(value(&rows, "Total hours"), "23");
+    }
+}
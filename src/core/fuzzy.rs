@@ -0,0 +1,65 @@
+/// Score how well `query` matches `candidate`, higher is better, `None` if it doesn't match.
+///
+/// Every whitespace-separated word of `query` has to appear in `candidate` as a case-insensitive
+/// subsequence, in any order, so "jwt decode" matches "Decode JWT". Characters matched at the
+/// start of a word or right after the previous match score more.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let candidate = candidate.chars().collect::<Vec<_>>();
+    query
+        .split_whitespace()
+        .map(|word| word_score(word, &candidate))
+        .sum()
+}
+
+/// Best score of `word` over every way of matching it in `candidate`.
+fn word_score(word: &str, candidate: &[char]) -> Option<u32> {
+    let word_start = |index: usize| {
+        index == 0
+            || !candidate[index - 1].is_alphanumeric()
+            || (candidate[index - 1].is_lowercase() && candidate[index].is_uppercase())
+    };
+
+    // `best[j]`: best score of the word so far with its last character matched at `j`.
+    let mut best = vec![None; candidate.len()];
+    for (i, c) in word.chars().enumerate() {
+        let mut next = vec![None; candidate.len()];
+        for j in 0..candidate.len() {
+            if !candidate[j].to_lowercase().eq(c.to_lowercase()) {
+                continue;
+            }
+            let previous = if i == 0 {
+                Some(0)
+            } else {
+                (0..j)
+                    .filter_map(|k| Some(best[k]? + if k + 1 == j { 4 } else { 0 }))
+                    .max()
+            };
+            next[j] = previous.map(|v| v + 1 + if word_start(j) { 8 } else { 0 });
+        }
+        best = next;
+    }
+    best.into_iter().flatten().max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_subsequences() {
+        assert!(fuzzy_score("", "Hash").is_some());
+        assert!(fuzzy_score("hsh", "Hash").is_some());
+        assert!(fuzzy_score("HASH", "hash").is_some());
+        assert!(fuzzy_score("jwt decode", "Decode JWT").is_some());
+        assert!(fuzzy_score("hahs", "Hash").is_none());
+        assert!(fuzzy_score("jwt x", "Decode JWT").is_none());
+    }
+
+    #[test]
+    fn prefers_word_starts_and_runs() {
+        let score = |candidate| fuzzy_score("dt", candidate).unwrap();
+        assert!(score("DateTime") > score("Validate certificate"));
+        assert!(score("Data transfer") > score("Dotted"));
+        assert!(fuzzy_score("zoom", "Zoom in") > fuzzy_score("zoom", "Zero room"));
+    }
+}
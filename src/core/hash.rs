@@ -0,0 +1,958 @@
+use std::{io::Read, ops::ControlFlow};
+
+use base64::{
+    Engine,
+    engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE_NO_PAD},
+};
+use digest::{Digest, core_api::BlockSizeUser};
+use hmac::{Mac, SimpleHmac, digest::KeyInit};
+use strum::IntoEnumIterator;
+use subtle::ConstantTimeEq;
+
+use crate::core::checksum;
+
+/// Bytes read at a time by [`hash_reader`].
+const CHUNK_SIZE: usize = 1 << 20;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+)]
+pub enum HashAlgorithm {
+    #[strum(to_string = "MD5")]
+    Md5,
+
+    #[strum(to_string = "SHA1")]
+    Sha1,
+
+    #[strum(to_string = "SHA2-224")]
+    Sha224,
+
+    #[strum(to_string = "SHA2-256")]
+    Sha256,
+
+    #[strum(to_string = "SHA2-384")]
+    Sha384,
+
+    #[strum(to_string = "SHA2-512")]
+    Sha512,
+
+    #[strum(to_string = "SHA2-512/224")]
+    Sha512_224,
+
+    #[strum(to_string = "SHA2-512/256")]
+    Sha512_256,
+
+    #[strum(to_string = "SHA3-224")]
+    Sha3_224,
+
+    #[strum(to_string = "SHA3-256")]
+    Sha3_256,
+
+    #[strum(to_string = "SHA3-384")]
+    Sha3_384,
+
+    #[strum(to_string = "SHA3-512")]
+    Sha3_512,
+
+    /// Keccak before the SHA-3 padding change, as used by Ethereum.
+    #[strum(to_string = "Keccak-256")]
+    Keccak256,
+
+    #[strum(to_string = "SHAKE128")]
+    Shake128,
+
+    #[strum(to_string = "SHAKE256")]
+    Shake256,
+
+    #[strum(to_string = "BLAKE2b-512")]
+    Blake2b512,
+
+    #[strum(to_string = "BLAKE2s-256")]
+    Blake2s256,
+
+    #[strum(to_string = "BLAKE3")]
+    Blake3,
+
+    #[strum(to_string = "RIPEMD-160")]
+    Ripemd160,
+
+    #[strum(to_string = "SM3")]
+    Sm3,
+
+    Whirlpool,
+
+    #[strum(to_string = "CRC-32")]
+    Crc32,
+
+    #[strum(to_string = "CRC-32C")]
+    Crc32c,
+
+    /// CRC-64/XZ, as used by xz and 7-Zip.
+    #[strum(to_string = "CRC-64/XZ")]
+    Crc64,
+
+    #[strum(to_string = "Adler-32")]
+    Adler32,
+
+    #[strum(to_string = "XXH32")]
+    Xxh32,
+
+    #[strum(to_string = "XXH64")]
+    Xxh64,
+
+    #[strum(to_string = "XXH3-64")]
+    Xxh3_64,
+
+    #[strum(to_string = "XXH3-128")]
+    Xxh3_128,
+
+    #[strum(to_string = "FNV-1a 32")]
+    Fnv1a32,
+
+    #[strum(to_string = "FNV-1a 64")]
+    Fnv1a64,
+
+    #[strum(to_string = "MurmurHash3 x86_32")]
+    Murmur3_32,
+
+    #[strum(to_string = "MurmurHash3 x64_128")]
+    Murmur3_128,
+}
+
+/// Incremental hash of any [`HashAlgorithm`].
+pub trait Hasher {
+    fn update(&mut self, input: &[u8]);
+    fn finalize(self: Box<Self>) -> Vec<u8>;
+}
+
+struct DigestHasher<D>(D);
+
+impl<D: Digest> Hasher for DigestHasher<D> {
+    fn update(&mut self, input: &[u8]) {
+        Digest::update(&mut self.0, input);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        self.0.finalize().to_vec()
+    }
+}
+
+/// Extendable-output function producing `size` bytes.
+struct XofHasher<D> {
+    xof: D,
+    size: usize,
+}
+
+impl<D: digest::Update + digest::ExtendableOutput> Hasher for XofHasher<D> {
+    fn update(&mut self, input: &[u8]) {
+        self.xof.update(input);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        let mut output = vec![0; self.size];
+        self.xof.finalize_xof_into(&mut output);
+        output
+    }
+}
+
+/// Checksums are shown as big-endian integers, as their own tools print them.
+macro_rules! checksum_hasher {
+    ($type:ty, $state:ident => $update:expr, $finalize:expr) => {
+        impl Hasher for $type {
+            fn update(&mut self, input: &[u8]) {
+                let $state = self;
+                $update(input);
+            }
+
+            fn finalize(self: Box<Self>) -> Vec<u8> {
+                let $state = *self;
+                $finalize.to_be_bytes().to_vec()
+            }
+        }
+    };
+}
+
+checksum_hasher!(crc::Digest<'static, u32>, v => |input| v.update(input), v.finalize());
+checksum_hasher!(crc::Digest<'static, u64>, v => |input| v.update(input), v.finalize());
+checksum_hasher!(adler2::Adler32, v => |input| v.write_slice(input), v.checksum());
+checksum_hasher!(xxhash_rust::xxh32::Xxh32, v => |input| v.update(input), v.digest());
+checksum_hasher!(xxhash_rust::xxh64::Xxh64, v => |input| v.update(input), v.digest());
+checksum_hasher!(checksum::Fnv1a32, v => |input| v.update(input), v.finalize());
+checksum_hasher!(checksum::Fnv1a64, v => |input| v.update(input), v.finalize());
+checksum_hasher!(checksum::Murmur3_32, v => |input| v.update(input), v.finalize());
+
+/// XXH3 gives 64 or 128 bits from the same state.
+struct Xxh3Hasher {
+    state: Box<xxhash_rust::xxh3::Xxh3>,
+    wide: bool,
+}
+
+impl Hasher for Xxh3Hasher {
+    fn update(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        if self.wide {
+            self.state.digest128().to_be_bytes().to_vec()
+        } else {
+            self.state.digest().to_be_bytes().to_vec()
+        }
+    }
+}
+
+impl Hasher for blake3::Hasher {
+    fn update(&mut self, input: &[u8]) {
+        blake3::Hasher::update(self, input);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        blake3::Hasher::finalize(&self).as_bytes().to_vec()
+    }
+}
+
+impl Hasher for checksum::Murmur3_128 {
+    fn update(&mut self, input: &[u8]) {
+        checksum::Murmur3_128::update(self, input);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        checksum::Murmur3_128::finalize(&self).to_vec()
+    }
+}
+
+static CRC_32: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+static CRC_32C: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISCSI);
+static CRC_64: crc::Crc<u64> = crc::Crc::<u64>::new(&crc::CRC_64_XZ);
+
+impl HashAlgorithm {
+    pub fn hasher(&self) -> Box<dyn Hasher> {
+        self.hasher_with_size(self.output_size())
+    }
+
+    /// Hasher producing `size` bytes, which only extendable-output functions take into account.
+    pub fn hasher_with_size(&self, size: usize) -> Box<dyn Hasher> {
+        fn digest<D: Digest + 'static>() -> Box<dyn Hasher> {
+            Box::new(DigestHasher(D::new()))
+        }
+
+        match self {
+            HashAlgorithm::Md5 => digest::<md5::Md5>(),
+            HashAlgorithm::Sha1 => digest::<sha1::Sha1>(),
+            HashAlgorithm::Sha224 => digest::<sha2::Sha224>(),
+            HashAlgorithm::Sha256 => digest::<sha2::Sha256>(),
+            HashAlgorithm::Sha384 => digest::<sha2::Sha384>(),
+            HashAlgorithm::Sha512 => digest::<sha2::Sha512>(),
+            HashAlgorithm::Sha512_224 => digest::<sha2::Sha512_224>(),
+            HashAlgorithm::Sha512_256 => digest::<sha2::Sha512_256>(),
+            HashAlgorithm::Sha3_224 => digest::<sha3::Sha3_224>(),
+            HashAlgorithm::Sha3_256 => digest::<sha3::Sha3_256>(),
+            HashAlgorithm::Sha3_384 => digest::<sha3::Sha3_384>(),
+            HashAlgorithm::Sha3_512 => digest::<sha3::Sha3_512>(),
+            HashAlgorithm::Keccak256 => digest::<sha3::Keccak256>(),
+            HashAlgorithm::Shake128 => Box::new(XofHasher {
+                xof: sha3::Shake128::default(),
+                size,
+            }),
+            HashAlgorithm::Shake256 => Box::new(XofHasher {
+                xof: sha3::Shake256::default(),
+                size,
+            }),
+            HashAlgorithm::Blake2b512 => digest::<blake2::Blake2b512>(),
+            HashAlgorithm::Blake2s256 => digest::<blake2::Blake2s256>(),
+            HashAlgorithm::Blake3 => Box::new(blake3::Hasher::new()),
+            HashAlgorithm::Ripemd160 => digest::<ripemd::Ripemd160>(),
+            HashAlgorithm::Sm3 => digest::<sm3::Sm3>(),
+            HashAlgorithm::Whirlpool => digest::<whirlpool::Whirlpool>(),
+            HashAlgorithm::Crc32 => Box::new(CRC_32.digest()),
+            HashAlgorithm::Crc32c => Box::new(CRC_32C.digest()),
+            HashAlgorithm::Crc64 => Box::new(CRC_64.digest()),
+            HashAlgorithm::Adler32 => Box::new(adler2::Adler32::new()),
+            HashAlgorithm::Xxh32 => Box::new(xxhash_rust::xxh32::Xxh32::new(0)),
+            HashAlgorithm::Xxh64 => Box::new(xxhash_rust::xxh64::Xxh64::new(0)),
+            HashAlgorithm::Xxh3_64 | HashAlgorithm::Xxh3_128 => Box::new(Xxh3Hasher {
+                state: Box::new(xxhash_rust::xxh3::Xxh3::new()),
+                wide: *self == HashAlgorithm::Xxh3_128,
+            }),
+            HashAlgorithm::Fnv1a32 => Box::new(checksum::Fnv1a32::default()),
+            HashAlgorithm::Fnv1a64 => Box::new(checksum::Fnv1a64::default()),
+            HashAlgorithm::Murmur3_32 => Box::new(checksum::Murmur3_32::default()),
+            HashAlgorithm::Murmur3_128 => Box::new(checksum::Murmur3_128::default()),
+        }
+    }
+
+    /// Digest size in bytes, the default one for extendable-output functions.
+    pub fn output_size(&self) -> usize {
+        match self {
+            HashAlgorithm::Crc32
+            | HashAlgorithm::Crc32c
+            | HashAlgorithm::Adler32
+            | HashAlgorithm::Xxh32
+            | HashAlgorithm::Fnv1a32
+            | HashAlgorithm::Murmur3_32 => 4,
+            HashAlgorithm::Crc64
+            | HashAlgorithm::Xxh64
+            | HashAlgorithm::Xxh3_64
+            | HashAlgorithm::Fnv1a64 => 8,
+            HashAlgorithm::Md5 | HashAlgorithm::Xxh3_128 | HashAlgorithm::Murmur3_128 => 16,
+            HashAlgorithm::Sha1 | HashAlgorithm::Ripemd160 => 20,
+            HashAlgorithm::Sha224 | HashAlgorithm::Sha512_224 | HashAlgorithm::Sha3_224 => 28,
+            HashAlgorithm::Sha256
+            | HashAlgorithm::Sha512_256
+            | HashAlgorithm::Sha3_256
+            | HashAlgorithm::Keccak256
+            | HashAlgorithm::Shake128
+            | HashAlgorithm::Blake2s256
+            | HashAlgorithm::Blake3
+            | HashAlgorithm::Sm3 => 32,
+            HashAlgorithm::Sha384 | HashAlgorithm::Sha3_384 => 48,
+            HashAlgorithm::Sha512
+            | HashAlgorithm::Sha3_512
+            | HashAlgorithm::Shake256
+            | HashAlgorithm::Blake2b512
+            | HashAlgorithm::Whirlpool => 64,
+        }
+    }
+
+    /// Whether the output size can be picked, see [`HashAlgorithm::hasher_with_size`].
+    pub fn is_xof(&self) -> bool {
+        matches!(self, HashAlgorithm::Shake128 | HashAlgorithm::Shake256)
+    }
+
+    /// Checksums and hashes for hash tables, easy to collide on purpose.
+    pub fn is_cryptographic(&self) -> bool {
+        !matches!(
+            self,
+            HashAlgorithm::Crc32
+                | HashAlgorithm::Crc32c
+                | HashAlgorithm::Crc64
+                | HashAlgorithm::Adler32
+                | HashAlgorithm::Xxh32
+                | HashAlgorithm::Xxh64
+                | HashAlgorithm::Xxh3_64
+                | HashAlgorithm::Xxh3_128
+                | HashAlgorithm::Fnv1a32
+                | HashAlgorithm::Fnv1a64
+                | HashAlgorithm::Murmur3_32
+                | HashAlgorithm::Murmur3_128
+        )
+    }
+
+    /// Whether [`hmac`] is defined for the algorithm.
+    pub fn has_hmac(&self) -> bool {
+        self.is_cryptographic() && !self.is_xof() && *self != HashAlgorithm::Blake3
+    }
+
+    /// Code of the algorithm in the multicodec table, used by multihashes.
+    pub fn multicodec(&self) -> Option<u64> {
+        Some(match self {
+            HashAlgorithm::Md5 => 0xd5,
+            HashAlgorithm::Sha1 => 0x11,
+            HashAlgorithm::Sha224 => 0x1013,
+            HashAlgorithm::Sha256 => 0x12,
+            HashAlgorithm::Sha384 => 0x20,
+            HashAlgorithm::Sha512 => 0x13,
+            HashAlgorithm::Sha512_224 => 0x1014,
+            HashAlgorithm::Sha512_256 => 0x1015,
+            HashAlgorithm::Sha3_224 => 0x17,
+            HashAlgorithm::Sha3_256 => 0x16,
+            HashAlgorithm::Sha3_384 => 0x15,
+            HashAlgorithm::Sha3_512 => 0x14,
+            HashAlgorithm::Keccak256 => 0x1b,
+            HashAlgorithm::Shake128 => 0x18,
+            HashAlgorithm::Shake256 => 0x19,
+            HashAlgorithm::Blake2b512 => 0xb240,
+            HashAlgorithm::Blake2s256 => 0xb260,
+            HashAlgorithm::Blake3 => 0x1e,
+            HashAlgorithm::Ripemd160 => 0x1053,
+            HashAlgorithm::Sm3 => 0x534d,
+            _ => return None,
+        })
+    }
+
+    /// Rows shown by the Hash tool until the user picks others.
+    pub fn defaults() -> Vec<HashAlgorithm> {
+        vec![
+            HashAlgorithm::Md5,
+            HashAlgorithm::Sha1,
+            HashAlgorithm::Sha224,
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Sha384,
+            HashAlgorithm::Sha512,
+            HashAlgorithm::Sha512_224,
+            HashAlgorithm::Sha512_256,
+            HashAlgorithm::Sha3_224,
+            HashAlgorithm::Sha3_256,
+            HashAlgorithm::Sha3_384,
+            HashAlgorithm::Sha3_512,
+        ]
+    }
+}
+
+pub fn hash(algorithm: HashAlgorithm, input: &[u8]) -> Vec<u8> {
+    let mut hasher = algorithm.hasher();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// How the HMAC key typed by the user is turned into bytes.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+pub enum HmacKeyKind {
+    #[strum(to_string = "UTF-8")]
+    Utf8,
+    Hex,
+    Base64,
+}
+
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum HmacKeyError {
+    #[display("invalid hex: {_0}")]
+    #[from]
+    Hex(hex::FromHexError),
+
+    #[display("invalid base64: {_0}")]
+    #[from]
+    Base64(base64::DecodeError),
+}
+
+impl std::error::Error for HmacKeyError {}
+
+impl HmacKeyKind {
+    /// Convert `key` to bytes; hex and base64 may contain whitespace.
+    pub fn decode(self, key: &str) -> Result<Vec<u8>, HmacKeyError> {
+        let compact = || key.split_whitespace().collect::<String>();
+        Ok(match self {
+            HmacKeyKind::Utf8 => key.as_bytes().to_vec(),
+            HmacKeyKind::Hex => hex::decode(compact())?,
+            HmacKeyKind::Base64 => crate::core::base64::decode(&compact())?,
+        })
+    }
+}
+
+fn mac<D: Digest + BlockSizeUser>(key: &[u8], input: &[u8]) -> Vec<u8> {
+    let mut mac =
+        <SimpleHmac<D> as KeyInit>::new_from_slice(key).expect("HMAC takes keys of any size");
+    mac.update(input);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// HMAC of `input`, `None` for the algorithms it isn't defined for: extendable-output
+/// functions, BLAKE3 and the non-cryptographic ones.
+pub fn hmac(algorithm: HashAlgorithm, key: &[u8], input: &[u8]) -> Option<Vec<u8>> {
+    Some(match algorithm {
+        HashAlgorithm::Md5 => mac::<md5::Md5>(key, input),
+        HashAlgorithm::Sha1 => mac::<sha1::Sha1>(key, input),
+        HashAlgorithm::Sha224 => mac::<sha2::Sha224>(key, input),
+        HashAlgorithm::Sha256 => mac::<sha2::Sha256>(key, input),
+        HashAlgorithm::Sha384 => mac::<sha2::Sha384>(key, input),
+        HashAlgorithm::Sha512 => mac::<sha2::Sha512>(key, input),
+        HashAlgorithm::Sha512_224 => mac::<sha2::Sha512_224>(key, input),
+        HashAlgorithm::Sha512_256 => mac::<sha2::Sha512_256>(key, input),
+        HashAlgorithm::Sha3_224 => mac::<sha3::Sha3_224>(key, input),
+        HashAlgorithm::Sha3_256 => mac::<sha3::Sha3_256>(key, input),
+        HashAlgorithm::Sha3_384 => mac::<sha3::Sha3_384>(key, input),
+        HashAlgorithm::Sha3_512 => mac::<sha3::Sha3_512>(key, input),
+        HashAlgorithm::Keccak256 => mac::<sha3::Keccak256>(key, input),
+        HashAlgorithm::Blake2b512 => mac::<blake2::Blake2b512>(key, input),
+        HashAlgorithm::Blake2s256 => mac::<blake2::Blake2s256>(key, input),
+        HashAlgorithm::Ripemd160 => mac::<ripemd::Ripemd160>(key, input),
+        HashAlgorithm::Sm3 => mac::<sm3::Sm3>(key, input),
+        HashAlgorithm::Whirlpool => mac::<whirlpool::Whirlpool>(key, input),
+        _ => return None,
+    })
+}
+
+/// Hashers of several algorithms fed with the same bytes.
+pub struct MultiHasher {
+    hashers: Vec<Box<dyn Hasher>>,
+}
+
+impl MultiHasher {
+    pub fn new(algorithms: &[HashAlgorithm]) -> Self {
+        Self {
+            hashers: algorithms.iter().map(HashAlgorithm::hasher).collect(),
+        }
+    }
+
+    /// Like [`MultiHasher::new`], extendable-output functions producing `size` bytes.
+    pub fn with_xof_size(algorithms: &[HashAlgorithm], size: usize) -> Self {
+        Self {
+            hashers: algorithms
+                .iter()
+                .map(|v| v.hasher_with_size(size))
+                .collect(),
+        }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        for hasher in &mut self.hashers {
+            hasher.update(input);
+        }
+    }
+
+    /// Digests in the order of the algorithms given to [`MultiHasher::new`].
+    pub fn finalize(self) -> Vec<Vec<u8>> {
+        self.hashers.into_iter().map(|v| v.finalize()).collect()
+    }
+}
+
+/// Hash `input` with every algorithm in a single pass, extendable-output functions producing
+/// `xof_size` bytes.
+pub fn hash_all(algorithms: &[HashAlgorithm], xof_size: usize, input: &[u8]) -> Vec<Vec<u8>> {
+    let mut hasher = MultiHasher::with_xof_size(algorithms, xof_size);
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// Feed everything `reader` yields to `hasher`, a chunk at a time.
+///
+/// `progress` gets the number of bytes read so far after every chunk; returning
+/// `ControlFlow::Break` stops reading and makes this return `Ok(None)`.
+pub fn hash_reader(
+    mut reader: impl Read,
+    mut hasher: MultiHasher,
+    mut progress: impl FnMut(u64) -> ControlFlow<()>,
+) -> std::io::Result<Option<Vec<Vec<u8>>>> {
+    let mut buffer = vec![0; CHUNK_SIZE];
+    let mut total = 0;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&buffer[..read]);
+        total += read as u64;
+        if progress(total).is_break() {
+            return Ok(None);
+        }
+    }
+    Ok(Some(hasher.finalize()))
+}
+
+/// How a digest pasted by the user is written.
+#[derive(Debug, Clone, Copy, PartialEq, strum_macros::Display)]
+pub enum DigestEncoding {
+    #[strum(to_string = "hex")]
+    Hex,
+
+    #[strum(to_string = "base64")]
+    Base64,
+}
+
+/// Read a digest written as hex in any case, or as base64 with either alphabet and optional
+/// padding. Hex wins when both would do.
+pub fn parse_digest(text: &str) -> Option<(Vec<u8>, DigestEncoding)> {
+    let text = text
+        .chars()
+        .filter(|v| !v.is_whitespace())
+        .collect::<String>();
+    if text.is_empty() {
+        return None;
+    }
+    if let Ok(bytes) = hex::decode(&text) {
+        return Some((bytes, DigestEncoding::Hex));
+    }
+    let text = text.trim_end_matches('=');
+    let engine = if text.contains(['-', '_']) {
+        URL_SAFE_NO_PAD
+    } else {
+        STANDARD_NO_PAD
+    };
+    engine
+        .decode(text)
+        .ok()
+        .map(|bytes| (bytes, DigestEncoding::Base64))
+}
+
+/// Algorithms whose digests are `size` bytes long.
+pub fn algorithms_with_size(size: usize) -> Vec<HashAlgorithm> {
+    HashAlgorithm::iter()
+        .filter(|v| v.output_size() == size)
+        .collect()
+}
+
+/// Compare digests in constant time, so that how much of a guess is right doesn't leak.
+pub fn digest_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// How a digest is written out.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+)]
+pub enum DigestFormat {
+    #[strum(to_string = "hex")]
+    Hex,
+
+    #[strum(to_string = "HEX")]
+    HexUpper,
+
+    #[strum(to_string = "HE:X")]
+    HexColon,
+
+    Base64,
+
+    #[strum(to_string = "Base64 URL")]
+    Base64Url,
+
+    Base32,
+
+    /// Big-endian unsigned integer, as checksums are usually printed.
+    Integer,
+
+    /// Subresource Integrity, e.g. `sha384-…`.
+    #[strum(to_string = "SRI")]
+    Sri,
+
+    /// Hex of the multihash: algorithm code, length and digest.
+    Multihash,
+
+    /// CIDv1 of raw bytes, in base32.
+    #[strum(to_string = "CID")]
+    Cid,
+}
+
+/// Unsigned LEB128, as multiformats write their numbers.
+fn varint(mut value: u64, output: &mut Vec<u8>) {
+    while value >= 0x80 {
+        output.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+/// RFC 4648 base32, upper case with padding.
+fn base32(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    let mut output = String::with_capacity(input.len().div_ceil(5) * 8);
+    for chunk in input.chunks(5) {
+        let mut block = [0; 5];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let bits = block.iter().fold(0u64, |value, &v| (value << 8) | v as u64);
+        let chars = (chunk.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            if i < chars {
+                output.push(ALPHABET[(bits >> (35 - i * 5)) as usize & 31] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+impl DigestFormat {
+    /// Write `digest` of `algorithm`, `None` when the format has no way to say it: integers
+    /// longer than 128 bits, SRI for other than SHA-256, SHA-384 and SHA-512, multihashes
+    /// for algorithms without a multicodec.
+    pub fn format(self, algorithm: HashAlgorithm, digest: &[u8]) -> Option<String> {
+        Some(match self {
+            DigestFormat::Hex => hex::encode(digest),
+            DigestFormat::HexUpper => hex::encode_upper(digest),
+            DigestFormat::HexColon => digest
+                .iter()
+                .map(|v| format!("{v:02X}"))
+                .collect::<Vec<_>>()
+                .join(":"),
+            DigestFormat::Base64 => STANDARD.encode(digest),
+            DigestFormat::Base64Url => URL_SAFE_NO_PAD.encode(digest),
+            DigestFormat::Base32 => base32(digest),
+            DigestFormat::Integer => {
+                if digest.len() > 16 {
+                    return None;
+                }
+                digest
+                    .iter()
+                    .fold(0u128, |value, &v| (value << 8) | v as u128)
+                    .to_string()
+            }
+            DigestFormat::Sri => {
+                let prefix = match algorithm {
+                    HashAlgorithm::Sha256 => "sha256",
+                    HashAlgorithm::Sha384 => "sha384",
+                    HashAlgorithm::Sha512 => "sha512",
+                    _ => return None,
+                };
+                format!("{prefix}-{}", STANDARD.encode(digest))
+            }
+            DigestFormat::Multihash => hex::encode(multihash(algorithm, digest)?),
+            DigestFormat::Cid => {
+                // Version 1, raw binary content.
+                let mut cid = vec![];
+                varint(1, &mut cid);
+                varint(0x55, &mut cid);
+                cid.extend(multihash(algorithm, digest)?);
+                // Multibase prefix of lower case base32 without padding.
+                format!("b{}", base32(&cid).trim_end_matches('=').to_lowercase())
+            }
+        })
+    }
+}
+
+fn multihash(algorithm: HashAlgorithm, digest: &[u8]) -> Option<Vec<u8>> {
+    let mut output = vec![];
+    varint(algorithm.multicodec()?, &mut output);
+    varint(digest.len() as u64, &mut output);
+    output.extend(digest);
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_digests() {
+        assert_eq!(
+            hex::encode(hash(HashAlgorithm::Md5, b"abc")),
+            "900150983cd24fb0d6963f7d28e17f72"
+        );
+        assert_eq!(
+            hex::encode(hash(HashAlgorithm::Sha256, b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex::encode(hash(HashAlgorithm::Sha3_256, b"")),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+    }
+
+    #[test]
+    fn extended_digests() {
+        let cases = [
+            (
+                HashAlgorithm::Keccak256,
+                &b""[..],
+                "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            ),
+            (
+                HashAlgorithm::Shake128,
+                b"",
+                "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26",
+            ),
+            (
+                HashAlgorithm::Blake2b512,
+                b"abc",
+                "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+            ),
+            (
+                HashAlgorithm::Blake2s256,
+                b"abc",
+                "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982",
+            ),
+            (
+                HashAlgorithm::Blake3,
+                b"",
+                "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            ),
+            (
+                HashAlgorithm::Ripemd160,
+                b"",
+                "9c1185a5c5e9fc54612808977ee8f548b2258d31",
+            ),
+            (
+                HashAlgorithm::Sm3,
+                b"abc",
+                "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0",
+            ),
+            (
+                HashAlgorithm::Whirlpool,
+                b"",
+                "19fa61d75522a4669b44e39c1d2e1726c530232130d407f89afee0964997f7a73e83be698b288febcf88e3e03c4f0757ea8964e59b63d93708b138cc42a66eb3",
+            ),
+            (HashAlgorithm::Crc32, b"123456789", "cbf43926"),
+            (HashAlgorithm::Crc32c, b"123456789", "e3069283"),
+            (HashAlgorithm::Crc64, b"123456789", "995dc9bbdf1939fa"),
+            (HashAlgorithm::Adler32, b"Wikipedia", "11e60398"),
+            (HashAlgorithm::Xxh32, b"", "02cc5d05"),
+            (HashAlgorithm::Xxh64, b"", "ef46db3751d8e999"),
+            (HashAlgorithm::Xxh3_64, b"", "2d06800538d394c2"),
+            (
+                HashAlgorithm::Xxh3_128,
+                b"",
+                "99aa06d3014798d86001c324468d497f",
+            ),
+            (HashAlgorithm::Fnv1a32, b"foobar", "bf9cf968"),
+            (HashAlgorithm::Murmur3_32, b"hello", "248bfa47"),
+        ];
+        for (algorithm, input, digest) in cases {
+            assert_eq!(hex::encode(hash(algorithm, input)), digest, "{algorithm}");
+        }
+
+        let mut shake = HashAlgorithm::Shake256.hasher_with_size(4);
+        shake.update(b"");
+        assert_eq!(hex::encode(shake.finalize()), "46b9dd2b");
+    }
+
+    #[test]
+    fn output_sizes() {
+        for algorithm in HashAlgorithm::iter() {
+            assert_eq!(algorithm.output_size(), algorithm.hasher().finalize().len());
+        }
+    }
+
+    #[test]
+    fn hash_reader_in_chunks() {
+        let input = vec![7; CHUNK_SIZE * 2 + 3];
+        let algorithms = [HashAlgorithm::Md5, HashAlgorithm::Sha3_256];
+        let mut reported = vec![];
+        let digests = hash_reader(&input[..], MultiHasher::new(&algorithms), |v| {
+            reported.push(v);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(digests, Some(hash_all(&algorithms, 32, &input)));
+        assert_eq!(reported.last(), Some(&(input.len() as u64)));
+
+        let cancelled = hash_reader(&input[..], MultiHasher::new(&algorithms), |_| {
+            ControlFlow::Break(())
+        })
+        .unwrap();
+        assert_eq!(cancelled, None);
+    }
+
+    #[test]
+    fn hmac_digests() {
+        // RFC 4231, test case 2.
+        let key = HmacKeyKind::Utf8.decode("Jefe").unwrap();
+        let input = b"what do ya want for nothing?";
+        assert_eq!(
+            hex::encode(hmac(HashAlgorithm::Sha256, &key, input).unwrap()),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // RFC 2202, test case 2.
+        assert_eq!(
+            hex::encode(hmac(HashAlgorithm::Md5, &key, input).unwrap()),
+            "750c783e6ab0b503eaa86e310a5db738"
+        );
+        assert_eq!(HmacKeyKind::Hex.decode("4a 65 66 65").unwrap(), key);
+        assert_eq!(HmacKeyKind::Base64.decode("SmVmZQ==").unwrap(), key);
+        assert!(HmacKeyKind::Base64.decode("SmVmZQ=").is_err());
+        for algorithm in HashAlgorithm::iter() {
+            let mac = hmac(algorithm, &key, input);
+            assert_eq!(mac.is_some(), algorithm.has_hmac(), "{algorithm}");
+            if let Some(mac) = mac {
+                assert_eq!(mac.len(), algorithm.output_size());
+            }
+        }
+    }
+
+    #[test]
+    fn expected_digests() {
+        let digest = hash(HashAlgorithm::Sha256, b"abc");
+        let upper = hex::encode_upper(&digest);
+        assert_eq!(
+            parse_digest(&upper),
+            Some((digest.clone(), DigestEncoding::Hex))
+        );
+        for text in [
+            "ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=",
+            "ungWv48Bz-pBQUDeXa4iI7ADYaOWF3qctBD_YfIAFa0",
+        ] {
+            assert_eq!(
+                parse_digest(text),
+                Some((digest.clone(), DigestEncoding::Base64))
+            );
+        }
+        assert_eq!(parse_digest("  "), None);
+        assert_eq!(parse_digest("not a digest!"), None);
+
+        assert_eq!(
+            algorithms_with_size(32),
+            [
+                HashAlgorithm::Sha256,
+                HashAlgorithm::Sha512_256,
+                HashAlgorithm::Sha3_256,
+                HashAlgorithm::Keccak256,
+                HashAlgorithm::Shake128,
+                HashAlgorithm::Blake2s256,
+                HashAlgorithm::Blake3,
+                HashAlgorithm::Sm3,
+            ]
+        );
+        assert!(digest_eq(&digest, &hash(HashAlgorithm::Sha256, b"abc")));
+        assert!(!digest_eq(&digest, &hash(HashAlgorithm::Sha256, b"abd")));
+        assert!(!digest_eq(&digest, &digest[1..]));
+    }
+
+    #[test]
+    fn digest_formats() {
+        let sha256 = hash(HashAlgorithm::Sha256, b"");
+        let format =
+            |format: DigestFormat, algorithm, digest: &[u8]| format.format(algorithm, digest);
+        assert_eq!(
+            format(DigestFormat::HexColon, HashAlgorithm::Md5, &[0xab, 0x01]).unwrap(),
+            "AB:01"
+        );
+        assert_eq!(base32(b"f"), "MY======");
+        assert_eq!(base32(b"foobar"), "MZXW6YTBOI======");
+        assert_eq!(
+            format(
+                DigestFormat::Integer,
+                HashAlgorithm::Crc32,
+                &hash(HashAlgorithm::Crc32, b"123456789")
+            )
+            .unwrap(),
+            "3421780262"
+        );
+        assert_eq!(
+            format(DigestFormat::Integer, HashAlgorithm::Sha256, &sha256),
+            None
+        );
+        assert_eq!(
+            format(
+                DigestFormat::Sri,
+                HashAlgorithm::Sha384,
+                &hash(HashAlgorithm::Sha384, b"")
+            )
+            .unwrap(),
+            "sha384-OLBgp1GsljhM2TJ+sbHjaiH9txEUvgdDTAzHv2P24donTt6/529l+9Ua0vFImLlb"
+        );
+        assert_eq!(format(DigestFormat::Sri, HashAlgorithm::Md5, &sha256), None);
+        assert!(
+            format(DigestFormat::Multihash, HashAlgorithm::Sha256, &sha256)
+                .unwrap()
+                .starts_with("1220e3b0c442")
+        );
+        // `ipfs add --raw-leaves --cid-version 1` of an empty file.
+        assert_eq!(
+            format(DigestFormat::Cid, HashAlgorithm::Sha256, &sha256).unwrap(),
+            "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+        );
+        assert_eq!(
+            format(DigestFormat::Cid, HashAlgorithm::Crc32, &sha256),
+            None
+        );
+
+        let mut encoded = vec![];
+        varint(0xb240, &mut encoded);
+        assert_eq!(encoded, [0xc0, 0xe4, 0x02]);
+    }
+}
@@ -0,0 +1,534 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+    sync::LazyLock,
+};
+
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
+
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum IpError {
+    #[display("invalid IP address: {_0}")]
+    #[from]
+    InvalidAddress(std::net::AddrParseError),
+
+    #[display("invalid prefix length")]
+    InvalidPrefixLength,
+
+    #[display("invalid netmask")]
+    InvalidNetmask,
+
+    #[display("netmask is not contiguous")]
+    NonContiguousNetmask,
+
+    #[display("invalid subnet count")]
+    InvalidSubnetCount,
+
+    #[display("/{prefix} does not fit in {parent}")]
+    PrefixDoesNotFit { prefix: u8, parent: IpNet },
+
+    #[display("more than {MAX_SUBNETS} subnets")]
+    TooManySubnets,
+
+    /// Error in one line of a CIDR list.
+    #[display("line {line}: {error}")]
+    Line { line: usize, error: Box<IpError> },
+}
+
+impl std::error::Error for IpError {}
+
+/// Special-purpose ranges from the IANA IPv4 and IPv6 registries (RFC 6890 and friends).
+static SPECIAL_RANGES: LazyLock<Vec<(IpNet, &'static str)>> = LazyLock::new(|| {
+    [
+        ("0.0.0.0/8", "this network"),
+        ("10.0.0.0/8", "private"),
+        ("100.64.0.0/10", "CGNAT"),
+        ("127.0.0.0/8", "loopback"),
+        ("169.254.0.0/16", "link-local"),
+        ("172.16.0.0/12", "private"),
+        ("192.0.0.0/24", "IETF protocol assignments"),
+        ("192.0.2.0/24", "documentation"),
+        ("192.88.99.0/24", "6to4 relay anycast"),
+        ("192.168.0.0/16", "private"),
+        ("198.18.0.0/15", "benchmarking"),
+        ("198.51.100.0/24", "documentation"),
+        ("203.0.113.0/24", "documentation"),
+        ("224.0.0.0/4", "multicast"),
+        ("240.0.0.0/4", "reserved"),
+        ("255.255.255.255/32", "limited broadcast"),
+        ("::/128", "unspecified"),
+        ("::1/128", "loopback"),
+        ("::ffff:0:0/96", "IPv4-mapped"),
+        ("64:ff9b::/96", "NAT64"),
+        ("100::/64", "discard-only"),
+        ("2001::/32", "Teredo"),
+        ("2001:db8::/32", "documentation"),
+        ("2002::/16", "6to4"),
+        ("3fff::/20", "documentation"),
+        ("fc00::/7", "ULA"),
+        ("fe80::/10", "link-local"),
+        ("ff00::/8", "multicast"),
+    ]
+    .into_iter()
+    .map(|(net, name)| (IpNet::from_str(net).unwrap(), name))
+    .collect()
+});
+
+/// Splitting a block into more subnets than this is almost certainly a typo.
+const MAX_SUBNETS: u128 = 65536;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+pub enum IpSplitKind {
+    #[strum(to_string = "Into N subnets")]
+    Count,
+
+    #[strum(to_string = "By prefix length")]
+    Prefix,
+}
+
+/// Parse `10.0.0.1`, `10.0.0.1/24`, `10.0.0.1/255.255.255.0` or `10.0.0.1 255.255.255.0`.
+///
+/// Returns the address itself together with the network it was given in; a bare address is a
+/// single-address network.
+pub fn parse_ip_input(input: &str) -> Result<(IpAddr, IpNet), IpError> {
+    let input = input.trim();
+    let (address, prefix) = match input.split_once(['/', ' ']) {
+        Some((address, prefix)) => (address.trim(), Some(prefix.trim())),
+        None => (input, None),
+    };
+    let address = IpAddr::from_str(address)?;
+    let net = match (address, prefix) {
+        (address, None) => IpNet::from(address),
+        (address, Some(prefix)) if prefix.bytes().all(|v| v.is_ascii_digit()) => {
+            let prefix = prefix.parse().map_err(|_| IpError::InvalidPrefixLength)?;
+            IpNet::new(address, prefix).map_err(|_| IpError::InvalidPrefixLength)?
+        }
+        (IpAddr::V4(address), Some(netmask)) => {
+            let netmask = Ipv4Addr::from_str(netmask).map_err(|_| IpError::InvalidNetmask)?;
+            Ipv4Net::with_netmask(address, netmask)
+                .map_err(|_| IpError::NonContiguousNetmask)?
+                .into()
+        }
+        (IpAddr::V6(_), Some(_)) => return Err(IpError::InvalidPrefixLength),
+    };
+    Ok((address, net))
+}
+
+/// Names of every special-purpose range `address` falls in, or `global` if none.
+pub fn classify_ip(address: IpAddr) -> Vec<&'static str> {
+    let names = SPECIAL_RANGES
+        .iter()
+        .filter(|(net, _)| net.contains(&address))
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        vec!["global"]
+    } else {
+        names
+    }
+}
+
+fn ipv4_binary(address: Ipv4Addr) -> String {
+    address
+        .octets()
+        .iter()
+        .map(|v| format!("{v:08b}"))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn ipv6_binary(address: Ipv6Addr) -> String {
+    address
+        .segments()
+        .iter()
+        .map(|v| format!("{v:016b}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn ipv6_expanded(address: Ipv6Addr) -> String {
+    address
+        .segments()
+        .iter()
+        .map(|v| format!("{v:04x}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// The 6to4 prefix (RFC 3056) derived from an IPv4 address.
+fn ipv4_to_6to4(address: Ipv4Addr) -> Ipv6Net {
+    let [a, b, c, d] = address.octets();
+    let address = Ipv6Addr::new(
+        0x2002,
+        u16::from_be_bytes([a, b]),
+        u16::from_be_bytes([c, d]),
+        0,
+        0,
+        0,
+        0,
+        0,
+    );
+    Ipv6Net::new(address, 48).unwrap()
+}
+
+/// The IPv4 address embedded in a 6to4 address.
+fn ipv6_from_6to4(address: Ipv6Addr) -> Option<Ipv4Addr> {
+    let segments = address.segments();
+    (segments[0] == 0x2002).then(|| {
+        let [a, b] = segments[1].to_be_bytes();
+        let [c, d] = segments[2].to_be_bytes();
+        Ipv4Addr::new(a, b, c, d)
+    })
+}
+
+fn ipv4_rows(address: Ipv4Addr, net: Ipv4Net) -> Vec<(&'static str, String)> {
+    let size = 1u64 << (32 - net.prefix_len());
+    // RFC 3021 point-to-point links and single hosts have no network/broadcast addresses.
+    let (first, last, hosts) = match net.prefix_len() {
+        32 | 31 => (net.network(), net.broadcast(), size),
+        _ => (
+            Ipv4Addr::from(u32::from(net.network()) + 1),
+            Ipv4Addr::from(u32::from(net.broadcast()) - 1),
+            size - 2,
+        ),
+    };
+    vec![
+        ("Address", address.to_string()),
+        ("Network", net.trunc().to_string()),
+        ("Netmask", net.netmask().to_string()),
+        ("Wildcard mask", net.hostmask().to_string()),
+        ("Broadcast", net.broadcast().to_string()),
+        ("First host", first.to_string()),
+        ("Last host", last.to_string()),
+        ("Hosts", hosts.to_string()),
+        ("Addresses", size.to_string()),
+        ("Binary", ipv4_binary(address)),
+        ("Netmask binary", ipv4_binary(net.netmask())),
+        ("Hex", format!("0x{:08X}", u32::from(address))),
+        ("Integer", u32::from(address).to_string()),
+        ("IPv4-mapped IPv6", address.to_ipv6_mapped().to_string()),
+        ("6to4", ipv4_to_6to4(address).to_string()),
+    ]
+}
+
+fn ipv6_rows(address: Ipv6Addr, net: Ipv6Net) -> Vec<(&'static str, String)> {
+    let size = match net.prefix_len() {
+        0 => "340282366920938463463374607431768211456".to_string(),
+        v => (1u128 << (128 - v)).to_string(),
+    };
+    let mut rows = vec![
+        ("Address", address.to_string()),
+        ("Expanded", ipv6_expanded(address)),
+        ("Network", net.trunc().to_string()),
+        ("Netmask", net.netmask().to_string()),
+        ("Wildcard mask", net.hostmask().to_string()),
+        ("First host", net.network().to_string()),
+        ("Last host", net.broadcast().to_string()),
+        ("Addresses", size),
+        ("Binary", ipv6_binary(address)),
+        ("Hex", format!("0x{:032X}", u128::from(address))),
+        ("Integer", u128::from(address).to_string()),
+    ];
+    if let Some(v) = address.to_ipv4_mapped() {
+        rows.push(("IPv4-mapped", v.to_string()));
+    }
+    if let Some(v) = ipv6_from_6to4(address) {
+        rows.push(("6to4 IPv4", v.to_string()));
+    }
+    rows
+}
+
+/// Parse a CIDR list separated by newlines, commas or spaces; `#` starts a comment.
+pub fn parse_ip_ranges(input: &str) -> Result<Vec<IpNet>, IpError> {
+    let mut ranges = vec![];
+    for (i, line) in input.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default();
+        for item in line.split([',', ' ', '\t']).filter(|v| !v.is_empty()) {
+            let (_, net) = parse_ip_input(item).map_err(|e| IpError::Line {
+                line: i + 1,
+                error: Box::new(e),
+            })?;
+            ranges.push(net.trunc());
+        }
+    }
+    Ok(ranges)
+}
+
+/// Minimal list of (start, prefix length) blocks exactly covering `start..=end`.
+fn range_to_cidrs(mut start: u128, end: u128, bits: u8) -> Vec<(u128, u8)> {
+    let mut blocks = vec![];
+    loop {
+        let alignment = start.trailing_zeros().min(bits.into());
+        let fits = match (end - start).checked_add(1) {
+            Some(len) => 127 - len.leading_zeros(),
+            None => 128,
+        };
+        let size = alignment.min(fits);
+        blocks.push((start, bits - size as u8));
+        // A block of 2^128 addresses covers everything.
+        match 1u128.checked_shl(size).and_then(|v| start.checked_add(v)) {
+            Some(next) if next <= end => start = next,
+            _ => return blocks,
+        }
+    }
+}
+
+fn cidr_to_net(address: u128, prefix: u8, bits: u8) -> IpNet {
+    match bits {
+        32 => Ipv4Net::new(Ipv4Addr::from(address as u32), prefix)
+            .unwrap()
+            .into(),
+        _ => Ipv6Net::new(Ipv6Addr::from(address), prefix)
+            .unwrap()
+            .into(),
+    }
+}
+
+/// Address ranges between consecutive aggregated blocks of the same family.
+pub fn ip_gaps(ranges: &[IpNet]) -> Vec<IpNet> {
+    let aggregated = IpNet::aggregate(&ranges.to_vec());
+    let mut gaps = vec![];
+    for pair in aggregated.windows(2) {
+        let (start, end, bits) = match (pair[0], pair[1]) {
+            (IpNet::V4(a), IpNet::V4(b)) => (
+                u32::from(a.broadcast()).into(),
+                u32::from(b.network()).into(),
+                32,
+            ),
+            (IpNet::V6(a), IpNet::V6(b)) => {
+                (u128::from(a.broadcast()), u128::from(b.network()), 128)
+            }
+            _ => continue,
+        };
+        if end - start < 2 {
+            continue;
+        }
+        gaps.extend(
+            range_to_cidrs(start + 1, end - 1, bits)
+                .into_iter()
+                .map(|(v, prefix)| cidr_to_net(v, prefix, bits)),
+        );
+    }
+    gaps
+}
+
+/// Every pair of ranges where one contains the other.
+pub fn ip_overlaps(ranges: &[IpNet]) -> Vec<String> {
+    let mut overlaps = vec![];
+    for (i, a) in ranges.iter().enumerate() {
+        for b in &ranges[i + 1..] {
+            if a == b {
+                overlaps.push(format!("{a} duplicates {b}"));
+            } else if a.contains(b) {
+                overlaps.push(format!("{a} contains {b}"));
+            } else if b.contains(a) {
+                overlaps.push(format!("{b} contains {a}"));
+            }
+        }
+    }
+    overlaps
+}
+
+/// Split `parent` into `value` equal subnets, or into subnets with prefix length `value`.
+pub fn split_ip_range(
+    parent: IpNet,
+    kind: IpSplitKind,
+    value: &str,
+) -> Result<Vec<IpNet>, IpError> {
+    let value = value.trim().trim_start_matches('/');
+    let (prefix, count) = match kind {
+        IpSplitKind::Count => {
+            let count = value
+                .parse::<u128>()
+                .map_err(|_| IpError::InvalidSubnetCount)?;
+            if count == 0 {
+                return Err(IpError::InvalidSubnetCount);
+            }
+            let extra_bits = 128 - (count - 1).leading_zeros();
+            let prefix = u32::from(parent.prefix_len()) + extra_bits;
+            (u8::try_from(prefix).unwrap_or(u8::MAX), count)
+        }
+        IpSplitKind::Prefix => {
+            let prefix = value
+                .parse::<u8>()
+                .map_err(|_| IpError::InvalidPrefixLength)?;
+            let bits = u32::from(prefix.saturating_sub(parent.prefix_len()));
+            (prefix, 1u128.checked_shl(bits).unwrap_or(u128::MAX))
+        }
+    };
+    if prefix > parent.max_prefix_len() || prefix < parent.prefix_len() {
+        return Err(IpError::PrefixDoesNotFit { prefix, parent });
+    }
+    if count > MAX_SUBNETS {
+        return Err(IpError::TooManySubnets);
+    }
+    Ok(parent
+        .subnets(prefix)
+        .map_err(|_| IpError::InvalidPrefixLength)?
+        .take(count as usize)
+        .collect())
+}
+
+/// Details of `address` within `net`, as returned by [`parse_ip_input`].
+pub fn ip_rows(address: IpAddr, net: IpNet) -> Vec<(&'static str, String)> {
+    match (address, net) {
+        (IpAddr::V4(address), IpNet::V4(net)) => ipv4_rows(address, net),
+        (IpAddr::V6(address), IpNet::V6(net)) => ipv6_rows(address, net),
+        _ => unreachable!("parse_ip_input keeps the address and network families equal"),
+    }
+}
+
+/// Ranges containing `needle`, in their original order.
+pub fn lookup_ip(ranges: &[IpNet], needle: IpNet) -> Vec<IpNet> {
+    let needle = needle.trunc();
+    ranges
+        .iter()
+        .filter(|v| v.contains(&needle))
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nets(input: &str) -> Vec<IpNet> {
+        parse_ip_ranges(input).unwrap()
+    }
+
+    #[test]
+    fn parse_input() {
+        let (address, net) = parse_ip_input("10.0.0.1 255.255.255.0").unwrap();
+        assert_eq!(address.to_string(), "10.0.0.1");
+        assert_eq!(net.to_string(), "10.0.0.1/24");
+        assert_eq!(parse_ip_input("::1").unwrap().1.to_string(), "::1/128");
+        assert!(matches!(
+            parse_ip_input("10.0.0.1/33"),
+            Err(IpError::InvalidPrefixLength)
+        ));
+        assert!(matches!(
+            parse_ip_input("10.0.0.1/255.0.255.0"),
+            Err(IpError::NonContiguousNetmask)
+        ));
+    }
+
+    #[test]
+    fn classify() {
+        assert_eq!(classify_ip("192.168.1.1".parse().unwrap()), ["private"]);
+        assert_eq!(classify_ip("8.8.8.8".parse().unwrap()), ["global"]);
+    }
+
+    fn row<'a>(rows: &'a [(&str, String)], name: &str) -> &'a str {
+        &rows.iter().find(|(n, _)| *n == name).unwrap().1
+    }
+
+    #[test]
+    fn ipv4_details() {
+        let rows = |input| {
+            let (address, net) = parse_ip_input(input).unwrap();
+            ip_rows(address, net)
+        };
+        let lan = rows("192.168.1.10/24");
+        assert_eq!(row(&lan, "Network"), "192.168.1.0/24");
+        assert_eq!(row(&lan, "Wildcard mask"), "0.0.0.255");
+        assert_eq!(row(&lan, "First host"), "192.168.1.1");
+        assert_eq!(row(&lan, "Last host"), "192.168.1.254");
+        assert_eq!(row(&lan, "Hosts"), "254");
+        assert_eq!(row(&lan, "Hex"), "0xC0A8010A");
+        assert_eq!(row(&lan, "6to4"), "2002:c0a8:10a::/48");
+        // Point-to-point links use both addresses.
+        let link = rows("10.0.0.0/31");
+        assert_eq!(row(&link, "First host"), "10.0.0.0");
+        assert_eq!(row(&link, "Hosts"), "2");
+    }
+
+    #[test]
+    fn ipv6_details() {
+        let (address, net) = parse_ip_input("2002:c0a8:10a::1/48").unwrap();
+        let rows = ip_rows(address, net);
+        assert_eq!(
+            row(&rows, "Expanded"),
+            "2002:c0a8:010a:0000:0000:0000:0000:0001"
+        );
+        assert_eq!(row(&rows, "Addresses"), "1208925819614629174706176");
+        assert_eq!(row(&rows, "6to4 IPv4"), "192.168.1.10");
+
+        let (address, net) = parse_ip_input("::ffff:10.0.0.1/0").unwrap();
+        let rows = ip_rows(address, net);
+        assert_eq!(
+            row(&rows, "Addresses"),
+            "340282366920938463463374607431768211456"
+        );
+        assert_eq!(row(&rows, "IPv4-mapped"), "10.0.0.1");
+    }
+
+    #[test]
+    fn ranges_report_line() {
+        let error = parse_ip_ranges("10.0.0.0/8\n# comment\nnope").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3: invalid IP address: invalid IP address syntax"
+        );
+    }
+
+    #[test]
+    fn gaps_and_overlaps() {
+        let ranges = nets("10.0.0.0/24, 10.0.0.0/25\n10.0.3.0/24");
+        assert_eq!(ip_overlaps(&ranges), ["10.0.0.0/24 contains 10.0.0.0/25"]);
+        assert_eq!(ip_gaps(&ranges), nets("10.0.1.0/24 10.0.2.0/24"));
+    }
+
+    #[test]
+    fn cidr_blocks() {
+        assert_eq!(range_to_cidrs(0, u128::MAX, 128), [(0, 0)]);
+        assert_eq!(range_to_cidrs(0, u32::MAX.into(), 32), [(0, 0)]);
+        assert_eq!(
+            range_to_cidrs(1, 6, 32),
+            [(1, 32), (2, 31), (4, 31), (6, 32)]
+        );
+        assert_eq!(
+            ip_gaps(&nets("::/1 ffff::/16")),
+            nets("8000::/2 c000::/3 e000::/4 f000::/5 f800::/6 fc00::/7 fe00::/8")
+                .into_iter()
+                .chain(nets(
+                    "ff00::/9 ff80::/10 ffc0::/11 ffe0::/12 fff0::/13 fff8::/14 fffc::/15 fffe::/16"
+                ))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn split() {
+        let parent = nets("10.0.0.0/24")[0];
+        assert_eq!(
+            split_ip_range(parent, IpSplitKind::Count, "3").unwrap(),
+            nets("10.0.0.0/26 10.0.0.64/26 10.0.0.128/26")
+        );
+        assert_eq!(
+            split_ip_range(parent, IpSplitKind::Prefix, "/25").unwrap(),
+            nets("10.0.0.0/25 10.0.0.128/25")
+        );
+        assert!(matches!(
+            split_ip_range(parent, IpSplitKind::Prefix, "23"),
+            Err(IpError::PrefixDoesNotFit { prefix: 23, .. })
+        ));
+    }
+
+    #[test]
+    fn lookup() {
+        let ranges = nets("10.0.0.0/8 192.168.0.0/16 10.1.0.0/16");
+        let (_, needle) = parse_ip_input("10.1.2.3").unwrap();
+        assert_eq!(lookup_ip(&ranges, needle), nets("10.0.0.0/8 10.1.0.0/16"));
+    }
+}
@@ -0,0 +1,749 @@
+use std::str::FromStr;
+
+use base64::{
+    Engine,
+    engine::{
+        DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig, general_purpose::URL_SAFE_NO_PAD,
+    },
+};
+use hmac::{Hmac, Mac, digest::KeyInit};
+use rsa::{
+    BigUint, RsaPrivateKey, RsaPublicKey,
+    pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey},
+    pkcs8::{DecodePrivateKey, DecodePublicKey},
+    signature::{RandomizedSigner, SignatureEncoding, Signer, Verifier},
+};
+use sha2::{Sha256, Sha384, Sha512};
+
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum JwtError {
+    #[display("malformed token")]
+    Malformed,
+
+    #[display("invalid base64url: {_0}")]
+    #[from]
+    Base64(base64::DecodeError),
+
+    #[display("invalid JSON: {_0}")]
+    #[from]
+    Json(serde_json::Error),
+
+    #[display("invalid header JSON: {_0}")]
+    HeaderJson(serde_json::Error),
+
+    #[display("invalid payload JSON: {_0}")]
+    PayloadJson(serde_json::Error),
+
+    #[display("header has no \"alg\"")]
+    MissingAlgorithm,
+
+    #[display("unsupported algorithm \"{_0}\"")]
+    UnsupportedAlgorithm(String),
+
+    #[display("invalid JWK: {_0}")]
+    Jwk(serde_json::Error),
+
+    #[display("JWK Set has no keys")]
+    EmptyJwkSet,
+
+    #[display("JWK Set has no key with \"kid\" \"{_0}\"")]
+    UnknownKeyId(String),
+
+    #[display("JWK is missing \"{_0}\"")]
+    MissingJwkParameter(&'static str),
+
+    #[display("JWK \"{_0}\" is not base64url")]
+    InvalidJwkParameter(&'static str),
+
+    #[display("JWK \"{name}\" must be {length} bytes")]
+    JwkParameterLength { name: &'static str, length: usize },
+
+    /// The key doesn't match what the algorithm needs.
+    #[display("{_0}")]
+    Key(&'static str),
+
+    #[display("{_0}")]
+    #[from]
+    Rsa(rsa::Error),
+
+    #[display("{_0}")]
+    #[from]
+    Signature(ed25519_dalek::SignatureError),
+}
+
+impl std::error::Error for JwtError {}
+
+/// JWT segments are base64url without padding, but some producers keep the `=`.
+const BASE64_URL_SAFE_LENIENT: GeneralPurpose = GeneralPurpose::new(
+    &base64::alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Registered claims holding NumericDate values (RFC 7519 section 4.1).
+pub const DATE_CLAIMS: [(&str, &str); 3] = [
+    ("exp", "Expiration Time"),
+    ("nbf", "Not Before"),
+    ("iat", "Issued At"),
+];
+
+pub const DEFAULT_HEADER: &str = "{\n  \"alg\": \"HS256\",\n  \"typ\": \"JWT\"\n}";
+
+/// Signature algorithms from RFC 7518 (and RFC 8037 for EdDSA) that the tool can handle.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+)]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum JwtAlgorithm {
+    Hs256,
+    Hs384,
+    Hs512,
+    Rs256,
+    Rs384,
+    Rs512,
+    Ps256,
+    Es256,
+    Es384,
+    #[strum(serialize = "EdDSA")]
+    EdDsa,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, strum_macros::Display)]
+pub enum JwtVerification {
+    #[strum(to_string = "valid")]
+    Valid,
+
+    #[strum(to_string = "invalid signature")]
+    InvalidSignature,
+
+    #[strum(to_string = "expired")]
+    Expired,
+
+    #[strum(to_string = "not yet valid")]
+    NotYetValid,
+}
+
+/// Result of decoding one dot-separated segment of a token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JwtSegment {
+    pub text: String,
+    pub valid: bool,
+}
+
+impl JwtSegment {
+    fn invalid(reason: impl Into<String>) -> Self {
+        Self {
+            text: reason.into(),
+            valid: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedJwt {
+    pub header: JwtSegment,
+    pub payload: JwtSegment,
+    pub signature: JwtSegment,
+    pub claims: Option<serde_json::Value>,
+}
+
+impl DecodedJwt {
+    pub fn valid(&self) -> bool {
+        self.header.valid && self.payload.valid && self.signature.valid
+    }
+}
+
+fn decode_json_segment(segment: &str) -> (JwtSegment, Option<serde_json::Value>) {
+    let bytes = match BASE64_URL_SAFE_LENIENT.decode(segment) {
+        Ok(v) => v,
+        Err(e) => return (JwtSegment::invalid(format!("invalid base64url: {e}")), None),
+    };
+    let s = String::from_utf8_lossy(&bytes).to_string();
+    match serde_json::from_str::<serde_json::Value>(&s) {
+        Ok(v) => {
+            let text = serde_json::to_string_pretty(&v).unwrap_or(s);
+            (JwtSegment { text, valid: true }, Some(v))
+        }
+        Err(e) => (JwtSegment::invalid(format!("invalid JSON: {e}\n{s}")), None),
+    }
+}
+
+pub fn decode_jwt(token: &str) -> DecodedJwt {
+    let token = token.trim();
+    let parts: Vec<_> = token.split('.').collect();
+    if parts.len() != 3 {
+        let reason = format!("expected 3 dot-separated segments, found {}", parts.len());
+        return DecodedJwt {
+            header: JwtSegment::invalid(reason.clone()),
+            payload: JwtSegment::invalid(reason.clone()),
+            signature: JwtSegment::invalid(reason),
+            claims: None,
+        };
+    }
+
+    let (header, _) = decode_json_segment(parts[0]);
+    let (payload, claims) = decode_json_segment(parts[1]);
+    let signature = match BASE64_URL_SAFE_LENIENT.decode(parts[2]) {
+        Ok(v) => JwtSegment {
+            text: hex::encode(v),
+            valid: true,
+        },
+        Err(e) => JwtSegment::invalid(format!("invalid base64url: {e}")),
+    };
+
+    DecodedJwt {
+        header,
+        payload,
+        signature,
+        claims,
+    }
+}
+
+/// Format a NumericDate (seconds since the Unix epoch) in UTC and local time.
+pub fn format_numeric_date(value: &serde_json::Value) -> Result<String, String> {
+    let seconds = value
+        .as_i64()
+        .or_else(|| value.as_f64().map(|v| v as i64))
+        .ok_or_else(|| format!("{value} is not a number"))?;
+    let timestamp = jiff::Timestamp::from_second(seconds).map_err(|e| e.to_string())?;
+    let local = timestamp.to_zoned(jiff::tz::TimeZone::system());
+    Ok(format!(
+        "{timestamp} ({})",
+        local.strftime("%Y-%m-%d %H:%M:%S %:z")
+    ))
+}
+
+/// Key material as pasted by the user.
+enum JwtKey<'a> {
+    Pem(&'a str),
+    Jwk(serde_json::Value),
+    Secret(&'a [u8]),
+}
+
+impl<'a> JwtKey<'a> {
+    /// Classify `key`, picking the entry matching `kid` when a JWK Set is given, or the first
+    /// one when the header has no `kid`.
+    fn parse(key: &'a str, kid: Option<&str>) -> Result<Self, JwtError> {
+        let trimmed = key.trim();
+        if trimmed.starts_with("-----BEGIN") {
+            return Ok(JwtKey::Pem(trimmed));
+        }
+        if !trimmed.starts_with('{') {
+            return Ok(JwtKey::Secret(key.as_bytes()));
+        }
+
+        let jwk: serde_json::Value = serde_json::from_str(trimmed).map_err(JwtError::Jwk)?;
+        let Some(keys) = jwk.get("keys").and_then(|v| v.as_array()) else {
+            return Ok(JwtKey::Jwk(jwk));
+        };
+        if keys.is_empty() {
+            return Err(JwtError::EmptyJwkSet);
+        }
+        let key = match kid {
+            Some(kid) => keys
+                .iter()
+                .find(|v| v.get("kid").and_then(|v| v.as_str()) == Some(kid))
+                .ok_or_else(|| JwtError::UnknownKeyId(kid.to_string()))?,
+            None => &keys[0],
+        };
+        Ok(JwtKey::Jwk(key.clone()))
+    }
+
+    fn jwk_param(jwk: &serde_json::Value, name: &'static str) -> Result<Vec<u8>, JwtError> {
+        let value = jwk
+            .get(name)
+            .and_then(|v| v.as_str())
+            .ok_or(JwtError::MissingJwkParameter(name))?;
+        BASE64_URL_SAFE_LENIENT
+            .decode(value)
+            .map_err(|_| JwtError::InvalidJwkParameter(name))
+    }
+
+    fn secret(&self) -> Result<Vec<u8>, JwtError> {
+        match self {
+            JwtKey::Secret(v) => Ok(v.to_vec()),
+            JwtKey::Jwk(jwk) => Self::jwk_param(jwk, "k"),
+            JwtKey::Pem(_) => Err(JwtError::Key("HMAC needs a secret, not a PEM key")),
+        }
+    }
+
+    fn rsa_private(&self) -> Result<RsaPrivateKey, JwtError> {
+        match self {
+            JwtKey::Pem(pem) => RsaPrivateKey::from_pkcs8_pem(pem)
+                .ok()
+                .or_else(|| RsaPrivateKey::from_pkcs1_pem(pem).ok())
+                .ok_or(JwtError::Key("not an RSA private key")),
+            JwtKey::Jwk(jwk) => {
+                let [n, e, d, p, q] = ["n", "e", "d", "p", "q"]
+                    .map(|name| Self::jwk_param(jwk, name).map(|v| BigUint::from_bytes_be(&v)));
+                Ok(RsaPrivateKey::from_components(n?, e?, d?, vec![p?, q?])?)
+            }
+            JwtKey::Secret(_) => Err(JwtError::Key("RSA needs a PEM key or JWK")),
+        }
+    }
+
+    fn rsa_public(&self) -> Result<RsaPublicKey, JwtError> {
+        match self {
+            JwtKey::Pem(pem) => RsaPublicKey::from_public_key_pem(pem)
+                .ok()
+                .or_else(|| RsaPublicKey::from_pkcs1_pem(pem).ok())
+                .or_else(|| self.rsa_private().ok().map(|v| v.to_public_key()))
+                .ok_or(JwtError::Key("not an RSA key")),
+            JwtKey::Jwk(jwk) => {
+                let n = BigUint::from_bytes_be(&Self::jwk_param(jwk, "n")?);
+                let e = BigUint::from_bytes_be(&Self::jwk_param(jwk, "e")?);
+                Ok(RsaPublicKey::new(n, e)?)
+            }
+            JwtKey::Secret(_) => Err(JwtError::Key("RSA needs a PEM key or JWK")),
+        }
+    }
+
+    fn ed25519_signing(&self) -> Result<ed25519_dalek::SigningKey, JwtError> {
+        match self {
+            JwtKey::Pem(pem) => ed25519_dalek::SigningKey::from_pkcs8_pem(pem)
+                .map_err(|_| JwtError::Key("not an Ed25519 private key")),
+            JwtKey::Jwk(jwk) => {
+                let d: [u8; 32] = Self::jwk_param(jwk, "d")?.try_into().map_err(|_| {
+                    JwtError::JwkParameterLength {
+                        name: "d",
+                        length: 32,
+                    }
+                })?;
+                Ok(ed25519_dalek::SigningKey::from_bytes(&d))
+            }
+            JwtKey::Secret(_) => Err(JwtError::Key("EdDSA needs a PEM key or JWK")),
+        }
+    }
+
+    fn ed25519_verifying(&self) -> Result<ed25519_dalek::VerifyingKey, JwtError> {
+        match self {
+            JwtKey::Pem(pem) => ed25519_dalek::VerifyingKey::from_public_key_pem(pem)
+                .ok()
+                .or_else(|| self.ed25519_signing().ok().map(|v| v.verifying_key()))
+                .ok_or(JwtError::Key("not an Ed25519 key")),
+            JwtKey::Jwk(jwk) => {
+                let x: [u8; 32] = Self::jwk_param(jwk, "x")?.try_into().map_err(|_| {
+                    JwtError::JwkParameterLength {
+                        name: "x",
+                        length: 32,
+                    }
+                })?;
+                Ok(ed25519_dalek::VerifyingKey::from_bytes(&x)?)
+            }
+            JwtKey::Secret(_) => Err(JwtError::Key("EdDSA needs a PEM key or JWK")),
+        }
+    }
+}
+
+/// Generate `JwtKey::<signing_fn>` and `JwtKey::<verifying_fn>` for an ECDSA curve crate.
+macro_rules! ecdsa_keys {
+    ($curve:ident, $name:literal, $signing_fn:ident, $verifying_fn:ident) => {
+        impl JwtKey<'_> {
+            fn $signing_fn(&self) -> Result<$curve::ecdsa::SigningKey, JwtError> {
+                let secret = match self {
+                    JwtKey::Pem(pem) => $curve::SecretKey::from_pkcs8_pem(pem)
+                        .ok()
+                        .or_else(|| $curve::SecretKey::from_sec1_pem(pem).ok())
+                        .ok_or(JwtError::Key(concat!("not a ", $name, " private key")))?,
+                    JwtKey::Jwk(jwk) => $curve::SecretKey::from_jwk_str(&jwk.to_string())
+                        .map_err(|_| JwtError::Key(concat!("not a ", $name, " private JWK")))?,
+                    JwtKey::Secret(_) => return Err(JwtError::Key("ECDSA needs a PEM key or JWK")),
+                };
+                Ok(secret.into())
+            }
+
+            fn $verifying_fn(&self) -> Result<$curve::ecdsa::VerifyingKey, JwtError> {
+                let public = match self {
+                    JwtKey::Pem(pem) => $curve::PublicKey::from_public_key_pem(pem)
+                        .ok()
+                        .or_else(|| {
+                            self.$signing_fn()
+                                .ok()
+                                .map(|v| $curve::PublicKey::from(v.verifying_key()))
+                        })
+                        .ok_or(JwtError::Key(concat!("not a ", $name, " key")))?,
+                    JwtKey::Jwk(jwk) => $curve::PublicKey::from_jwk_str(&jwk.to_string())
+                        .map_err(|_| JwtError::Key(concat!("not a ", $name, " JWK")))?,
+                    JwtKey::Secret(_) => return Err(JwtError::Key("ECDSA needs a PEM key or JWK")),
+                };
+                Ok(public.into())
+            }
+        }
+    };
+}
+
+ecdsa_keys!(p256, "P-256", p256_signing, p256_verifying);
+ecdsa_keys!(p384, "P-384", p384_signing, p384_verifying);
+
+fn hmac_sign<M: Mac + KeyInit>(secret: &[u8], message: &[u8]) -> Result<Vec<u8>, JwtError> {
+    let mut mac =
+        <M as KeyInit>::new_from_slice(secret).map_err(|_| JwtError::Key("invalid HMAC secret"))?;
+    mac.update(message);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+fn hmac_verify<M: Mac + KeyInit>(
+    secret: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, JwtError> {
+    let mut mac =
+        <M as KeyInit>::new_from_slice(secret).map_err(|_| JwtError::Key("invalid HMAC secret"))?;
+    mac.update(message);
+    Ok(mac.verify_slice(signature).is_ok())
+}
+
+fn sign_with<S: SignatureEncoding>(signer: impl Signer<S>, message: &[u8]) -> Vec<u8> {
+    signer.sign(message).to_vec()
+}
+
+fn verify_with<S: SignatureEncoding>(
+    verifier: impl Verifier<S>,
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    S::try_from(signature).is_ok_and(|v| verifier.verify(message, &v).is_ok())
+}
+
+fn create_signature(alg: JwtAlgorithm, key: &JwtKey, message: &[u8]) -> Result<Vec<u8>, JwtError> {
+    use rsa::{pkcs1v15, pss};
+
+    Ok(match alg {
+        JwtAlgorithm::Hs256 => hmac_sign::<Hmac<Sha256>>(&key.secret()?, message)?,
+        JwtAlgorithm::Hs384 => hmac_sign::<Hmac<Sha384>>(&key.secret()?, message)?,
+        JwtAlgorithm::Hs512 => hmac_sign::<Hmac<Sha512>>(&key.secret()?, message)?,
+        JwtAlgorithm::Rs256 => sign_with::<pkcs1v15::Signature>(
+            pkcs1v15::SigningKey::<Sha256>::new(key.rsa_private()?),
+            message,
+        ),
+        JwtAlgorithm::Rs384 => sign_with::<pkcs1v15::Signature>(
+            pkcs1v15::SigningKey::<Sha384>::new(key.rsa_private()?),
+            message,
+        ),
+        JwtAlgorithm::Rs512 => sign_with::<pkcs1v15::Signature>(
+            pkcs1v15::SigningKey::<Sha512>::new(key.rsa_private()?),
+            message,
+        ),
+        JwtAlgorithm::Ps256 => pss::BlindedSigningKey::<Sha256>::new(key.rsa_private()?)
+            .sign_with_rng(&mut rand::thread_rng(), message)
+            .to_vec(),
+        JwtAlgorithm::Es256 => sign_with::<p256::ecdsa::Signature>(key.p256_signing()?, message),
+        JwtAlgorithm::Es384 => sign_with::<p384::ecdsa::Signature>(key.p384_signing()?, message),
+        JwtAlgorithm::EdDsa => {
+            sign_with::<ed25519_dalek::Signature>(key.ed25519_signing()?, message)
+        }
+    })
+}
+
+fn verify_signature(
+    alg: JwtAlgorithm,
+    key: &JwtKey,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, JwtError> {
+    use rsa::{pkcs1v15, pss};
+
+    Ok(match alg {
+        JwtAlgorithm::Hs256 => hmac_verify::<Hmac<Sha256>>(&key.secret()?, message, signature)?,
+        JwtAlgorithm::Hs384 => hmac_verify::<Hmac<Sha384>>(&key.secret()?, message, signature)?,
+        JwtAlgorithm::Hs512 => hmac_verify::<Hmac<Sha512>>(&key.secret()?, message, signature)?,
+        JwtAlgorithm::Rs256 => verify_with::<pkcs1v15::Signature>(
+            pkcs1v15::VerifyingKey::<Sha256>::new(key.rsa_public()?),
+            message,
+            signature,
+        ),
+        JwtAlgorithm::Rs384 => verify_with::<pkcs1v15::Signature>(
+            pkcs1v15::VerifyingKey::<Sha384>::new(key.rsa_public()?),
+            message,
+            signature,
+        ),
+        JwtAlgorithm::Rs512 => verify_with::<pkcs1v15::Signature>(
+            pkcs1v15::VerifyingKey::<Sha512>::new(key.rsa_public()?),
+            message,
+            signature,
+        ),
+        JwtAlgorithm::Ps256 => verify_with::<pss::Signature>(
+            pss::VerifyingKey::<Sha256>::new(key.rsa_public()?),
+            message,
+            signature,
+        ),
+        JwtAlgorithm::Es256 => {
+            verify_with::<p256::ecdsa::Signature>(key.p256_verifying()?, message, signature)
+        }
+        JwtAlgorithm::Es384 => {
+            verify_with::<p384::ecdsa::Signature>(key.p384_verifying()?, message, signature)
+        }
+        JwtAlgorithm::EdDsa => {
+            verify_with::<ed25519_dalek::Signature>(key.ed25519_verifying()?, message, signature)
+        }
+    })
+}
+
+fn header_algorithm(header: &serde_json::Value) -> Result<JwtAlgorithm, JwtError> {
+    let alg = header
+        .get("alg")
+        .and_then(|v| v.as_str())
+        .ok_or(JwtError::MissingAlgorithm)?;
+    JwtAlgorithm::from_str(alg).map_err(|_| JwtError::UnsupportedAlgorithm(alg.to_string()))
+}
+
+/// Check the signature of `token` with `key`, then its `exp` and `nbf` claims against `now`.
+pub fn verify_jwt(
+    token: &str,
+    key: &str,
+    now: jiff::Timestamp,
+) -> Result<JwtVerification, JwtError> {
+    let token = token.trim();
+    let (signing_input, signature) = token.rsplit_once('.').ok_or(JwtError::Malformed)?;
+    let (header, payload) = signing_input.split_once('.').ok_or(JwtError::Malformed)?;
+    let header: serde_json::Value =
+        serde_json::from_slice(&BASE64_URL_SAFE_LENIENT.decode(header)?)?;
+    let claims: serde_json::Value =
+        serde_json::from_slice(&BASE64_URL_SAFE_LENIENT.decode(payload)?)?;
+    let signature = BASE64_URL_SAFE_LENIENT.decode(signature)?;
+
+    let alg = header_algorithm(&header)?;
+    let key = JwtKey::parse(key, header.get("kid").and_then(|v| v.as_str()))?;
+    if !verify_signature(alg, &key, signing_input.as_bytes(), &signature)? {
+        return Ok(JwtVerification::InvalidSignature);
+    }
+
+    let claim_time = |name: &str| claims.get(name).and_then(|v| v.as_f64()).map(|v| v as i64);
+    let now = now.as_second();
+    if claim_time("exp").is_some_and(|exp| now >= exp) {
+        return Ok(JwtVerification::Expired);
+    }
+    if claim_time("nbf").is_some_and(|nbf| now < nbf) {
+        return Ok(JwtVerification::NotYetValid);
+    }
+    Ok(JwtVerification::Valid)
+}
+
+/// Build a signed token from JSON `header` and `payload`, using the algorithm named in the header.
+pub fn encode_jwt(header: &str, payload: &str, key: &str) -> Result<String, JwtError> {
+    let header: serde_json::Value = serde_json::from_str(header).map_err(JwtError::HeaderJson)?;
+    let payload: serde_json::Value =
+        serde_json::from_str(payload).map_err(JwtError::PayloadJson)?;
+    let alg = header_algorithm(&header)?;
+    let key = JwtKey::parse(key, header.get("kid").and_then(|v| v.as_str()))?;
+
+    let signing_input = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header)?),
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(&payload)?)
+    );
+    let signature = create_signature(alg, &key, signing_input.as_bytes())?;
+    Ok(format!(
+        "{signing_input}.{}",
+        URL_SAFE_NO_PAD.encode(signature)
+    ))
+}
+
+/// Replace `alg` in a JSON header, keeping the text untouched if it doesn't parse.
+pub fn set_header_algorithm(header: &str, alg: JwtAlgorithm) -> String {
+    let Ok(mut value) = serde_json::from_str::<serde_json::Value>(header) else {
+        return header.to_string();
+    };
+    let Some(object) = value.as_object_mut() else {
+        return header.to_string();
+    };
+    object.insert("alg".into(), alg.to_string().into());
+    serde_json::to_string_pretty(&value).unwrap_or(header.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "secret";
+
+    fn sign(payload: &str) -> String {
+        encode_jwt(DEFAULT_HEADER, payload, SECRET).unwrap()
+    }
+
+    #[test]
+    fn sign_and_decode() {
+        let token = sign(r#"{"sub":"1234567890"}"#);
+        assert_eq!(
+            token,
+            "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiIxMjM0NTY3ODkwIn0.\
+             Rq8IxqeX7eA6GgYxlcHdPFVRNFFZc5rEI3MQTZZbK3I"
+        );
+        let decoded = decode_jwt(&token);
+        assert!(decoded.valid());
+        assert_eq!(decoded.claims.unwrap()["sub"], "1234567890");
+    }
+
+    #[test]
+    fn decode_malformed() {
+        let decoded = decode_jwt("a.b");
+        assert!(!decoded.valid());
+        assert_eq!(
+            decoded.header.text,
+            "expected 3 dot-separated segments, found 2"
+        );
+    }
+
+    #[test]
+    fn decode_segments() {
+        // Padded segments, a payload that isn't JSON and a signature that isn't base64url.
+        let decoded = decode_jwt(" eyJhbGciOiJub25lIn0=.bm90IGpzb24.!! ");
+        assert!(decoded.header.valid);
+        assert!(decoded.header.text.contains(r#""alg": "none""#));
+        assert!(!decoded.payload.valid);
+        assert!(decoded.payload.text.ends_with("\nnot json"));
+        assert!(!decoded.signature.valid);
+        assert_eq!(decoded.claims, None);
+    }
+
+    #[test]
+    fn numeric_dates() {
+        let formatted = format_numeric_date(&serde_json::json!(1700000000)).unwrap();
+        assert!(formatted.starts_with("2023-11-14T22:13:20Z ("));
+        assert_eq!(
+            format_numeric_date(&serde_json::json!(1700000000.9)).unwrap(),
+            formatted
+        );
+        assert_eq!(
+            format_numeric_date(&serde_json::json!("soon")).unwrap_err(),
+            "\"soon\" is not a number"
+        );
+    }
+
+    #[test]
+    fn verify_claims() {
+        let now = jiff::Timestamp::from_second(1000).unwrap();
+        let verify = |payload, key| verify_jwt(&sign(payload), key, now).unwrap();
+        assert_eq!(verify(r#"{"exp":2000}"#, SECRET), JwtVerification::Valid);
+        assert_eq!(verify(r#"{"exp":1000}"#, SECRET), JwtVerification::Expired);
+        assert_eq!(
+            verify(r#"{"nbf":1001}"#, SECRET),
+            JwtVerification::NotYetValid
+        );
+        assert_eq!(
+            verify(r#"{"exp":2000}"#, "wrong"),
+            JwtVerification::InvalidSignature
+        );
+    }
+
+    /// Private and public Ed25519 JWKs made from `seed`.
+    fn ed25519_jwks(seed: u8, kid: &str) -> (String, serde_json::Value) {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[seed; 32]);
+        let x = URL_SAFE_NO_PAD.encode(key.verifying_key().as_bytes());
+        let d = URL_SAFE_NO_PAD.encode(key.as_bytes());
+        let public = serde_json::json!({"kty": "OKP", "crv": "Ed25519", "kid": kid, "x": x});
+        let mut private = public.clone();
+        private["d"] = d.into();
+        (private.to_string(), public)
+    }
+
+    #[test]
+    fn asymmetric_keys() {
+        use p256::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+
+        let now = jiff::Timestamp::from_second(1000).unwrap();
+        let header = |alg: JwtAlgorithm| set_header_algorithm(DEFAULT_HEADER, alg);
+
+        let ec = p256::SecretKey::from_slice(&[1; 32]).unwrap();
+        let ec_private = ec.to_pkcs8_pem(LineEnding::LF).unwrap();
+        let ec_public = ec.public_key().to_public_key_pem(LineEnding::LF).unwrap();
+        let token = encode_jwt(&header(JwtAlgorithm::Es256), "{}", &ec_private).unwrap();
+        assert_eq!(
+            verify_jwt(&token, &ec_public, now).unwrap(),
+            JwtVerification::Valid
+        );
+        assert_eq!(
+            verify_jwt(&token, &ec.to_jwk_string(), now).unwrap(),
+            JwtVerification::Valid
+        );
+
+        let rsa = RsaPrivateKey::new(&mut rand::thread_rng(), 1024).unwrap();
+        let rsa_private = rsa.to_pkcs8_pem(LineEnding::LF).unwrap();
+        let rsa_public = rsa
+            .to_public_key()
+            .to_public_key_pem(LineEnding::LF)
+            .unwrap();
+        for alg in [JwtAlgorithm::Rs256, JwtAlgorithm::Ps256] {
+            let token = encode_jwt(&header(alg), "{}", &rsa_private).unwrap();
+            assert_eq!(
+                verify_jwt(&token, &rsa_public, now).unwrap(),
+                JwtVerification::Valid
+            );
+            assert_eq!(
+                verify_jwt(&token, &ec_public, now).unwrap_err().to_string(),
+                "not an RSA key"
+            );
+        }
+
+        let (private, public) = ed25519_jwks(1, "a");
+        let token = encode_jwt(&header(JwtAlgorithm::EdDsa), "{}", &private).unwrap();
+        assert_eq!(
+            verify_jwt(&token, &public.to_string(), now).unwrap(),
+            JwtVerification::Valid
+        );
+        assert_eq!(
+            verify_jwt(&token, &ed25519_jwks(2, "a").1.to_string(), now).unwrap(),
+            JwtVerification::InvalidSignature
+        );
+    }
+
+    #[test]
+    fn jwk_set_key_ids() {
+        let now = jiff::Timestamp::from_second(1000).unwrap();
+        let (private, public_b) = ed25519_jwks(2, "b");
+        let set = serde_json::json!({"keys": [ed25519_jwks(1, "a").1, public_b]}).to_string();
+        let sign = |kid: Option<&str>| {
+            let mut header = serde_json::json!({"alg": "EdDSA"});
+            if let Some(kid) = kid {
+                header["kid"] = kid.into();
+            }
+            encode_jwt(&header.to_string(), "{}", &private).unwrap()
+        };
+
+        assert_eq!(
+            verify_jwt(&sign(Some("b")), &set, now).unwrap(),
+            JwtVerification::Valid
+        );
+        // Without a `kid` the first key is used.
+        assert_eq!(
+            verify_jwt(&sign(None), &set, now).unwrap(),
+            JwtVerification::InvalidSignature
+        );
+        assert!(matches!(
+            verify_jwt(&sign(Some("c")), &set, now),
+            Err(JwtError::UnknownKeyId(kid)) if kid == "c"
+        ));
+        assert!(matches!(
+            verify_jwt(&sign(None), r#"{"keys": []}"#, now),
+            Err(JwtError::EmptyJwkSet)
+        ));
+    }
+
+    #[test]
+    fn header_errors() {
+        assert!(matches!(
+            encode_jwt("{}", "{}", SECRET),
+            Err(JwtError::MissingAlgorithm)
+        ));
+        assert!(matches!(
+            encode_jwt(r#"{"alg":"none"}"#, "{}", SECRET),
+            Err(JwtError::UnsupportedAlgorithm(alg)) if alg == "none"
+        ));
+        assert!(matches!(
+            encode_jwt(r#"{"alg":"RS256"}"#, "{}", SECRET),
+            Err(JwtError::Key(_))
+        ));
+    }
+
+    #[test]
+    fn replace_algorithm() {
+        let header = set_header_algorithm(DEFAULT_HEADER, JwtAlgorithm::EdDsa);
+        assert!(header.contains(r#""alg": "EdDSA""#));
+        assert_eq!(set_header_algorithm("{", JwtAlgorithm::Hs512), "{");
+    }
+}
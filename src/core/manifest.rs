@@ -0,0 +1,477 @@
+//! Checksum manifests such as `SHA256SUMS`, in the formats of GNU coreutils and BSD.
+
+use std::{
+    ops::ControlFlow,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::core::hash::{HashAlgorithm, MultiHasher, hash_reader};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+)]
+pub enum ManifestFormat {
+    /// `<hex>  <path>`, as written by `sha256sum`.
+    #[strum(to_string = "GNU")]
+    Gnu,
+
+    /// `SHA256 (<path>) = <hex>`, as written by `sha256 -r` or `sha256sum --tag`.
+    #[strum(to_string = "BSD")]
+    Bsd,
+}
+
+/// Name of `algorithm` in BSD-style lines.
+pub fn bsd_tag(algorithm: HashAlgorithm) -> &'static str {
+    match algorithm {
+        HashAlgorithm::Md5 => "MD5",
+        HashAlgorithm::Sha1 => "SHA1",
+        HashAlgorithm::Sha224 => "SHA224",
+        HashAlgorithm::Sha256 => "SHA256",
+        HashAlgorithm::Sha384 => "SHA384",
+        HashAlgorithm::Sha512 => "SHA512",
+        HashAlgorithm::Sha512_224 => "SHA512t224",
+        HashAlgorithm::Sha512_256 => "SHA512t256",
+        HashAlgorithm::Sha3_224 => "SHA3-224",
+        HashAlgorithm::Sha3_256 => "SHA3-256",
+        HashAlgorithm::Sha3_384 => "SHA3-384",
+        HashAlgorithm::Sha3_512 => "SHA3-512",
+        HashAlgorithm::Keccak256 => "KECCAK-256",
+        HashAlgorithm::Shake128 => "SHAKE128",
+        HashAlgorithm::Shake256 => "SHAKE256",
+        HashAlgorithm::Blake2b512 => "BLAKE2b",
+        HashAlgorithm::Blake2s256 => "BLAKE2s",
+        HashAlgorithm::Blake3 => "BLAKE3",
+        HashAlgorithm::Ripemd160 => "RMD160",
+        HashAlgorithm::Sm3 => "SM3",
+        HashAlgorithm::Whirlpool => "WHIRLPOOL",
+        HashAlgorithm::Crc32 => "CRC32",
+        HashAlgorithm::Crc32c => "CRC32C",
+        HashAlgorithm::Crc64 => "CRC64",
+        HashAlgorithm::Adler32 => "ADLER32",
+        HashAlgorithm::Xxh32 => "XXH32",
+        HashAlgorithm::Xxh64 => "XXH64",
+        HashAlgorithm::Xxh3_64 => "XXH3",
+        HashAlgorithm::Xxh3_128 => "XXH128",
+        HashAlgorithm::Fnv1a32 => "FNV1A32",
+        HashAlgorithm::Fnv1a64 => "FNV1A64",
+        HashAlgorithm::Murmur3_32 => "MURMUR3_32",
+        HashAlgorithm::Murmur3_128 => "MURMUR3_128",
+    }
+}
+
+fn algorithm_from_bsd_tag(tag: &str) -> Option<HashAlgorithm> {
+    use strum::IntoEnumIterator;
+
+    HashAlgorithm::iter().find(|v| bsd_tag(*v).eq_ignore_ascii_case(tag))
+}
+
+/// Escape a path the way coreutils does, returning whether the line needs a leading `\`.
+fn escape_path(path: &str) -> (bool, String) {
+    if !path.contains(['\\', '\n', '\r']) {
+        return (false, path.to_string());
+    }
+    let escaped = path
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    (true, escaped)
+}
+
+fn unescape_path(path: &str) -> Option<String> {
+    let mut output = String::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        output.push(match chars.next()? {
+            '\\' => '\\',
+            'n' => '\n',
+            'r' => '\r',
+            _ => return None,
+        });
+    }
+    Some(output)
+}
+
+/// One line of a manifest, `path` being relative to the manifest.
+pub fn format_manifest_line(
+    format: ManifestFormat,
+    algorithm: HashAlgorithm,
+    path: &str,
+    digest: &[u8],
+) -> String {
+    let (escaped, path) = escape_path(path);
+    let prefix = if escaped { "\\" } else { "" };
+    match format {
+        ManifestFormat::Gnu => format!("{prefix}{}  {path}", hex::encode(digest)),
+        ManifestFormat::Bsd => {
+            format!(
+                "{prefix}{} ({path}) = {}",
+                bsd_tag(algorithm),
+                hex::encode(digest)
+            )
+        }
+    }
+}
+
+/// A parsed line of a manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestEntry {
+    pub path: String,
+    /// Given by BSD-style lines only, GNU ones need to be told.
+    pub algorithm: Option<HashAlgorithm>,
+    pub digest: Vec<u8>,
+}
+
+fn parse_line(line: &str) -> Option<ManifestEntry> {
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+    let unescape = |path: &str| {
+        if escaped {
+            unescape_path(path)
+        } else {
+            Some(path.to_string())
+        }
+    };
+
+    // BSD: `TAG (path) = hex`.
+    if let Some((tag, rest)) = line.split_once(" (")
+        && let Some((path, digest)) = rest.rsplit_once(") = ")
+        && let Some(algorithm) = algorithm_from_bsd_tag(tag)
+    {
+        return Some(ManifestEntry {
+            path: unescape(path)?,
+            algorithm: Some(algorithm),
+            digest: hex::decode(digest.trim_end()).ok()?,
+        });
+    }
+
+    // GNU: `hex  path` in text mode, `hex *path` in binary mode.
+    let (digest, rest) = line.split_once(' ')?;
+    let path = rest.strip_prefix([' ', '*']).unwrap_or(rest);
+    if path.is_empty() {
+        return None;
+    }
+    Some(ManifestEntry {
+        path: unescape(path)?,
+        algorithm: None,
+        digest: hex::decode(digest).ok()?,
+    })
+}
+
+/// Every line of `text` but the blank ones and `#` comments, with its 1-based number.
+pub fn parse_manifest(text: &str) -> Vec<(usize, Option<ManifestEntry>)> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| (number, parse_line(line)))
+        .collect()
+}
+
+/// Every file under `root` recursively, relative to it with `/` separators and sorted.
+///
+/// Symbolic links to files are listed, symbolic links to directories aren't followed.
+pub fn list_files(root: &Path) -> std::io::Result<Vec<String>> {
+    fn walk(root: &Path, directory: &Path, files: &mut Vec<String>) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(directory)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                walk(root, &path, files)?;
+            } else if path.is_file()
+                && let Ok(relative) = path.strip_prefix(root)
+            {
+                let parts = relative
+                    .components()
+                    .map(|v| v.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>();
+                files.push(parts.join("/"));
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = vec![];
+    walk(root, root, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+/// How far creating or verifying a manifest went.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ManifestProgress {
+    pub files_done: usize,
+    pub files: usize,
+    /// Bytes hashed so far, over every file.
+    pub bytes: u64,
+}
+
+/// Hash `path` with `algorithm`, reporting the bytes read to `progress`.
+fn hash_path(
+    path: &Path,
+    algorithm: HashAlgorithm,
+    progress: &mut ManifestProgress,
+    report: &mut impl FnMut(ManifestProgress) -> ControlFlow<()>,
+) -> std::io::Result<Option<Vec<u8>>> {
+    let file = std::fs::File::open(path)?;
+    let start = progress.bytes;
+    let digests = hash_reader(file, MultiHasher::new(&[algorithm]), |done| {
+        progress.bytes = start + done;
+        report(*progress)
+    })?;
+    Ok(digests.map(|mut v| v.remove(0)))
+}
+
+/// Manifest of every file under a directory.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CreatedManifest {
+    pub text: String,
+    /// Files that couldn't be read, left out of `text`.
+    pub errors: Vec<String>,
+}
+
+/// Hash every file under `root`, stopping with `Ok(None)` when `report` breaks.
+pub fn create_manifest(
+    root: &Path,
+    algorithm: HashAlgorithm,
+    format: ManifestFormat,
+    mut report: impl FnMut(ManifestProgress) -> ControlFlow<()>,
+) -> std::io::Result<Option<CreatedManifest>> {
+    let files = list_files(root)?;
+    let mut progress = ManifestProgress {
+        files: files.len(),
+        ..Default::default()
+    };
+    let mut manifest = CreatedManifest::default();
+    for file in files {
+        match hash_path(&root.join(&file), algorithm, &mut progress, &mut report) {
+            Ok(Some(digest)) => {
+                manifest
+                    .text
+                    .push_str(&format_manifest_line(format, algorithm, &file, &digest));
+                manifest.text.push('\n');
+            }
+            Ok(None) => return Ok(None),
+            Err(e) => manifest.errors.push(format!("{file}: {e}")),
+        }
+        progress.files_done += 1;
+        if report(progress).is_break() {
+            return Ok(None);
+        }
+    }
+    Ok(Some(manifest))
+}
+
+#[derive(Debug, Clone, PartialEq, derive_more::Display)]
+pub enum VerifyStatus {
+    #[display("OK")]
+    Ok,
+
+    #[display("FAILED")]
+    Failed,
+
+    #[display("MISSING")]
+    Missing,
+
+    #[display("FAILED open or read: {_0}")]
+    Unreadable(String),
+
+    #[display("improperly formatted line")]
+    Improper,
+
+    #[display("{_0}-byte digest isn't {_1}")]
+    WrongLength(usize, HashAlgorithm),
+
+    #[display("path is absolute or goes outside the directory")]
+    OutsideRoot,
+}
+
+/// Outcome of checking one line of a manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyResult {
+    pub line: usize,
+    pub path: String,
+    pub status: VerifyStatus,
+}
+
+/// Check every file listed in `text` against the files under `root`.
+///
+/// GNU-style lines don't name their algorithm, they are checked with `algorithm`.
+/// Stops with `None` when `report` breaks.
+pub fn verify_manifest(
+    text: &str,
+    root: &Path,
+    algorithm: HashAlgorithm,
+    mut report: impl FnMut(ManifestProgress) -> ControlFlow<()>,
+) -> Option<Vec<VerifyResult>> {
+    let lines = parse_manifest(text);
+    let mut progress = ManifestProgress {
+        files: lines.len(),
+        ..Default::default()
+    };
+    let mut results = vec![];
+    for (line, entry) in lines {
+        let (path, status) = match entry {
+            None => (String::new(), VerifyStatus::Improper),
+            Some(entry) => {
+                let algorithm = entry.algorithm.unwrap_or(algorithm);
+                let full_path = root.join(PathBuf::from(&entry.path));
+                let outside_root = Path::new(&entry.path)
+                    .components()
+                    .any(|v| !matches!(v, Component::Normal(_) | Component::CurDir));
+                let status = if outside_root {
+                    VerifyStatus::OutsideRoot
+                } else if entry.digest.len() != algorithm.output_size() {
+                    VerifyStatus::WrongLength(entry.digest.len(), algorithm)
+                } else if !full_path.exists() {
+                    VerifyStatus::Missing
+                } else {
+                    match hash_path(&full_path, algorithm, &mut progress, &mut report) {
+                        Ok(Some(digest)) if digest == entry.digest => VerifyStatus::Ok,
+                        Ok(Some(_)) => VerifyStatus::Failed,
+                        Ok(None) => return None,
+                        Err(e) => VerifyStatus::Unreadable(e.to_string()),
+                    }
+                };
+                (entry.path, status)
+            }
+        };
+        results.push(VerifyResult { line, path, status });
+        progress.files_done += 1;
+        if report(progress).is_break() {
+            return None;
+        }
+    }
+    Some(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::hash::hash;
+
+    #[test]
+    fn format_and_parse_lines() {
+        let digest = hash(HashAlgorithm::Sha256, b"abc");
+        let gnu = format_manifest_line(ManifestFormat::Gnu, HashAlgorithm::Sha256, "a/b", &digest);
+        assert_eq!(
+            gnu,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  a/b"
+        );
+        let bsd = format_manifest_line(ManifestFormat::Bsd, HashAlgorithm::Sha256, "a/b", &digest);
+        assert_eq!(
+            bsd,
+            "SHA256 (a/b) = ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let escaped = format_manifest_line(ManifestFormat::Gnu, HashAlgorithm::Md5, "a\nb", &[0]);
+        assert_eq!(escaped, "\\00  a\\nb");
+
+        let text = format!("# comment\n{gnu}\n\n{bsd}\n{escaped}\n12 *c d\nnot a line\n");
+        let lines = parse_manifest(&text);
+        let entry = |path: &str, algorithm, digest: &[u8]| ManifestEntry {
+            path: path.to_string(),
+            algorithm,
+            digest: digest.to_vec(),
+        };
+        assert_eq!(
+            lines,
+            [
+                (2, Some(entry("a/b", None, &digest))),
+                (4, Some(entry("a/b", Some(HashAlgorithm::Sha256), &digest))),
+                (5, Some(entry("a\nb", None, &[0]))),
+                (6, Some(entry("c d", None, &[0x12]))),
+                (7, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn create_and_verify() {
+        let root = std::env::temp_dir().join(format!("utils-manifest-{}", std::process::id()));
+        _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::write(root.join("a.txt"), "a").unwrap();
+        std::fs::write(root.join("sub/b.txt"), "b").unwrap();
+
+        let manifest = create_manifest(&root, HashAlgorithm::Sha256, ManifestFormat::Gnu, |_| {
+            ControlFlow::Continue(())
+        })
+        .unwrap()
+        .unwrap();
+        assert!(manifest.errors.is_empty());
+        assert_eq!(manifest.text.lines().count(), 2);
+        assert!(manifest.text.ends_with("  sub/b.txt\n"));
+
+        std::fs::write(root.join("a.txt"), "changed").unwrap();
+        std::fs::remove_file(root.join("sub/b.txt")).unwrap();
+        let text = format!("{}garbage\n", manifest.text);
+        let statuses = verify_manifest(&text, &root, HashAlgorithm::Sha256, |_| {
+            ControlFlow::Continue(())
+        })
+        .unwrap()
+        .into_iter()
+        .map(|v| v.status)
+        .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            [
+                VerifyStatus::Failed,
+                VerifyStatus::Missing,
+                VerifyStatus::Improper
+            ]
+        );
+
+        let statuses = verify_manifest(&manifest.text, &root, HashAlgorithm::Md5, |_| {
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(
+            statuses[0].status,
+            VerifyStatus::WrongLength(32, HashAlgorithm::Md5)
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks() {
+        let root =
+            std::env::temp_dir().join(format!("utils-manifest-links-{}", std::process::id()));
+        _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::write(root.join("sub/a.txt"), "a").unwrap();
+        std::os::unix::fs::symlink(&root, root.join("sub/loop")).unwrap();
+        std::os::unix::fs::symlink(root.join("sub/a.txt"), root.join("b.txt")).unwrap();
+
+        let files = list_files(&root);
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(files.unwrap(), ["b.txt", "sub/a.txt"]);
+    }
+
+    #[test]
+    fn paths_outside_root() {
+        let digest = hex::encode(hash(HashAlgorithm::Sha256, b""));
+        let text = format!("{digest}  ../secret\n{digest}  /etc/passwd\n{digest}  ./a/../b\n");
+        let statuses = verify_manifest(&text, Path::new("."), HashAlgorithm::Sha256, |_| {
+            ControlFlow::Continue(())
+        })
+        .unwrap()
+        .into_iter()
+        .map(|v| v.status)
+        .collect::<Vec<_>>();
+        assert_eq!(statuses, vec![VerifyStatus::OutsideRoot; 3]);
+    }
+}
@@ -0,0 +1,58 @@
+//! Framework-free implementation of every tool.
+//!
+//! Nothing in here knows about Dioxus: views in `crate::views` and the headless CLI in
+//! `crate::cli` are thin wrappers around these functions.
+
+pub mod base64;
+pub mod certificate;
+pub mod checksum;
+pub mod datetime;
+pub mod detect;
+pub mod fuzzy;
+pub mod hash;
+pub mod ip;
+pub mod jwt;
+pub mod manifest;
+pub mod password;
+pub mod pipeline;
+
+/// How text typed by the user is turned into bytes.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+pub enum InputKind {
+    #[strum(to_string = "UTF-8")]
+    Utf8,
+    Hex,
+}
+
+impl InputKind {
+    /// Convert `input` to bytes; hex may contain whitespace between digits.
+    pub fn decode(self, input: &str) -> Result<Vec<u8>, hex::FromHexError> {
+        match self {
+            InputKind::Utf8 => Ok(input.as_bytes().to_vec()),
+            InputKind::Hex => hex::decode(input.replace([' ', '\t', '\r', '\n'], "")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_input() {
+        assert_eq!(InputKind::Utf8.decode("ab").unwrap(), b"ab");
+        assert_eq!(InputKind::Hex.decode("61 62\n63").unwrap(), b"abc");
+        assert!(InputKind::Hex.decode("6").is_err());
+    }
+}
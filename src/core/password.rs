@@ -0,0 +1,513 @@
+use argon2::Argon2;
+use password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng};
+use pbkdf2::Pbkdf2;
+use scrypt::Scrypt;
+
+use crate::core::hash::digest_eq;
+
+/// Most memory Argon2 and scrypt may use, allocating more could abort the whole app.
+const MAX_MEMORY_KIB: u64 = 2 * 1024 * 1024;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+)]
+pub enum PasswordAlgorithm {
+    #[strum(to_string = "bcrypt")]
+    Bcrypt,
+
+    Argon2id,
+    Argon2i,
+    Argon2d,
+
+    #[strum(to_string = "scrypt")]
+    Scrypt,
+
+    #[strum(to_string = "PBKDF2-SHA256")]
+    Pbkdf2Sha256,
+
+    #[strum(to_string = "PBKDF2-SHA512")]
+    Pbkdf2Sha512,
+
+    #[strum(to_string = "SHA-256 crypt ($5$)")]
+    Sha256Crypt,
+
+    #[strum(to_string = "SHA-512 crypt ($6$)")]
+    Sha512Crypt,
+}
+
+/// Cost of every algorithm, each one only reads its own.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct PasswordParams {
+    /// Base-2 logarithm of the number of rounds.
+    pub bcrypt_cost: u32,
+    /// KiB.
+    pub argon2_memory: u32,
+    pub argon2_iterations: u32,
+    pub argon2_parallelism: u32,
+    /// Base-2 logarithm of N.
+    pub scrypt_log_n: u32,
+    pub scrypt_r: u32,
+    pub scrypt_p: u32,
+    pub pbkdf2_rounds: u32,
+    pub crypt_rounds: u32,
+}
+
+impl Default for PasswordParams {
+    /// What the libraries recommend, and OWASP's iterations for PBKDF2.
+    fn default() -> Self {
+        Self {
+            bcrypt_cost: bcrypt::DEFAULT_COST,
+            argon2_memory: argon2::Params::DEFAULT_M_COST,
+            argon2_iterations: argon2::Params::DEFAULT_T_COST,
+            argon2_parallelism: argon2::Params::DEFAULT_P_COST,
+            scrypt_log_n: scrypt::Params::RECOMMENDED_LOG_N as u32,
+            scrypt_r: scrypt::Params::RECOMMENDED_R,
+            scrypt_p: scrypt::Params::RECOMMENDED_P,
+            pbkdf2_rounds: 600_000,
+            crypt_rounds: 5_000,
+        }
+    }
+}
+
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum PasswordError {
+    #[display("{_0}")]
+    #[from]
+    Bcrypt(bcrypt::BcryptError),
+
+    #[display("{_0}")]
+    #[from]
+    Phc(password_hash::Error),
+
+    #[display("invalid Argon2 parameters: {_0}")]
+    #[from]
+    Argon2(argon2::Error),
+
+    #[display("invalid scrypt parameters")]
+    #[from]
+    Scrypt(scrypt::errors::InvalidParams),
+
+    #[display("rounds must be between 1000 and 999999999")]
+    CryptRounds,
+
+    #[display("parameters need {_0} KiB of memory, more than 2 GiB")]
+    TooMuchMemory(u64),
+
+    #[display("invalid crypt hash: {_0}")]
+    InvalidCrypt(String),
+
+    #[display("not a bcrypt, crypt or PHC hash")]
+    UnknownFormat,
+}
+
+impl std::error::Error for PasswordError {}
+
+/// Hash `password` with a random salt, in the format other implementations store it: modular
+/// crypt for bcrypt and SHA-crypt, PHC strings for the others.
+pub fn hash_password(
+    algorithm: PasswordAlgorithm,
+    params: &PasswordParams,
+    password: &str,
+) -> Result<String, PasswordError> {
+    let salt = SaltString::generate(&mut OsRng);
+    let password_bytes = password.as_bytes();
+    Ok(match algorithm {
+        PasswordAlgorithm::Bcrypt => bcrypt::hash(password_bytes, params.bcrypt_cost)?,
+        PasswordAlgorithm::Argon2id | PasswordAlgorithm::Argon2i | PasswordAlgorithm::Argon2d => {
+            let variant = match algorithm {
+                PasswordAlgorithm::Argon2i => argon2::Algorithm::Argon2i,
+                PasswordAlgorithm::Argon2d => argon2::Algorithm::Argon2d,
+                _ => argon2::Algorithm::Argon2id,
+            };
+            check_memory(argon2_memory_kib(
+                params.argon2_memory,
+                params.argon2_parallelism,
+            ))?;
+            let argon2_params = argon2::Params::new(
+                params.argon2_memory,
+                params.argon2_iterations,
+                params.argon2_parallelism,
+                None,
+            )?;
+            Argon2::new(variant, argon2::Version::V0x13, argon2_params)
+                .hash_password(password_bytes, &salt)?
+                .to_string()
+        }
+        PasswordAlgorithm::Scrypt => {
+            let log_n =
+                u8::try_from(params.scrypt_log_n).map_err(|_| scrypt::errors::InvalidParams)?;
+            check_memory(scrypt_memory_kib(
+                params.scrypt_log_n,
+                params.scrypt_r,
+                params.scrypt_p,
+            ))?;
+            let scrypt_params = scrypt::Params::new(
+                log_n,
+                params.scrypt_r,
+                params.scrypt_p,
+                scrypt::Params::RECOMMENDED_LEN,
+            )?;
+            Scrypt
+                .hash_password_customized(password_bytes, None, None, scrypt_params, &salt)?
+                .to_string()
+        }
+        PasswordAlgorithm::Pbkdf2Sha256 | PasswordAlgorithm::Pbkdf2Sha512 => {
+            let variant = match algorithm {
+                PasswordAlgorithm::Pbkdf2Sha512 => pbkdf2::Algorithm::Pbkdf2Sha512,
+                _ => pbkdf2::Algorithm::Pbkdf2Sha256,
+            };
+            let pbkdf2_params = pbkdf2::Params {
+                rounds: params.pbkdf2_rounds,
+                output_length: 32,
+            };
+            Pbkdf2
+                .hash_password_customized(
+                    password_bytes,
+                    Some(variant.ident()),
+                    None,
+                    pbkdf2_params,
+                    &salt,
+                )?
+                .to_string()
+        }
+        PasswordAlgorithm::Sha256Crypt => {
+            let rounds = params.crypt_rounds as usize;
+            let crypt_params =
+                sha_crypt::Sha256Params::new(rounds).map_err(|_| PasswordError::CryptRounds)?;
+            sha_crypt::sha256_simple(password, &crypt_params)
+                .map_err(|_| PasswordError::CryptRounds)?
+        }
+        PasswordAlgorithm::Sha512Crypt => {
+            let rounds = params.crypt_rounds as usize;
+            let crypt_params =
+                sha_crypt::Sha512Params::new(rounds).map_err(|_| PasswordError::CryptRounds)?;
+            sha_crypt::sha512_simple(password, &crypt_params)
+                .map_err(|_| PasswordError::CryptRounds)?
+        }
+    })
+}
+
+/// Memory Argon2 allocates, at least 8 blocks of 1 KiB per lane.
+fn argon2_memory_kib(memory: u32, parallelism: u32) -> u64 {
+    u64::from(memory).max(8 * u64::from(parallelism))
+}
+
+/// Memory scrypt allocates, `128 * r * N` bytes plus `128 * r` per unit of parallelism.
+fn scrypt_memory_kib(log_n: u32, r: u32, p: u32) -> u64 {
+    1u64.checked_shl(log_n)
+        .unwrap_or(u64::MAX)
+        .saturating_add(u64::from(p))
+        .saturating_mul(128 * u64::from(r))
+        / 1024
+}
+
+fn check_memory(kib: u64) -> Result<(), PasswordError> {
+    if kib > MAX_MEMORY_KIB {
+        return Err(PasswordError::TooMuchMemory(kib));
+    }
+    Ok(())
+}
+
+/// What a stored hash says about how it was made.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedHash {
+    pub algorithm: PasswordAlgorithm,
+    /// Name and value of every parameter, in the order of the hash.
+    pub params: Vec<(String, String)>,
+    /// As written in the hash.
+    pub salt: String,
+    pub hash: String,
+}
+
+/// Name of a PHC parameter for people who don't know the specifications by heart.
+fn phc_param_name(name: &str) -> String {
+    let description = match name {
+        "v" => "version",
+        "m" => "memory, KiB",
+        "t" => "iterations",
+        "p" => "parallelism",
+        "ln" => "log₂ N",
+        "r" => "block size",
+        "i" => "rounds",
+        "l" => "output length",
+        _ => return name.to_string(),
+    };
+    format!("{name} ({description})")
+}
+
+/// Read the algorithm, parameters and salt of a `$2b$…`, `$5$…`, `$6$…` or PHC hash.
+pub fn parse_password_hash(hash: &str) -> Result<ParsedHash, PasswordError> {
+    let hash = hash.trim();
+    if let Some(rest) = hash.strip_prefix("$2") {
+        // `$2b$12$` then 22 characters of salt and 31 of hash.
+        let mut parts = rest.split('$');
+        let (Some(minor), Some(cost), Some(salt_hash), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(bcrypt::BcryptError::InvalidHash(hash.to_string()).into());
+        };
+        if !matches!(minor, "a" | "b" | "x" | "y")
+            || cost.parse::<u32>().is_err()
+            || salt_hash.len() != 53
+            || !salt_hash.is_ascii()
+        {
+            return Err(bcrypt::BcryptError::InvalidHash(hash.to_string()).into());
+        }
+        return Ok(ParsedHash {
+            algorithm: PasswordAlgorithm::Bcrypt,
+            params: vec![
+                ("version".to_string(), format!("2{minor}")),
+                ("cost".to_string(), cost.to_string()),
+            ],
+            salt: salt_hash[..22].to_string(),
+            hash: salt_hash[22..].to_string(),
+        });
+    }
+
+    for (prefix, algorithm) in [
+        ("$5$", PasswordAlgorithm::Sha256Crypt),
+        ("$6$", PasswordAlgorithm::Sha512Crypt),
+    ] {
+        let Some(rest) = hash.strip_prefix(prefix) else {
+            continue;
+        };
+        let mut parts = rest.split('$').collect::<Vec<_>>();
+        let rounds = match parts.first().and_then(|v| v.strip_prefix("rounds=")) {
+            Some(rounds) => {
+                let rounds = rounds.parse::<u64>().map_err(|_| {
+                    PasswordError::InvalidCrypt("rounds must be a number".to_string())
+                })?;
+                parts.remove(0);
+                // Out of range rounds are clamped as glibc does, not rejected.
+                rounds.clamp(sha_crypt::ROUNDS_MIN as u64, sha_crypt::ROUNDS_MAX as u64) as u32
+            }
+            None => sha_crypt::ROUNDS_DEFAULT as u32,
+        };
+        let [salt, hash] = parts[..] else {
+            return Err(PasswordError::InvalidCrypt(
+                "expected a salt and a hash".to_string(),
+            ));
+        };
+        return Ok(ParsedHash {
+            algorithm,
+            params: vec![("rounds".to_string(), rounds.to_string())],
+            salt: salt.to_string(),
+            hash: hash.to_string(),
+        });
+    }
+
+    let parsed = PasswordHash::new(hash)?;
+    let algorithm = match parsed.algorithm.as_str() {
+        "argon2id" => PasswordAlgorithm::Argon2id,
+        "argon2i" => PasswordAlgorithm::Argon2i,
+        "argon2d" => PasswordAlgorithm::Argon2d,
+        "scrypt" => PasswordAlgorithm::Scrypt,
+        "pbkdf2-sha256" => PasswordAlgorithm::Pbkdf2Sha256,
+        "pbkdf2-sha512" => PasswordAlgorithm::Pbkdf2Sha512,
+        _ => return Err(PasswordError::UnknownFormat),
+    };
+    let params = parsed
+        .version
+        .map(|v| (phc_param_name("v"), v.to_string()))
+        .into_iter()
+        .chain(
+            parsed
+                .params
+                .iter()
+                .map(|(name, value)| (phc_param_name(name.as_str()), value.to_string())),
+        )
+        .collect();
+    Ok(ParsedHash {
+        algorithm,
+        params,
+        salt: parsed.salt.map(|v| v.to_string()).unwrap_or_default(),
+        hash: parsed.hash.map(|v| v.to_string()).unwrap_or_default(),
+    })
+}
+
+/// Whether `password` is the one `hash` was made from, the algorithm and parameters being read
+/// from `hash`.
+pub fn verify_password(password: &str, hash: &str) -> Result<bool, PasswordError> {
+    let hash = hash.trim();
+    let phc_result = |result: password_hash::Result<()>| match result {
+        Ok(()) => Ok(true),
+        Err(password_hash::Error::Password) => Ok(false),
+        Err(e) => Err(PasswordError::Phc(e)),
+    };
+    let parsed = parse_password_hash(hash)?;
+    match parsed.algorithm {
+        PasswordAlgorithm::Bcrypt => Ok(bcrypt::verify(password, hash)?),
+        PasswordAlgorithm::Sha256Crypt | PasswordAlgorithm::Sha512Crypt => {
+            let rounds = parsed.params[0].1.parse::<usize>().unwrap_or_default();
+            let (password, salt) = (password.as_bytes(), parsed.salt.as_bytes());
+            let computed = if parsed.algorithm == PasswordAlgorithm::Sha256Crypt {
+                sha_crypt::Sha256Params::new(rounds)
+                    .and_then(|params| sha_crypt::sha256_crypt_b64(password, salt, &params))
+            } else {
+                sha_crypt::Sha512Params::new(rounds)
+                    .and_then(|params| sha_crypt::sha512_crypt_b64(password, salt, &params))
+            }
+            .map_err(|_| PasswordError::CryptRounds)?;
+            Ok(digest_eq(computed.as_bytes(), parsed.hash.as_bytes()))
+        }
+        PasswordAlgorithm::Argon2id | PasswordAlgorithm::Argon2i | PasswordAlgorithm::Argon2d => {
+            let phc = PasswordHash::new(hash)?;
+            let param = |name| phc.params.get_decimal(name).unwrap_or_default();
+            check_memory(argon2_memory_kib(param("m"), param("p")))?;
+            phc_result(Argon2::default().verify_password(password.as_bytes(), &phc))
+        }
+        PasswordAlgorithm::Scrypt => {
+            let phc = PasswordHash::new(hash)?;
+            let param = |name| phc.params.get_decimal(name).unwrap_or_default();
+            check_memory(scrypt_memory_kib(param("ln"), param("r"), param("p")))?;
+            phc_result(Scrypt.verify_password(password.as_bytes(), &phc))
+        }
+        PasswordAlgorithm::Pbkdf2Sha256 | PasswordAlgorithm::Pbkdf2Sha512 => {
+            phc_result(Pbkdf2.verify_password(password.as_bytes(), &PasswordHash::new(hash)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    /// Parameters keeping the tests fast, far too weak for real use.
+    fn cheap_params() -> PasswordParams {
+        PasswordParams {
+            bcrypt_cost: 4,
+            argon2_memory: 64,
+            argon2_iterations: 1,
+            argon2_parallelism: 1,
+            scrypt_log_n: 4,
+            scrypt_r: 8,
+            scrypt_p: 1,
+            pbkdf2_rounds: 1_000,
+            crypt_rounds: 1_000,
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        for algorithm in PasswordAlgorithm::iter() {
+            let hash = hash_password(algorithm, &cheap_params(), "hunter2").unwrap();
+            assert_eq!(
+                parse_password_hash(&hash).unwrap().algorithm,
+                algorithm,
+                "{hash}"
+            );
+            assert!(verify_password("hunter2", &hash).unwrap(), "{hash}");
+            assert!(!verify_password("hunter3", &hash).unwrap(), "{hash}");
+        }
+    }
+
+    #[test]
+    fn known_hashes() {
+        // From the SHA-crypt specification.
+        let hash = "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1";
+        assert!(verify_password("Hello world!", hash).unwrap());
+        // From the OpenBSD test suite.
+        let hash = "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW";
+        assert!(verify_password("U*U", hash).unwrap());
+        assert!(matches!(
+            verify_password("U*U", "$1$abc$def"),
+            Err(PasswordError::Phc(_) | PasswordError::UnknownFormat)
+        ));
+    }
+
+    #[test]
+    fn parsed_parameters() {
+        let hash = hash_password(PasswordAlgorithm::Argon2id, &cheap_params(), "x").unwrap();
+        let parsed = parse_password_hash(&hash).unwrap();
+        let params = parsed
+            .params
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            params,
+            [
+                "v (version)=19",
+                "m (memory, KiB)=64",
+                "t (iterations)=1",
+                "p (parallelism)=1"
+            ]
+        );
+        assert_eq!(parsed.salt.len(), 22);
+
+        let parsed = parse_password_hash(
+            "$5$rounds=10000$saltstringsaltst$3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA",
+        )
+        .unwrap();
+        assert_eq!(parsed.algorithm, PasswordAlgorithm::Sha256Crypt);
+        assert_eq!(parsed.params, [("rounds".to_string(), "10000".to_string())]);
+        assert_eq!(parsed.salt, "saltstringsaltst");
+
+        let parsed = parse_password_hash("$6$rounds=10$roundstoolow$hash").unwrap();
+        assert_eq!(parsed.params, [("rounds".to_string(), "1000".to_string())]);
+
+        let parsed =
+            parse_password_hash("$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW")
+                .unwrap();
+        assert_eq!(parsed.params[1], ("cost".to_string(), "12".to_string()));
+        assert_eq!(parsed.salt, "R9h/cIPz0gi.URNNX3kh2O");
+    }
+
+    #[test]
+    fn crypt_rounds_clamped() {
+        // From the test vectors of the SHA-crypt specification.
+        let password = "the minimum number is still observed";
+        let hash = "$5$rounds=10$roundstoolow$yfvwcWrQ8l/K0DAWyuPMDNHpIVlTQebY9l/gL972bIC";
+        assert!(verify_password(password, hash).unwrap());
+        assert!(!verify_password("other", hash).unwrap());
+    }
+
+    #[test]
+    fn memory_limit() {
+        let hash = "$argon2id$v=19$m=4294967295,t=1,p=1$c2FsdHNhbHQ$aGFzaGhhc2hoYXNoaGFzaA";
+        assert!(matches!(
+            verify_password("hunter2", hash),
+            Err(PasswordError::TooMuchMemory(4294967295))
+        ));
+        let hash = "$scrypt$ln=40,r=8,p=1$c2FsdHNhbHQ$aGFzaGhhc2hoYXNoaGFzaA";
+        assert!(matches!(
+            verify_password("hunter2", hash),
+            Err(PasswordError::TooMuchMemory(_))
+        ));
+
+        let params = PasswordParams {
+            argon2_memory: u32::MAX,
+            ..cheap_params()
+        };
+        assert!(matches!(
+            hash_password(PasswordAlgorithm::Argon2id, &params, "hunter2"),
+            Err(PasswordError::TooMuchMemory(_))
+        ));
+        let params = PasswordParams {
+            argon2_parallelism: 1 << 20,
+            ..cheap_params()
+        };
+        assert!(matches!(
+            hash_password(PasswordAlgorithm::Argon2id, &params, "hunter2"),
+            Err(PasswordError::TooMuchMemory(_))
+        ));
+        let params = PasswordParams {
+            scrypt_log_n: 30,
+            ..cheap_params()
+        };
+        assert!(matches!(
+            hash_password(PasswordAlgorithm::Scrypt, &params, "hunter2"),
+            Err(PasswordError::TooMuchMemory(_))
+        ));
+    }
+}
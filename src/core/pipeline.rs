@@ -0,0 +1,328 @@
+use std::{fmt, io::Read, io::Write};
+
+use crate::core::{
+    base64::{Base64Alphabet, decode_with, encode},
+    hash::{HashAlgorithm, hash},
+};
+
+/// Longest preview of the bytes between two steps.
+const MAX_PREVIEW_BYTES: usize = 4096;
+
+/// Largest output of a decompression step, so a small gzip bomb can't fill the memory.
+pub const MAX_GUNZIP_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum PipelineError {
+    #[display("invalid hex: {_0}")]
+    #[from]
+    Hex(hex::FromHexError),
+
+    #[display("invalid base64: {_0}")]
+    #[from]
+    Base64(base64::DecodeError),
+
+    #[display("invalid JSON: {_0}")]
+    #[from]
+    Json(serde_json::Error),
+
+    #[display("invalid gzip data: {_0}")]
+    #[from]
+    Gzip(std::io::Error),
+
+    #[display("not UTF-8 text")]
+    NotUtf8,
+
+    #[display("invalid percent-encoding at byte {_0}")]
+    InvalidPercentEncoding(usize),
+
+    #[display("output too large, more than {_0} bytes")]
+    OutputTooLarge(u64),
+}
+
+impl std::error::Error for PipelineError {}
+
+/// One step of a recipe, turning bytes into other bytes.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "op")]
+pub enum Operation {
+    FromHex,
+    ToHex,
+    FromBase64 { alphabet: Base64Alphabet },
+    ToBase64 { alphabet: Base64Alphabet },
+    Gunzip,
+    Gzip,
+    JsonPretty,
+    JsonMinify,
+    UrlDecode,
+    UrlEncode,
+    Hash { algorithm: HashAlgorithm },
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::FromBase64 { alphabet } | Operation::ToBase64 { alphabet } => {
+                write!(f, "{} ({alphabet})", self.name())
+            }
+            Operation::Hash { algorithm } => write!(f, "{} ({algorithm})", self.name()),
+            _ => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+impl Operation {
+    /// Every operation, with default parameters.
+    pub fn all() -> Vec<Operation> {
+        vec![
+            Operation::FromHex,
+            Operation::ToHex,
+            Operation::FromBase64 {
+                alphabet: Base64Alphabet::Standard,
+            },
+            Operation::ToBase64 {
+                alphabet: Base64Alphabet::Standard,
+            },
+            Operation::Gunzip,
+            Operation::Gzip,
+            Operation::JsonPretty,
+            Operation::JsonMinify,
+            Operation::UrlDecode,
+            Operation::UrlEncode,
+            Operation::Hash {
+                algorithm: HashAlgorithm::Sha256,
+            },
+        ]
+    }
+
+    /// Name without the parameters.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operation::FromHex => "From Hex",
+            Operation::ToHex => "To Hex",
+            Operation::FromBase64 { .. } => "From Base64",
+            Operation::ToBase64 { .. } => "To Base64",
+            Operation::Gunzip => "Gunzip",
+            Operation::Gzip => "Gzip",
+            Operation::JsonPretty => "JSON Pretty",
+            Operation::JsonMinify => "JSON Minify",
+            Operation::UrlDecode => "URL Decode",
+            Operation::UrlEncode => "URL Encode",
+            Operation::Hash { .. } => "Hash",
+        }
+    }
+
+    pub fn apply(&self, input: &[u8]) -> Result<Vec<u8>, PipelineError> {
+        Ok(match self {
+            Operation::FromHex => hex::decode(text(input)?.split_whitespace().collect::<String>())?,
+            Operation::ToHex => hex::encode(input).into_bytes(),
+            Operation::FromBase64 { alphabet } => {
+                let text = text(input)?.split_whitespace().collect::<String>();
+                decode_with(&text, *alphabet)?
+            }
+            Operation::ToBase64 { alphabet } => encode(input, *alphabet).into_bytes(),
+            Operation::Gunzip => {
+                let mut output = vec![];
+                flate2::read::MultiGzDecoder::new(input)
+                    .take(MAX_GUNZIP_BYTES + 1)
+                    .read_to_end(&mut output)?;
+                if output.len() as u64 > MAX_GUNZIP_BYTES {
+                    return Err(PipelineError::OutputTooLarge(MAX_GUNZIP_BYTES));
+                }
+                output
+            }
+            Operation::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+                encoder.write_all(input)?;
+                encoder.finish()?
+            }
+            Operation::JsonPretty => {
+                serde_json::to_vec_pretty(&serde_json::from_slice::<serde_json::Value>(input)?)?
+            }
+            Operation::JsonMinify => {
+                serde_json::to_vec(&serde_json::from_slice::<serde_json::Value>(input)?)?
+            }
+            Operation::UrlDecode => percent_decode(input)?,
+            Operation::UrlEncode => percent_encode(input).into_bytes(),
+            Operation::Hash { algorithm } => hash(*algorithm, input),
+        })
+    }
+}
+
+fn text(input: &[u8]) -> Result<&str, PipelineError> {
+    std::str::from_utf8(input).map_err(|_| PipelineError::NotUtf8)
+}
+
+/// `%XX` escapes and `+` for spaces, as in `application/x-www-form-urlencoded`.
+fn percent_decode(input: &[u8]) -> Result<Vec<u8>, PipelineError> {
+    let mut output = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        match input[i] {
+            b'%' => {
+                let digits = input
+                    .get(i + 1..i + 3)
+                    .ok_or(PipelineError::InvalidPercentEncoding(i))?;
+                let byte =
+                    hex::decode(digits).map_err(|_| PipelineError::InvalidPercentEncoding(i))?;
+                output.extend(byte);
+                i += 3;
+            }
+            b'+' => {
+                output.push(b' ');
+                i += 1;
+            }
+            byte => {
+                output.push(byte);
+                i += 1;
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// Escape everything but the unreserved characters of RFC 3986.
+fn percent_encode(input: &[u8]) -> String {
+    input
+        .iter()
+        .map(|&v| match v {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (v as char).to_string()
+            }
+            _ => format!("%{v:02X}"),
+        })
+        .collect()
+}
+
+/// Named list of operations, exported and imported as JSON.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Recipe {
+    pub name: String,
+    pub steps: Vec<Operation>,
+}
+
+/// Output of every step in order, up to and including the first one that fails.
+pub fn run_pipeline(input: &[u8], steps: &[Operation]) -> Vec<Result<Vec<u8>, PipelineError>> {
+    let mut results: Vec<Result<Vec<u8>, PipelineError>> = vec![];
+    for step in steps {
+        let input = match results.last() {
+            None => input,
+            Some(Ok(v)) => v,
+            Some(Err(_)) => break,
+        };
+        results.push(step.apply(input));
+    }
+    results
+}
+
+/// Bytes between two steps as text if they are UTF-8, as a hex dump otherwise.
+pub fn preview(bytes: &[u8]) -> String {
+    let truncated = &bytes[..bytes.len().min(MAX_PREVIEW_BYTES)];
+    let mut preview = match std::str::from_utf8(truncated) {
+        Ok(v) => v.to_string(),
+        Err(e) if bytes.len() > MAX_PREVIEW_BYTES && e.error_len().is_none() => {
+            // Cut in the middle of a character.
+            String::from_utf8_lossy(&truncated[..e.valid_up_to()]).to_string()
+        }
+        Err(_) => pretty_hex::pretty_hex(&truncated),
+    };
+    if bytes.len() > MAX_PREVIEW_BYTES {
+        preview.push_str(&format!(
+            "\n… {} more bytes",
+            bytes.len() - MAX_PREVIEW_BYTES
+        ));
+    }
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_base64_gunzip_json() {
+        let json = br#"{"a":[1,2]}"#;
+        let gzipped = Operation::Gzip.apply(json).unwrap();
+        let input = hex::encode(
+            Operation::ToBase64 {
+                alphabet: Base64Alphabet::Standard,
+            }
+            .apply(&gzipped)
+            .unwrap(),
+        );
+
+        let steps = [
+            Operation::FromHex,
+            Operation::FromBase64 {
+                alphabet: Base64Alphabet::Standard,
+            },
+            Operation::Gunzip,
+            Operation::JsonPretty,
+        ];
+        let results = run_pipeline(input.as_bytes(), &steps);
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[3].as_ref().unwrap(),
+            b"{\n  \"a\": [\n    1,\n    2\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn stops_at_first_error() {
+        let steps = [Operation::FromHex, Operation::ToHex];
+        let results = run_pipeline(b"xyz", &steps);
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], Err(PipelineError::Hex(_))));
+    }
+
+    #[test]
+    fn gunzip_limit() {
+        let zeros = vec![0; MAX_GUNZIP_BYTES as usize];
+        let gzipped = Operation::Gzip.apply(&zeros).unwrap();
+        assert_eq!(
+            Operation::Gunzip.apply(&gzipped).unwrap().len(),
+            zeros.len()
+        );
+
+        let mut bomb = gzipped.clone();
+        bomb.extend(Operation::Gzip.apply(b"!").unwrap());
+        assert!(matches!(
+            Operation::Gunzip.apply(&bomb),
+            Err(PipelineError::OutputTooLarge(MAX_GUNZIP_BYTES))
+        ));
+    }
+
+    #[test]
+    fn percent_encoding() {
+        let encoded = Operation::UrlEncode.apply("a b/ü".as_bytes()).unwrap();
+        assert_eq!(encoded, b"a%20b%2F%C3%BC");
+        assert_eq!(
+            Operation::UrlDecode.apply(&encoded).unwrap(),
+            "a b/ü".as_bytes()
+        );
+        assert_eq!(Operation::UrlDecode.apply(b"a+b").unwrap(), b"a b");
+        assert!(Operation::UrlDecode.apply(b"%4").is_err());
+    }
+
+    #[test]
+    fn recipe_json() {
+        let recipe = Recipe {
+            name: "decode".to_string(),
+            steps: vec![
+                Operation::FromBase64 {
+                    alphabet: Base64Alphabet::UrlSafe,
+                },
+                Operation::Hash {
+                    algorithm: HashAlgorithm::Md5,
+                },
+            ],
+        };
+        let json = serde_json::to_string(&recipe).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"decode","steps":[{"op":"FromBase64","alphabet":"UrlSafe"},{"op":"Hash","algorithm":"Md5"}]}"#
+        );
+        assert_eq!(serde_json::from_str::<Recipe>(&json).unwrap(), recipe);
+    }
+}
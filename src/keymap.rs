@@ -0,0 +1,294 @@
+use crate::actions::Action;
+
+/// Key names accepted after the modifiers besides single characters and `F1`..`F24`.
+const NAMED_KEYS: &[&str] = &[
+    "Tab",
+    "Enter",
+    "Escape",
+    "Space",
+    "Backspace",
+    "Delete",
+    "Insert",
+    "Home",
+    "End",
+    "PageUp",
+    "PageDown",
+    "ArrowUp",
+    "ArrowDown",
+    "ArrowLeft",
+    "ArrowRight",
+];
+
+// From: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct KeyboardEvent {
+    altKey: bool,
+    /// Returns a string with the code value of the physical key represented by the event.
+    code: String,
+    /// Returns a boolean value that is true if the Ctrl key was active when the key event was generated.
+    ctrlKey: bool,
+    isComposing: bool,
+    /// Returns a string representing the key value of the key represented by the event.
+    key: String,
+    location: u32,
+    /// Returns a boolean value that is true if the Meta key (on Mac keyboards, the ⌘ Command key;
+    /// on Windows keyboards, the Windows key (⊞)) was active when the key event was generated.
+    metaKey: bool,
+    repeat: bool,
+    shiftKey: bool,
+}
+
+impl KeyboardEvent {
+    fn ctrl_or_command(&self) -> bool {
+        #[cfg(not(target_os = "macos"))]
+        {
+            self.ctrlKey
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.metaKey
+        }
+    }
+
+    /// The pressed keys in the form used by the keymap, e.g. `Ctrl+Shift+C`, or `None` for a
+    /// lone modifier. `shortcutKey()` of `main.js` does the same.
+    pub fn shortcut(&self) -> Option<String> {
+        if self.isComposing || ["Control", "Shift", "Alt", "Meta"].contains(&self.key.as_str()) {
+            return None;
+        }
+        let key = match self.key.as_str() {
+            " " => "Space".to_string(),
+            key if key.chars().count() == 1 => key.to_uppercase(),
+            key => key.to_string(),
+        };
+        Some(join_shortcut(
+            self.ctrl_or_command(),
+            self.altKey,
+            self.shiftKey,
+            &key,
+        ))
+    }
+}
+
+fn join_shortcut(ctrl: bool, alt: bool, shift: bool, key: &str) -> String {
+    let modifiers = [(ctrl, "Ctrl+"), (alt, "Alt+"), (shift, "Shift+")];
+    let mut shortcut = modifiers
+        .into_iter()
+        .filter_map(|(pressed, name)| pressed.then_some(name))
+        .collect::<String>();
+    shortcut.push_str(key);
+    shortcut
+}
+
+#[derive(Debug, Clone, PartialEq, derive_more::Display)]
+pub enum KeymapProblem {
+    #[display("invalid shortcut \"{_0}\"")]
+    InvalidShortcut(String),
+
+    #[display("{shortcut} is bound to both {first} and {second}")]
+    Conflict {
+        shortcut: String,
+        first: Action,
+        second: Action,
+    },
+}
+
+/// Put a shortcut typed by the user, e.g. `cmd+shift+c`, in the form of
+/// `KeyboardEvent::shortcut`. `Ctrl`, `Control`, `Cmd`, `Command` and `Mod` all mean Ctrl, or
+/// Command on macOS.
+pub fn normalize_shortcut(shortcut: &str) -> Result<String, KeymapProblem> {
+    let invalid = || KeymapProblem::InvalidShortcut(shortcut.to_string());
+    let parts = shortcut.split('+').map(str::trim).collect::<Vec<_>>();
+    let (key, modifiers) = parts.split_last().ok_or_else(invalid)?;
+
+    let (mut ctrl, mut alt, mut shift) = (false, false, false);
+    for modifier in modifiers {
+        let pressed = match modifier.to_lowercase().as_str() {
+            "ctrl" | "control" | "cmd" | "command" | "mod" => &mut ctrl,
+            "alt" | "option" => &mut alt,
+            "shift" => &mut shift,
+            _ => return Err(invalid()),
+        };
+        *pressed = true;
+    }
+
+    let key = if key.chars().count() == 1 {
+        key.to_uppercase()
+    } else if let Some(name) = NAMED_KEYS.iter().find(|v| v.eq_ignore_ascii_case(key)) {
+        name.to_string()
+    } else if let Some(n) = key.strip_prefix(['F', 'f'])
+        && let Ok(n) = n.parse::<u8>()
+        && (1..=24).contains(&n)
+    {
+        format!("F{n}")
+    } else {
+        return Err(invalid());
+    };
+    Ok(join_shortcut(ctrl, alt, shift, &key))
+}
+
+/// Shortcuts of one action replacing its default ones, none to unbind it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShortcutOverride {
+    pub action: Action,
+    pub keys: Vec<String>,
+}
+
+fn default_bindings() -> Vec<(String, Action)> {
+    let switch_to_tool = (1..=9).map(|n| (format!("Ctrl+{n}"), Action::SwitchToTool(n)));
+    let others = [
+        ("Ctrl+K", Action::OpenCommandPalette),
+        ("Ctrl+/", Action::ShowShortcuts),
+        ("Ctrl+Tab", Action::NextTool),
+        ("Ctrl+PageDown", Action::NextTool),
+        ("Ctrl+Shift+Tab", Action::PreviousTool),
+        ("Ctrl+PageUp", Action::PreviousTool),
+        ("Ctrl+L", Action::FocusInput),
+        ("Ctrl+Shift+C", Action::CopyOutput),
+        ("Ctrl+Shift+S", Action::SwapInputOutput),
+        ("Ctrl+Shift+X", Action::ClearInput),
+        ("Ctrl+Shift+L", Action::ToggleTheme),
+        ("Ctrl+=", Action::ZoomIn),
+        ("Ctrl+-", Action::ZoomOut),
+        ("Ctrl+0", Action::ResetZoom),
+    ]
+    .map(|(key, action)| (key.to_string(), action));
+    switch_to_tool.chain(others).collect()
+}
+
+/// Shortcut of every action: the defaults with the user overrides applied.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Keymap {
+    /// Normalized shortcuts and their actions, each shortcut once.
+    bindings: Vec<(String, Action)>,
+    /// Invalid and conflicting shortcuts, left out of `bindings`.
+    pub problems: Vec<KeymapProblem>,
+}
+
+impl Keymap {
+    /// On a conflict, user shortcuts win over the default ones and earlier ones over later ones.
+    pub fn new(overrides: &[ShortcutOverride]) -> Self {
+        let user = overrides
+            .iter()
+            .flat_map(|v| v.keys.iter().map(|key| (key.clone(), v.action.clone())));
+        let defaults = default_bindings()
+            .into_iter()
+            .filter(|(_, action)| !overrides.iter().any(|v| v.action == *action));
+
+        let mut keymap = Self::default();
+        for (shortcut, action) in user.chain(defaults) {
+            let shortcut = match normalize_shortcut(&shortcut) {
+                Ok(v) => v,
+                Err(e) => {
+                    keymap.problems.push(e);
+                    continue;
+                }
+            };
+            match keymap.action(&shortcut) {
+                None => keymap.bindings.push((shortcut, action)),
+                Some(first) if *first == action => {}
+                Some(first) => {
+                    let first = first.clone();
+                    keymap.problems.push(KeymapProblem::Conflict {
+                        shortcut,
+                        first,
+                        second: action,
+                    });
+                }
+            }
+        }
+        keymap
+    }
+
+    /// Action bound to a shortcut from `KeyboardEvent::shortcut`.
+    pub fn action(&self, shortcut: &str) -> Option<&Action> {
+        self.bindings
+            .iter()
+            .find(|(v, _)| v == shortcut)
+            .map(|(_, action)| action)
+    }
+
+    pub fn shortcuts(&self, action: &Action) -> Vec<&str> {
+        self.bindings
+            .iter()
+            .filter(|(_, v)| v == action)
+            .map(|(shortcut, _)| shortcut.as_str())
+            .collect()
+    }
+
+    /// Every bound shortcut, for `setShortcuts()` of `main.js`.
+    pub fn all_shortcuts(&self) -> Vec<&str> {
+        self.bindings.iter().map(|(v, _)| v.as_str()).collect()
+    }
+
+    /// Every action with a shortcut and its shortcuts, in the order of the defaults.
+    pub fn cheatsheet(&self) -> Vec<(Action, Vec<&str>)> {
+        let actions = default_bindings()
+            .into_iter()
+            .map(|(_, action)| action)
+            .chain(self.bindings.iter().map(|(_, action)| action.clone()));
+        let mut seen = vec![];
+        actions
+            .filter(|action| {
+                let new = !seen.contains(action);
+                seen.push(action.clone());
+                new
+            })
+            .map(|action| {
+                let shortcuts = self.shortcuts(&action);
+                (action, shortcuts)
+            })
+            .filter(|(_, shortcuts)| !shortcuts.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize() {
+        let normalize = |v| normalize_shortcut(v).ok();
+        assert_eq!(normalize("ctrl+k").as_deref(), Some("Ctrl+K"));
+        assert_eq!(
+            normalize("Shift + Cmd + c").as_deref(),
+            Some("Ctrl+Shift+C")
+        );
+        assert_eq!(normalize("Alt+pageup").as_deref(), Some("Alt+PageUp"));
+        assert_eq!(normalize("f5").as_deref(), Some("F5"));
+        assert_eq!(normalize("Ctrl+="), Some("Ctrl+=".to_string()));
+        assert_eq!(normalize("Hyper+K"), None);
+        assert_eq!(normalize("Ctrl+F25"), None);
+        assert_eq!(normalize("Ctrl+"), None);
+    }
+
+    #[test]
+    fn overrides_and_conflicts() {
+        let keymap = Keymap::new(&[
+            ShortcutOverride {
+                action: Action::ToggleTheme,
+                keys: vec!["ctrl+k".to_string(), "Ctrl+T".to_string()],
+            },
+            ShortcutOverride {
+                action: Action::ZoomIn,
+                keys: vec![],
+            },
+        ]);
+        assert_eq!(keymap.action("Ctrl+K"), Some(&Action::ToggleTheme));
+        assert_eq!(keymap.shortcuts(&Action::ToggleTheme), ["Ctrl+K", "Ctrl+T"]);
+        assert_eq!(keymap.action("Ctrl+="), None);
+        assert_eq!(
+            keymap.problems,
+            [KeymapProblem::Conflict {
+                shortcut: "Ctrl+K".to_string(),
+                first: Action::ToggleTheme,
+                second: Action::OpenCommandPalette,
+            }]
+        );
+        assert!(Keymap::new(&[]).problems.is_empty());
+    }
+}
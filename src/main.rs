@@ -1,115 +1,221 @@
-use std::{cell::Cell, rc::Rc};
-
+use actions::{Action, AppState, Overlay};
+use clap::Parser;
+use dioxus::desktop::tao::event::{Event as WryEvent, WindowEvent};
 use dioxus::{logger::tracing, prelude::*};
+use keymap::{KeyboardEvent, Keymap};
+use session::Session;
+use settings::{LoadErrors, Settings, Theme};
+use std::time::Duration;
 use strum::IntoEnumIterator;
 use views::{
-    tool_base64_decode::{ToolBase64DecodeComponent, ToolBase64DecodeComponentData},
-    tool_base64_encode::{ToolBase64EncodeComponent, ToolBase64EncodeComponentData},
-    tool_base64_hash::{ToolHashComponent, ToolHashComponentData},
+    command_palette::CommandPaletteComponent, settings::SettingsComponent,
+    shortcuts::ShortcutsComponent, tool_base64_decode::ToolBase64DecodeComponent,
+    tool_base64_encode::ToolBase64EncodeComponent, tool_base64_hash::ToolHashComponent,
+    tool_certificate::ToolCertificateComponent, tool_datetime::ToolDateTimeComponent,
+    tool_detect::ToolDetectComponent, tool_ip::ToolIpComponent, tool_jwt::ToolJwtComponent,
+    tool_password::ToolPasswordComponent, tool_pipeline::ToolPipelineComponent,
 };
 
+mod actions;
+mod cli;
+mod core;
+mod keymap;
+mod session;
+mod settings;
 mod utils;
 mod views;
 
 fn main() -> anyhow::Result<()> {
+    let cli = cli::Cli::parse();
+    if let Some(command) = cli.command {
+        return cli::run(command);
+    }
+
+    let (settings, settings_error) = Settings::load();
+    let (session, session_error) = Session::load(&settings);
+    let load_errors = settings_error
+        .into_iter()
+        .chain(session_error)
+        .inspect(|e| tracing::warn!("{e:#}"))
+        .map(|e| format!("{e:#}"))
+        .collect();
     dioxus::LaunchBuilder::desktop()
-        .with_cfg(make_config())
+        .with_cfg(make_config(&settings))
+        .with_context(settings)
+        .with_context(session)
+        .with_context(LoadErrors(load_errors))
         .launch(app);
 
     Ok(())
 }
 
+/// Quiet time after the last change of the session before it is written to disk.
+const SESSION_SAVE_DELAY: Duration = Duration::from_millis(500);
+
 fn app() -> Element {
-    let zoom_level = use_hook(|| Rc::new(Cell::new(1.0)));
-
-    use_future(move || {
-        clone!(zoom_level);
-        async move {
-            // From: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent
-            #[allow(non_snake_case)]
-            #[allow(dead_code)]
-            #[derive(Debug, Clone, Default, serde::Deserialize)]
-            struct KeyboardEvent {
-                altKey: bool,
-                /// Returns a string with the code value of the physical key represented by the event.
-                code: String,
-                /// Returns a boolean value that is true if the Ctrl key was active when the key event was generated.
-                ctrlKey: bool,
-                isComposing: bool,
-                /// Returns a string representing the key value of the key represented by the event.
-                key: String,
-                location: u32,
-                /// Returns a boolean value that is true if the Meta key (on Mac keyboards, the ⌘ Command key;
-                /// on Windows keyboards, the Windows key (⊞)) was active when the key event was generated.
-                metaKey: bool,
-                repeat: bool,
-                shiftKey: bool,
-            }
-            impl KeyboardEvent {
-                fn ctrl_or_command(&self) -> bool {
-                    #[cfg(not(target_os = "macos"))]
-                    {
-                        self.ctrlKey
-                    }
+    let settings = use_context_provider(|| Signal::new(consume_context::<Settings>()));
+    let mut session = use_context_provider(|| Signal::new(consume_context::<Session>()));
+    let mut overlay = use_signal(|| None);
+    let state = use_context_provider(|| AppState {
+        settings,
+        session,
+        overlay,
+    });
 
-                    #[cfg(target_os = "macos")]
-                    {
-                        self.metaKey
-                    }
-                }
+    use_effect(move || {
+        if let Err(e) = settings.read().save() {
+            tracing::error!("saving settings failed: {e:#}");
+        }
+    });
+    // Saved once typing pauses rather than on every keystroke, and when the window closes.
+    let mut pending_save = use_signal(|| None::<Task>);
+    use_effect(move || {
+        session.read();
+        if let Some(task) = pending_save.take() {
+            task.cancel();
+        }
+        pending_save.set(Some(spawn(async move {
+            tokio::time::sleep(SESSION_SAVE_DELAY).await;
+            let session = session.peek().clone();
+            let saved = tokio::task::spawn_blocking(move || session.save()).await;
+            if let Ok(Err(e)) = saved {
+                tracing::error!("saving session failed: {e:#}");
+            }
+        })));
+    });
+    dioxus::desktop::use_wry_event_handler(move |event, _| {
+        if let WryEvent::WindowEvent {
+            event: WindowEvent::CloseRequested,
+            ..
+        } = event
+        {
+            if let Some(task) = pending_save.take() {
+                task.cancel();
             }
+            if let Err(e) = session.peek().save() {
+                tracing::error!("saving session failed: {e:#}");
+            }
+        }
+    });
 
-            let mut eval =
-                document::eval("$(document).ready(function() { setupKeydownEvent(dioxus); });");
-            loop {
-                match eval.recv::<KeyboardEvent>().await {
-                    Ok(event) => {
-                        tracing::debug!("keypress {event:?}");
-                        if event.ctrl_or_command() && event.key == "=" {
-                            tracing::debug!("zoom in");
-                            zoom_level.replace(zoom_level.get() + 0.1);
-                            _ = dioxus::desktop::window().webview.zoom(zoom_level.get());
-                        } else if event.ctrl_or_command() && event.key == "-" {
-                            tracing::debug!("zoom out");
-                            zoom_level.replace(zoom_level.get() - 0.1);
-                            _ = dioxus::desktop::window().webview.zoom(zoom_level.get());
-                        } else if event.ctrl_or_command() && event.key == "0" {
-                            tracing::debug!("reset zoom");
-                            zoom_level.replace(1.0);
-                            _ = dioxus::desktop::window().webview.zoom(zoom_level.get());
-                        }
-                    }
-                    Err(error) => {
-                        tracing::error!("watching keydown event failed with {error}");
-                        break;
+    let always_on_top = use_memo(move || settings.read().always_on_top);
+    use_effect(move || {
+        dioxus::desktop::window().set_always_on_top(always_on_top());
+    });
+
+    let font = use_memo(move || {
+        let settings = settings.read();
+        (
+            settings.font_family.clone(),
+            settings.monospace_font_family.clone(),
+            settings.font_size,
+        )
+    });
+    use_effect(move || {
+        let (family, monospace_family, size) = font();
+        spawn(async move {
+            let js = format!(
+                "setFont({}, {}, {size})",
+                serde_json::Value::from(family),
+                serde_json::Value::from(monospace_family)
+            );
+            if let Err(e) = document::eval(&js).await {
+                tracing::error!("running javascript failed: {e}");
+            }
+        });
+    });
+
+    let zoom_level = use_memo(move || session.read().zoom_level);
+    use_effect(move || {
+        _ = dioxus::desktop::window().webview.zoom(zoom_level());
+    });
+
+    let keymap = use_memo(move || Keymap::new(&settings.read().shortcuts));
+    use_effect(move || {
+        let shortcuts = serde_json::to_string(&keymap.read().all_shortcuts());
+        spawn(async move {
+            let js = format!("setShortcuts({})", shortcuts.unwrap_or_default());
+            if let Err(e) = document::eval(&js).await {
+                tracing::error!("running javascript failed: {e}");
+            }
+        });
+    });
+
+    use_future(move || async move {
+        let mut eval =
+            document::eval("$(document).ready(function() { setupKeydownEvent(dioxus); });");
+        loop {
+            match eval.recv::<KeyboardEvent>().await {
+                Ok(event) => {
+                    tracing::debug!("keypress {event:?}");
+                    let action = event
+                        .shortcut()
+                        .and_then(|v| keymap.peek().action(&v).cloned());
+                    if let Some(action) = action {
+                        action.run(state);
                     }
                 }
+                Err(error) => {
+                    tracing::error!("watching keydown event failed with {error}");
+                    break;
+                }
             }
         }
     });
 
     rsx! {
         Home {}
+        match overlay() {
+            Some(Overlay::CommandPalette) => rsx! {
+                CommandPaletteComponent {
+                    recent: session.read().recent_actions.clone(),
+                    keymap: keymap(),
+                    onrun: move |action: Action| {
+                        overlay.set(None);
+                        session.write().record_action(action.clone());
+                        action.run(state);
+                    },
+                    onclose: move |_| overlay.set(None),
+                }
+            },
+            Some(Overlay::Shortcuts) => rsx! {
+                ShortcutsComponent {
+                    keymap: keymap(),
+                    onclose: move |_| overlay.set(None),
+                }
+            },
+            None => rsx! {},
+        }
     }
 }
 
-fn make_config() -> dioxus::desktop::Config {
+fn make_config(settings: &Settings) -> dioxus::desktop::Config {
     dioxus::desktop::Config::default()
-        .with_window(make_window())
+        .with_window(make_window(settings))
         // .with_close_behaviour(dioxus::desktop::WindowCloseBehaviour::LastWindowExitsApp)
         .with_custom_index(
             include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/index.html")).into(),
         )
 }
 
-fn make_window() -> dioxus::desktop::WindowBuilder {
+fn make_window(settings: &Settings) -> dioxus::desktop::WindowBuilder {
     dioxus::desktop::WindowBuilder::new()
-        .with_always_on_top(false)
+        .with_always_on_top(settings.always_on_top)
         .with_title("Utils")
 }
 
-#[derive(Debug, Clone, PartialEq, strum_macros::Display, strum_macros::EnumIter)]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+)]
 enum Tools {
+    Detect,
+
     #[strum(to_string = "Base64 Encode")]
     Base64Encode,
 
@@ -117,41 +223,98 @@ enum Tools {
     Base64Decode,
 
     Hash,
+
+    #[strum(to_string = "Password Hash")]
+    PasswordHash,
+
     Jwt,
     Certificate,
     DateTime,
     IP,
+    Pipeline,
+    Settings,
 }
 
 #[component]
 pub fn Home() -> Element {
-    let mut selected_tool = use_signal(|| Tools::Base64Encode);
-
-    let mut data_tool_base64_encode = use_signal(ToolBase64EncodeComponentData::default);
-    let mut data_tool_base64_decode = use_signal(ToolBase64DecodeComponentData::default);
-    let mut data_tool_hash = use_signal(ToolHashComponentData::default);
+    let mut settings = use_context::<Signal<Settings>>();
+    let mut session = use_context::<Signal<Session>>();
+    let selected_tool = session.read().selected_tool.clone();
 
-    let selected_tool_ui = match selected_tool() {
+    let selected_tool_ui = match selected_tool {
+        Tools::Detect => rsx! {
+            ToolDetectComponent {
+                data: session.read().detect.clone(),
+                onupdate: move |v| session.write().detect = v,
+                onopen: move |(input_type, input)| session.write().open_detected(input_type, input),
+            }
+        },
         Tools::Base64Encode => rsx! {
             ToolBase64EncodeComponent {
-                data: data_tool_base64_encode(),
-                onupdate: move |v| data_tool_base64_encode.set(v),
+                data: session.read().base64_encode.clone(),
+                onupdate: move |v| session.write().base64_encode = v,
             }
         },
         Tools::Base64Decode => rsx! {
             ToolBase64DecodeComponent {
-                data: data_tool_base64_decode(),
-                onupdate: move |v| data_tool_base64_decode.set(v),
+                data: session.read().base64_decode.clone(),
+                onupdate: move |v| session.write().base64_decode = v,
             }
         },
         Tools::Hash => rsx! {
             ToolHashComponent {
-                data: data_tool_hash(),
-                onupdate: move |v| data_tool_hash.set(v),
+                data: session.read().hash.clone(),
+                onupdate: move |v| session.write().hash = v,
+                algorithms: settings.read().hash_algorithms.clone(),
+                onalgorithmsupdate: move |v| settings.write().hash_algorithms = v,
             }
         },
-        _ => rsx! {
-            "TODO"
+        Tools::PasswordHash => rsx! {
+            ToolPasswordComponent {
+                data: session.read().password.clone(),
+                onupdate: move |v| session.write().password = v,
+            }
+        },
+        Tools::Jwt => rsx! {
+            ToolJwtComponent {
+                data: session.read().jwt.clone(),
+                onupdate: move |v| session.write().jwt = v,
+            }
+        },
+        Tools::Certificate => rsx! {
+            ToolCertificateComponent {
+                data: session.read().certificate.clone(),
+                onupdate: move |v| session.write().certificate = v,
+            }
+        },
+        Tools::DateTime => rsx! {
+            ToolDateTimeComponent {
+                data: session.read().datetime.clone(),
+                onupdate: move |v| session.write().datetime = v,
+            }
+        },
+        Tools::IP => rsx! {
+            ToolIpComponent {
+                data: session.read().ip.clone(),
+                onupdate: move |v| session.write().ip = v,
+            }
+        },
+        Tools::Pipeline => rsx! {
+            ToolPipelineComponent {
+                data: session.read().pipeline.clone(),
+                onupdate: move |v| session.write().pipeline = v,
+                recipes: settings.read().recipes.clone(),
+                onrecipesupdate: move |v| settings.write().recipes = v,
+            }
+        },
+        Tools::Settings => rsx! {
+            SettingsComponent {
+                data: settings(),
+                onupdate: move |v: Settings| {
+                    session.write().apply_settings(&settings.peek(), &v);
+                    settings.set(v);
+                },
+            }
         },
     };
 
@@ -175,8 +338,9 @@ pub fn Home() -> Element {
                     style: "flex: 3; max-width: 250px;",
 
                     ToolSelectorComponent {
-                        selected: selected_tool(),
-                        onchange: move |selected| selected_tool.set(selected),
+                        selected: selected_tool,
+                        hidden: settings.read().hidden_tools.clone(),
+                        onchange: move |selected| session.write().selected_tool = selected,
                     }
                 }
 
@@ -197,7 +361,10 @@ pub fn Home() -> Element {
 
 #[component]
 fn StatusComponent() -> Element {
-    let mut theme_light_mode = use_signal(|| false);
+    let state = use_context::<AppState>();
+    let settings = state.settings;
+    let mut session = state.session;
+    let theme_light_mode = use_memo(move || settings.read().theme == Theme::Light);
     use_effect(move || {
         let mode = theme_light_mode();
         spawn(async move {
@@ -208,15 +375,35 @@ fn StatusComponent() -> Element {
         });
     });
 
+    let selected_tool = session.read().selected_tool.clone();
+    let saved = session.read().is_saved(&selected_tool);
+
     rsx! {
-        div { class: "form-check form-switch ms-auto",
+        div { class: "ms-auto" }
+        if selected_tool != Tools::Settings {
+            div { class: "form-check form-switch me-3",
+                title: "Remember the {selected_tool} inputs after a restart",
+                input {
+                    class: "form-check-input",
+                    id: "SaveInputsSelector",
+                    r#type: "checkbox",
+                    role: "switch",
+                    checked: saved,
+                    onclick: move |_| session.write().set_saved(selected_tool.clone(), !saved),
+                }
+                label { class: "form-check-label", r#for: "SaveInputsSelector",
+                    "💾"
+                }
+            }
+        }
+        div { class: "form-check form-switch",
             input {
                 class: "form-check-input",
                 id: "ThemeSelector",
                 r#type: "checkbox",
                 role: "switch",
                 checked: !theme_light_mode(),
-                onclick: move |_| theme_light_mode.toggle(),
+                onclick: move |_| Action::ToggleTheme.run(state),
             }
             label { class: "form-check-label", r#for: "ThemeSelector",
                 "🌒"
@@ -226,8 +413,12 @@ fn StatusComponent() -> Element {
 }
 
 #[component]
-fn ToolSelectorComponent(selected: Tools, onchange: EventHandler<Tools>) -> Element {
-    let tools = Tools::iter().map(|tool| rsx! {
+fn ToolSelectorComponent(
+    selected: Tools,
+    hidden: Vec<Tools>,
+    onchange: EventHandler<Tools>,
+) -> Element {
+    let tools = Tools::iter().filter(|v| !hidden.contains(v)).map(|tool| rsx! {
         button { class: format!("btn btn-block {}", if selected == tool { "btn-primary" } else { "" }),
             onclick: move |_| {
                 clone!(tool);
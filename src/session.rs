@@ -0,0 +1,218 @@
+use std::path::PathBuf;
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    Tools,
+    actions::Action,
+    core::{InputKind, detect::InputType},
+    settings::Settings,
+    utils::{load_json, save_json},
+    views::{
+        tool_base64_decode::ToolBase64DecodeComponentData,
+        tool_base64_encode::ToolBase64EncodeComponentData,
+        tool_base64_hash::ToolHashComponentData,
+        tool_certificate::ToolCertificateComponentData,
+        tool_datetime::{DateTimeMode, ToolDateTimeComponentData},
+        tool_detect::ToolDetectComponentData,
+        tool_ip::{IpMode, ToolIpComponentData},
+        tool_jwt::{JwtMode, ToolJwtComponentData},
+        tool_password::ToolPasswordComponentData,
+        tool_pipeline::ToolPipelineComponentData,
+    },
+};
+
+/// Tool that handles a detected input type, if any.
+pub fn detected_tool(input_type: InputType) -> Option<Tools> {
+    match input_type {
+        InputType::Jwt => Some(Tools::Jwt),
+        InputType::PemCertificate => Some(Tools::Certificate),
+        InputType::Base64 | InputType::Base64Url => Some(Tools::Base64Decode),
+        // Bytes given as hex, shown there as Base64.
+        InputType::Hex => Some(Tools::Base64Encode),
+        InputType::UnixTimestamp | InputType::DateTime => Some(Tools::DateTime),
+        InputType::Ipv4 | InputType::Ipv6 | InputType::Cidr => Some(Tools::IP),
+        InputType::Uuid | InputType::Url | InputType::Json => None,
+    }
+}
+
+const MAX_RECENT_ACTIONS: usize = 8;
+
+/// Everything restored when the app starts again: the selected tool, every tool's inputs and
+/// options and the zoom level.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub selected_tool: Tools,
+    pub zoom_level: f64,
+    /// Tools whose inputs are never written to disk, e.g. because they hold secrets.
+    pub unsaved_tools: Vec<Tools>,
+    /// Command palette actions, most recently used first.
+    pub recent_actions: Vec<Action>,
+
+    pub detect: ToolDetectComponentData,
+    pub base64_encode: ToolBase64EncodeComponentData,
+    pub base64_decode: ToolBase64DecodeComponentData,
+    pub hash: ToolHashComponentData,
+    pub password: ToolPasswordComponentData,
+    pub jwt: ToolJwtComponentData,
+    pub certificate: ToolCertificateComponentData,
+    pub datetime: ToolDateTimeComponentData,
+    pub ip: ToolIpComponentData,
+    pub pipeline: ToolPipelineComponentData,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            selected_tool: Tools::Base64Encode,
+            zoom_level: 1.0,
+            // JWT keys are HMAC secrets and private keys, passwords are passwords.
+            unsaved_tools: vec![Tools::PasswordHash, Tools::Jwt],
+            recent_actions: vec![],
+            detect: Default::default(),
+            base64_encode: Default::default(),
+            base64_decode: Default::default(),
+            hash: Default::default(),
+            password: Default::default(),
+            jwt: Default::default(),
+            certificate: Default::default(),
+            datetime: Default::default(),
+            ip: Default::default(),
+            pipeline: Default::default(),
+        }
+    }
+}
+
+impl Session {
+    /// `$XDG_DATA_HOME/utils/session.json` on Linux, the platform equivalent elsewhere.
+    fn path() -> Option<PathBuf> {
+        Some(dirs::data_dir()?.join("utils").join("session.json"))
+    }
+
+    /// A session with every tool in its initial state.
+    fn new(settings: &Settings) -> Self {
+        let mut session = Self {
+            zoom_level: settings.default_zoom,
+            ..Default::default()
+        };
+        for tool in Tools::iter() {
+            session.reset_tool(tool, settings);
+        }
+        session
+    }
+
+    /// Restore the last saved session, or start a fresh one if there is none or it is invalid,
+    /// together with the error in that case.
+    pub fn load(settings: &Settings) -> (Self, Option<anyhow::Error>) {
+        let (session, error) = match load_json::<Self>(Self::path()) {
+            Ok(session) => (session, None),
+            Err(e) => (None, Some(e)),
+        };
+        let mut session = session.unwrap_or_else(|| Self::new(settings));
+        for tool in session.unsaved_tools.clone() {
+            session.reset_tool(tool, settings);
+        }
+        (session, error)
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        save_json(Self::path(), &self.without_unsaved_tools())
+    }
+
+    pub fn is_saved(&self, tool: &Tools) -> bool {
+        !self.unsaved_tools.contains(tool)
+    }
+
+    pub fn set_saved(&mut self, tool: Tools, saved: bool) {
+        self.unsaved_tools.retain(|v| *v != tool);
+        if !saved {
+            self.unsaved_tools.push(tool);
+        }
+    }
+
+    /// Move `action` to the front of the recently used ones.
+    pub fn record_action(&mut self, action: Action) {
+        self.recent_actions.retain(|v| *v != action);
+        self.recent_actions.insert(0, action);
+        self.recent_actions.truncate(MAX_RECENT_ACTIONS);
+    }
+
+    /// Put `tool` back in its initial state.
+    fn reset_tool(&mut self, tool: Tools, settings: &Settings) {
+        match tool {
+            Tools::Detect => self.detect = Default::default(),
+            Tools::Base64Encode => {
+                self.base64_encode = ToolBase64EncodeComponentData::from_settings(settings)
+            }
+            Tools::Base64Decode => {
+                self.base64_decode = ToolBase64DecodeComponentData::from_settings(settings)
+            }
+            Tools::Hash => self.hash = ToolHashComponentData::from_settings(settings),
+            Tools::PasswordHash => self.password = Default::default(),
+            Tools::Jwt => self.jwt = Default::default(),
+            Tools::Certificate => self.certificate = Default::default(),
+            Tools::DateTime => self.datetime = Default::default(),
+            Tools::IP => self.ip = Default::default(),
+            Tools::Pipeline => self.pipeline = Default::default(),
+            Tools::Settings => {}
+        }
+    }
+
+    /// Open the tool of a detected input type with `input` in it.
+    pub fn open_detected(&mut self, input_type: InputType, input: String) {
+        let Some(tool) = detected_tool(input_type) else {
+            return;
+        };
+        match tool {
+            Tools::Base64Encode => self.base64_encode.set_input(input, InputKind::Hex),
+            Tools::Base64Decode => {
+                self.base64_decode.set_auto(true);
+                self.base64_decode.set_input(input);
+            }
+            Tools::Jwt => {
+                self.jwt.set_mode(JwtMode::Decode);
+                self.jwt.set_input(input);
+            }
+            Tools::Certificate => self.certificate.set_input(input),
+            Tools::DateTime => {
+                self.datetime.set_mode(DateTimeMode::Convert);
+                self.datetime.set_input(input);
+            }
+            Tools::IP => {
+                self.ip.set_mode(IpMode::Address);
+                self.ip.set_input(input);
+            }
+            Tools::Detect
+            | Tools::Hash
+            | Tools::PasswordHash
+            | Tools::Pipeline
+            | Tools::Settings => {}
+        }
+        self.selected_tool = tool;
+    }
+
+    /// Follow a change of the settings: tools the user hasn't touched pick up the new default
+    /// kinds, and the zoom level follows the default zoom.
+    pub fn apply_settings(&mut self, old: &Settings, new: &Settings) {
+        for tool in Tools::iter() {
+            let mut untouched = self.clone();
+            untouched.reset_tool(tool.clone(), old);
+            if untouched == *self {
+                self.reset_tool(tool, new);
+            }
+        }
+        if old.default_zoom != new.default_zoom {
+            self.zoom_level = new.default_zoom;
+        }
+    }
+
+    /// Copy of the session with the inputs of opted-out tools reset.
+    fn without_unsaved_tools(&self) -> Self {
+        let mut session = self.clone();
+        for tool in &self.unsaved_tools {
+            session.reset_tool(tool.clone(), &Settings::default());
+        }
+        session
+    }
+}
@@ -0,0 +1,130 @@
+use std::path::PathBuf;
+
+use crate::{
+    Tools,
+    core::{
+        InputKind, base64::Base64Alphabet, base64::DecodedFormat, hash::HashAlgorithm,
+        pipeline::Recipe,
+    },
+    keymap::ShortcutOverride,
+    utils::{load_json, save_json},
+};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+pub enum Theme {
+    Dark,
+    Light,
+}
+
+/// Kinds a tool starts with, before the user picks another one.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ToolDefaults {
+    pub base64_encode_input: InputKind,
+    pub base64_encode_alphabet: Base64Alphabet,
+    pub base64_decode_output: DecodedFormat,
+    pub hash_input: InputKind,
+}
+
+impl Default for ToolDefaults {
+    fn default() -> Self {
+        Self {
+            base64_encode_input: InputKind::Utf8,
+            base64_encode_alphabet: Base64Alphabet::Standard,
+            base64_decode_output: DecodedFormat::Utf8,
+            hash_input: InputKind::Utf8,
+        }
+    }
+}
+
+/// User preferences, edited in the Settings view and applied as soon as they change.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub theme: Theme,
+    /// Zoom level of a new session and of `Ctrl+0`.
+    pub default_zoom: f64,
+    /// Zoom change of `Ctrl+=` and `Ctrl+-`.
+    pub zoom_step: f64,
+    /// CSS font family of the UI, the Bootstrap one if empty.
+    pub font_family: String,
+    /// CSS font family of inputs and outputs, the Bootstrap one if empty.
+    pub monospace_font_family: String,
+    /// Base font size in pixels, the Bootstrap one if 0.
+    pub font_size: u32,
+    pub always_on_top: bool,
+    /// Tools left out of the sidebar.
+    pub hidden_tools: Vec<Tools>,
+    pub defaults: ToolDefaults,
+    /// Keyboard shortcuts replacing the default ones of their actions.
+    pub shortcuts: Vec<ShortcutOverride>,
+    /// Pipeline recipes saved by name.
+    pub recipes: Vec<Recipe>,
+    /// Rows shown by the Hash tool.
+    pub hash_algorithms: Vec<HashAlgorithm>,
+}
+
+/// Why the settings or the session couldn't be loaded at startup, shown in the Settings view.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadErrors(pub Vec<String>);
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::Dark,
+            default_zoom: 1.0,
+            zoom_step: 0.1,
+            font_family: Default::default(),
+            monospace_font_family: Default::default(),
+            font_size: 0,
+            always_on_top: false,
+            hidden_tools: vec![],
+            defaults: Default::default(),
+            shortcuts: vec![],
+            recipes: vec![],
+            hash_algorithms: HashAlgorithm::defaults(),
+        }
+    }
+}
+
+impl Settings {
+    /// `$XDG_CONFIG_HOME/utils/settings.json` on Linux, the platform equivalent elsewhere.
+    fn path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("utils").join("settings.json"))
+    }
+
+    /// Read the settings file, the defaults if there is none or if it is invalid, together
+    /// with the error in that case.
+    pub fn load() -> (Self, Option<anyhow::Error>) {
+        match load_json(Self::path()) {
+            Ok(settings) => (settings.unwrap_or_default(), None),
+            Err(e) => (Self::default(), Some(e)),
+        }
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        save_json(Self::path(), self)
+    }
+
+    pub fn is_hidden(&self, tool: &Tools) -> bool {
+        self.hidden_tools.contains(tool)
+    }
+
+    pub fn set_hidden(&mut self, tool: Tools, hidden: bool) {
+        self.hidden_tools.retain(|v| *v != tool);
+        if hidden {
+            self.hidden_tools.push(tool);
+        }
+    }
+}
@@ -1,2 +0,0 @@
-pub mod base64;
-pub mod hash;
@@ -75,3 +75,75 @@ macro_rules! idgen {
         )+
     };
 }
+
+/// Read a JSON file, `None` when there is none.
+///
+/// A file that can't be read or parsed is moved to `<name>.bak` before the error is returned, so
+/// that saving the defaults in its place doesn't lose what the user wrote in it.
+pub fn load_json<T: serde::de::DeserializeOwned>(
+    path: Option<std::path::PathBuf>,
+) -> anyhow::Result<Option<T>> {
+    let Some(path) = path.filter(|v| v.exists()) else {
+        return Ok(None);
+    };
+    let value = std::fs::read_to_string(&path)
+        .map_err(anyhow::Error::from)
+        .and_then(|text| Ok(serde_json::from_str(&text)?));
+    let error = match value {
+        Ok(value) => return Ok(Some(value)),
+        Err(e) => e,
+    };
+    let backup = path.with_extension("json.bak");
+    match std::fs::rename(&path, &backup) {
+        Ok(()) => anyhow::bail!(
+            "{} is invalid and was moved to {}: {error:#}",
+            path.display(),
+            backup.display()
+        ),
+        Err(e) => anyhow::bail!(
+            "{} is invalid: {error:#}, moving it to {} failed: {e}",
+            path.display(),
+            backup.display()
+        ),
+    }
+}
+
+/// Write `value` as pretty JSON, creating the parent directories as needed.
+pub fn save_json<T: serde::Serialize>(
+    path: Option<std::path::PathBuf>,
+    value: &T,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let path = path.context("no config or data directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("creating {}", parent.display()))?;
+    }
+    let text = serde_json::to_string_pretty(value)?;
+    std::fs::write(&path, text).with_context(|| format!("writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_json_is_kept_aside() {
+        let dir = std::env::temp_dir().join(format!("utils-load-json-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.json");
+        let load = || load_json::<Vec<u32>>(Some(path.clone()));
+
+        assert!(load().unwrap().is_none());
+        save_json(Some(path.clone()), &vec![1, 2]).unwrap();
+        assert_eq!(load().unwrap(), Some(vec![1, 2]));
+
+        std::fs::write(&path, "[1, 2,]").unwrap();
+        let error = load().unwrap_err().to_string();
+        let backup = std::fs::read_to_string(dir.join("settings.json.bak"));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(error.contains("is invalid and was moved to"), "{error}");
+        assert_eq!(backup.unwrap(), "[1, 2,]");
+    }
+}
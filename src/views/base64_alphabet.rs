@@ -0,0 +1,79 @@
+use dioxus::prelude::*;
+
+use crate::{
+    clone,
+    core::base64::{Base64Alphabet, CustomAlphabet},
+    views::enum_select::enum_select,
+};
+
+/// Dropdown of the builtin alphabets, with a switch to type an alphabet and a pad character
+/// instead, calling `onchange` with the alphabet, the switch and the custom alphabet.
+pub fn alphabet_picker(
+    id: &str,
+    alphabet: Base64Alphabet,
+    custom: bool,
+    custom_alphabet: CustomAlphabet,
+    onchange: impl Fn(Base64Alphabet, bool, CustomAlphabet) + Clone + 'static,
+) -> Element {
+    let id = format!("{id}-custom-alphabet");
+    rsx! {
+        div { class: "d-flex gap-2 align-items-center",
+            if custom {
+                input {
+                    class: "font-monospace form-control form-control-sm",
+                    r#type: "text",
+                    size: "64",
+                    placeholder: "Alphabet",
+                    title: "Alphabet",
+                    value: custom_alphabet.symbols.clone(),
+                    oninput: {
+                        clone!(onchange, custom_alphabet);
+                        move |v: Event<FormData>| {
+                            clone!(custom_alphabet);
+                            onchange(alphabet, custom, CustomAlphabet {
+                                symbols: v.value(),
+                                ..custom_alphabet
+                            });
+                        }
+                    },
+                }
+                input {
+                    class: "font-monospace form-control form-control-sm",
+                    r#type: "text",
+                    size: "3",
+                    placeholder: "Pad",
+                    title: "Pad, empty for none",
+                    value: custom_alphabet.pad.clone(),
+                    oninput: {
+                        clone!(onchange, custom_alphabet);
+                        move |v: Event<FormData>| {
+                            clone!(custom_alphabet);
+                            onchange(alphabet, custom, CustomAlphabet {
+                                pad: v.value(),
+                                ..custom_alphabet
+                            });
+                        }
+                    },
+                }
+            } else {
+                {
+                    enum_select(alphabet, {
+                        clone!(onchange, custom_alphabet);
+                        move |v| onchange(v, custom, custom_alphabet.clone())
+                    })
+                }
+            }
+            div { class: "form-check form-switch mb-0 text-nowrap",
+                input {
+                    class: "form-check-input",
+                    id: id.clone(),
+                    r#type: "checkbox",
+                    role: "switch",
+                    checked: custom,
+                    onchange: move |_| onchange(alphabet, !custom, custom_alphabet.clone()),
+                }
+                label { class: "form-check-label", r#for: id, "Custom" }
+            }
+        }
+    }
+}
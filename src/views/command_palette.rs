@@ -0,0 +1,90 @@
+use dioxus::prelude::*;
+
+use super::modal::ModalComponent;
+use crate::{
+    actions::{Action, rank_actions},
+    keymap::Keymap,
+};
+
+/// Overlay listing the actions matching what is typed, opened with `Ctrl+K`.
+#[component]
+pub fn CommandPaletteComponent(
+    recent: Vec<Action>,
+    keymap: Keymap,
+    onrun: EventHandler<Action>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut query = use_signal(String::new);
+    let mut selected = use_signal(|| 0_usize);
+
+    let actions = rank_actions(&query(), &recent);
+    let selected_action = actions
+        .get(selected().min(actions.len().saturating_sub(1)))
+        .cloned();
+    let count = actions.len();
+
+    let items = actions.into_iter().map(|action| {
+        let active = Some(&action) == selected_action.as_ref();
+        let kind = if recent.contains(&action) {
+            "Recent"
+        } else if action.is_tool() {
+            "Tool"
+        } else {
+            "Action"
+        };
+        let name = action.to_string();
+        let shortcuts = keymap.shortcuts(&action).join(", ");
+        rsx! {
+            button {
+                class: format!("list-group-item list-group-item-action d-flex {}", if active { "active" } else { "" }),
+                onclick: move |_| onrun.call(action.clone()),
+                "{name}"
+                span { class: "ms-auto me-2 small opacity-75 align-self-center", "{shortcuts}" }
+                span { class: "badge text-bg-secondary align-self-center", "{kind}" }
+            }
+        }
+    });
+
+    rsx! {
+        ModalComponent { onclose,
+            input {
+                class: "form-control form-control-lg",
+                r#type: "text",
+                placeholder: "Search tools and actions",
+                value: query(),
+                onmounted: move |e| async move {
+                    _ = e.set_focus(true).await;
+                },
+                oninput: move |e| {
+                    query.set(e.value());
+                    selected.set(0);
+                },
+                onkeydown: move |e| match e.key() {
+                    Key::ArrowDown => {
+                        e.prevent_default();
+                        selected.set((selected() + 1).min(count.saturating_sub(1)));
+                    }
+                    Key::ArrowUp => {
+                        e.prevent_default();
+                        selected.set(selected().saturating_sub(1));
+                    }
+                    Key::Enter => {
+                        if let Some(action) = selected_action.clone() {
+                            onrun.call(action);
+                        }
+                    }
+                    Key::Escape => onclose.call(()),
+                    _ => {}
+                },
+            }
+            div {
+                class: "list-group list-group-flush overflow-auto",
+                style: "max-height: 60vh;",
+                if count == 0 {
+                    div { class: "list-group-item text-body-secondary", "No matches" }
+                }
+                { items }
+            }
+        }
+    }
+}
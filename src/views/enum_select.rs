@@ -0,0 +1,27 @@
+use std::fmt::Display;
+
+use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+
+/// Dropdown picking one variant of `T`, calling `onchange` with the picked one.
+pub fn enum_select<T>(current: T, onchange: impl Fn(T) + 'static) -> Element
+where
+    T: IntoEnumIterator + PartialEq + Display + 'static,
+{
+    let options = T::iter().enumerate().map(|(i, v)| {
+        rsx! {
+            option { value: "{i}", selected: v == current, "{v}" }
+        }
+    });
+    rsx! {
+        select {
+            class: "form-select form-select-sm w-auto",
+            onchange: move |v: Event<FormData>| {
+                if let Some(v) = v.value().parse().ok().and_then(|i| T::iter().nth(i)) {
+                    onchange(v);
+                }
+            },
+            { options }
+        }
+    }
+}
@@ -1,3 +1,16 @@
+pub mod base64_alphabet;
+pub mod command_palette;
+pub mod enum_select;
+pub mod modal;
+pub mod settings;
+pub mod shortcuts;
 pub mod tool_base64_decode;
 pub mod tool_base64_encode;
 pub mod tool_base64_hash;
+pub mod tool_certificate;
+pub mod tool_datetime;
+pub mod tool_detect;
+pub mod tool_ip;
+pub mod tool_jwt;
+pub mod tool_password;
+pub mod tool_pipeline;
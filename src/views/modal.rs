@@ -0,0 +1,20 @@
+use dioxus::prelude::*;
+
+/// Card over the dimmed window, closed by clicking outside of it.
+#[component]
+pub fn ModalComponent(onclose: EventHandler<()>, children: Element) -> Element {
+    rsx! {
+        div {
+            class: "position-fixed top-0 start-0 w-100 h-100 d-flex justify-content-center align-items-start",
+            style: "z-index: 1050; background-color: rgba(0, 0, 0, 0.5);",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "card shadow mt-5",
+                style: "width: 500px; max-width: 90vw;",
+                onclick: move |e| e.stop_propagation(),
+                { children }
+            }
+        }
+    }
+}
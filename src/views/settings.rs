@@ -0,0 +1,237 @@
+use std::fmt::Display;
+
+use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+
+use crate::{
+    Tools, clone,
+    core::{InputKind, base64::Base64Alphabet, base64::DecodedFormat},
+    settings::{LoadErrors, Settings, Theme, ToolDefaults},
+    views::enum_select::enum_select,
+};
+
+/// Radio button group with one button per variant of `T`.
+fn radio_group<T>(name: &str, current: T, onchange: impl Fn(T) + Clone + 'static) -> Element
+where
+    T: IntoEnumIterator + Copy + PartialEq + Display + 'static,
+{
+    let buttons = T::iter().enumerate().map(|(i, v)| {
+        let id = format!("SettingsComponent-btn-radio-{name}-{i}");
+        let onchange = onchange.clone();
+        rsx! {
+            input {
+                checked: current == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| onchange(v),
+            }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
+        }
+    });
+
+    rsx! {
+        div {
+            class: "btn-group",
+            role: "group",
+            { buttons }
+        }
+    }
+}
+
+/// Label and control on one line.
+#[component]
+fn SettingsRow(label: String, children: Element) -> Element {
+    rsx! {
+        div { class: "row mb-2 align-items-center",
+            label { class: "col-3 col-form-label", "{label}" }
+            div { class: "col-9", { children } }
+        }
+    }
+}
+
+#[component]
+pub fn SettingsComponent(data: Settings, onupdate: EventHandler<Settings>) -> Element {
+    let update = {
+        clone!(data);
+        move |f: &dyn Fn(&mut Settings)| {
+            let mut settings = data.clone();
+            f(&mut settings);
+            onupdate.call(settings);
+        }
+    };
+    let update_defaults = {
+        clone!(update);
+        move |f: &dyn Fn(&mut ToolDefaults)| update(&|v| f(&mut v.defaults))
+    };
+
+    let theme = radio_group("theme", data.theme, {
+        clone!(update);
+        move |theme: Theme| update(&|v| v.theme = theme)
+    });
+    let base64_encode_input =
+        radio_group("base64-encode-input", data.defaults.base64_encode_input, {
+            clone!(update_defaults);
+            move |kind: InputKind| update_defaults(&|v| v.base64_encode_input = kind)
+        });
+    let base64_encode_alphabet = enum_select(data.defaults.base64_encode_alphabet, {
+        clone!(update_defaults);
+        move |kind: Base64Alphabet| update_defaults(&|v| v.base64_encode_alphabet = kind)
+    });
+    let base64_decode_output = radio_group(
+        "base64-decode-output",
+        data.defaults.base64_decode_output,
+        {
+            clone!(update_defaults);
+            move |kind: DecodedFormat| update_defaults(&|v| v.base64_decode_output = kind)
+        },
+    );
+    let hash_input = radio_group("hash-input", data.defaults.hash_input, {
+        clone!(update_defaults);
+        move |kind: InputKind| update_defaults(&|v| v.hash_input = kind)
+    });
+
+    let tools = Tools::iter()
+        .filter(|v| *v != Tools::Settings)
+        .enumerate()
+        .map(|(i, tool)| {
+            let id = format!("SettingsComponent-check-tool-{i}");
+            let hidden = data.is_hidden(&tool);
+            clone!(update);
+            rsx! {
+                div { class: "form-check form-check-inline",
+                    input {
+                        class: "form-check-input",
+                        id: id.clone(),
+                        r#type: "checkbox",
+                        checked: !hidden,
+                        onchange: move |_| {
+                            clone!(tool);
+                            update(&|v| v.set_hidden(tool.clone(), !hidden))
+                        },
+                    }
+                    label { class: "form-check-label", r#for: id, "{tool}" }
+                }
+            }
+        });
+
+    let load_errors = use_context::<LoadErrors>().0.into_iter().map(|error| {
+        rsx! {
+            div { class: "alert alert-warning py-2 small text-break", "{error}" }
+        }
+    });
+
+    rsx! {
+        div { class: "d-flex flex-column m-1",
+            { load_errors }
+            h5 { "Appearance" }
+            SettingsRow { label: "Theme", { theme } }
+            SettingsRow { label: "Font family",
+                input {
+                    class: "form-control",
+                    r#type: "text",
+                    placeholder: "system-ui, sans-serif",
+                    value: data.font_family.clone(),
+                    oninput: {
+                        clone!(update);
+                        move |e: Event<FormData>| update(&|v| v.font_family = e.value())
+                    },
+                }
+            }
+            SettingsRow { label: "Monospace font family",
+                input {
+                    class: "form-control font-monospace",
+                    r#type: "text",
+                    placeholder: "monospace",
+                    value: data.monospace_font_family.clone(),
+                    oninput: {
+                        clone!(update);
+                        move |e: Event<FormData>| update(&|v| v.monospace_font_family = e.value())
+                    },
+                }
+            }
+            SettingsRow { label: "Font size (px)",
+                input {
+                    class: "form-control",
+                    r#type: "number",
+                    min: "0",
+                    placeholder: "16",
+                    value: if data.font_size > 0 { data.font_size.to_string() } else { String::new() },
+                    oninput: {
+                        clone!(update);
+                        move |e: Event<FormData>| {
+                            let size = e.value().parse().unwrap_or_default();
+                            update(&|v| v.font_size = size)
+                        }
+                    },
+                }
+            }
+            SettingsRow { label: "Always on top",
+                div { class: "form-check form-switch",
+                    input {
+                        class: "form-check-input",
+                        r#type: "checkbox",
+                        role: "switch",
+                        checked: data.always_on_top,
+                        onchange: {
+                            clone!(update);
+                            let always_on_top = !data.always_on_top;
+                            move |_| update(&|v| v.always_on_top = always_on_top)
+                        },
+                    }
+                }
+            }
+
+            hr {}
+
+            h5 { "Zoom" }
+            SettingsRow { label: "Default zoom",
+                input {
+                    class: "form-control",
+                    r#type: "number",
+                    min: "0.1",
+                    step: "0.1",
+                    value: data.default_zoom.to_string(),
+                    oninput: {
+                        clone!(update);
+                        move |e: Event<FormData>| {
+                            if let Ok(zoom) = e.value().parse::<f64>() && zoom > 0.0 {
+                                update(&|v| v.default_zoom = zoom)
+                            }
+                        }
+                    },
+                }
+            }
+            SettingsRow { label: "Zoom step",
+                input {
+                    class: "form-control",
+                    r#type: "number",
+                    min: "0.01",
+                    step: "0.05",
+                    value: data.zoom_step.to_string(),
+                    oninput: {
+                        clone!(update);
+                        move |e: Event<FormData>| {
+                            if let Ok(step) = e.value().parse::<f64>() && step > 0.0 {
+                                update(&|v| v.zoom_step = step)
+                            }
+                        }
+                    },
+                }
+            }
+
+            hr {}
+
+            h5 { "Tool defaults" }
+            SettingsRow { label: "Base64 Encode input", { base64_encode_input } }
+            SettingsRow { label: "Base64 Encode alphabet", { base64_encode_alphabet } }
+            SettingsRow { label: "Base64 Decode output", { base64_decode_output } }
+            SettingsRow { label: "Hash input", { hash_input } }
+
+            hr {}
+
+            h5 { "Sidebar" }
+            div { { tools } }
+        }
+    }
+}
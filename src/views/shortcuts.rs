@@ -0,0 +1,61 @@
+use dioxus::prelude::*;
+
+use super::modal::ModalComponent;
+use crate::keymap::Keymap;
+
+/// Cheatsheet of the current keyboard shortcuts and of the problems of the user ones.
+#[component]
+pub fn ShortcutsComponent(keymap: Keymap, onclose: EventHandler<()>) -> Element {
+    let rows = keymap.cheatsheet().into_iter().map(|(action, shortcuts)| {
+        let shortcuts = shortcuts.into_iter().map(|shortcut| {
+            rsx! {
+                kbd { class: "me-1", "{shortcut}" }
+            }
+        });
+        rsx! {
+            tr {
+                td { "{action}" }
+                td { { shortcuts } }
+            }
+        }
+    });
+    let problems = keymap.problems.iter().map(|problem| {
+        rsx! {
+            li { "{problem}" }
+        }
+    });
+
+    rsx! {
+        ModalComponent { onclose,
+            div {
+                class: "card-body overflow-auto",
+                style: "max-height: 80vh;",
+                tabindex: "0",
+                onmounted: move |e| async move {
+                    _ = e.set_focus(true).await;
+                },
+                onkeydown: move |e| {
+                    if e.key() == Key::Escape {
+                        onclose.call(());
+                    }
+                },
+
+                h5 { class: "card-title", "Keyboard shortcuts" }
+                if !keymap.problems.is_empty() {
+                    div { class: "alert alert-warning",
+                        ul { class: "mb-0", { problems } }
+                    }
+                }
+                table { class: "table table-sm table-hover",
+                    tbody {
+                        { rows }
+                    }
+                }
+                p { class: "small text-body-secondary mb-0",
+                    "Override them with \"shortcuts\" in settings.json, e.g. "
+                    code { r#"[{{"action": "ToggleTheme", "keys": ["Ctrl+T"]}}]"# }
+                }
+            }
+        }
+    }
+}
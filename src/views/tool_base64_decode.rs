@@ -1,74 +1,159 @@
-use base64::prelude::*;
 use dioxus::prelude::*;
 use strum::IntoEnumIterator;
 
-use crate::clone;
+use crate::{
+    actions::select_input,
+    clone,
+    core::base64::{
+        Base64Alphabet, Base64Diagnostic, Base64Text, CustomAlphabet, CustomAlphabetError,
+        DecodedFormat, DecodedFormatDiscriminants, decode_with, format_decoded,
+    },
+    settings::Settings,
+    views::base64_alphabet::alphabet_picker,
+};
 
-#[derive(
-    Debug,
-    Clone,
-    Copy,
-    PartialEq,
-    strum_macros::Display,
-    strum_macros::EnumIter,
-    strum_macros::EnumDiscriminants,
-)]
-#[strum_discriminants(derive(strum_macros::Display))]
-enum Base64DecodeOutputKind {
-    #[strum(to_string = "UTF-8")]
-    Utf8,
-
-    #[strum(to_string = "JSON")]
-    Json,
-
-    #[strum(to_string = "Hex")]
-    SimpleHex,
-
-    #[strum(to_string = "Pretty Hex")]
-    PrettyHex,
-}
-
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct ToolBase64DecodeComponentData {
     input: String,
-    output_kind: Base64DecodeOutputKind,
+    /// Detect the alphabet and padding instead of using `alphabet` or `custom_alphabet`.
+    auto: bool,
+    alphabet: Base64Alphabet,
+    custom: bool,
+    custom_alphabet: CustomAlphabet,
+    output_kind: DecodedFormat,
 }
 
 impl Default for ToolBase64DecodeComponentData {
     fn default() -> Self {
         Self {
             input: Default::default(),
-            output_kind: Base64DecodeOutputKind::Utf8,
+            auto: true,
+            alphabet: Base64Alphabet::Standard,
+            custom: false,
+            custom_alphabet: Default::default(),
+            output_kind: DecodedFormat::Utf8,
         }
     }
 }
 
+impl ToolBase64DecodeComponentData {
+    /// Initial state using the kinds picked in the settings.
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            output_kind: settings.defaults.base64_decode_output,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_input(&mut self, input: String) {
+        self.input = input;
+    }
+
+    pub fn set_auto(&mut self, auto: bool) {
+        self.auto = auto;
+    }
+}
+
+/// Chars of the input shown around a diagnosed offset, on each side.
+const SNIPPET_CONTEXT: usize = 16;
+
+/// The input around `offset`, split before and after the char at `offset`.
+fn snippet(input: &str, offset: usize) -> (String, String, String) {
+    let chars = input.chars().collect::<Vec<_>>();
+    let offset = offset.min(chars.len());
+    let start = offset.saturating_sub(SNIPPET_CONTEXT);
+    let end = (offset + 1 + SNIPPET_CONTEXT).min(chars.len());
+    let text = |range: std::ops::Range<usize>| {
+        chars[range]
+            .iter()
+            .map(|&c| if c.is_control() { ' ' } else { c })
+            .collect::<String>()
+    };
+    (
+        format!(
+            "{}{}",
+            if start > 0 { "…" } else { "" },
+            text(start..offset)
+        ),
+        text(offset..(offset + 1).min(chars.len())),
+        format!(
+            "{}{}",
+            text((offset + 1).min(end)..end),
+            if end < chars.len() { "…" } else { "" }
+        ),
+    )
+}
+
 #[component]
 pub fn ToolBase64DecodeComponent(
     data: ToolBase64DecodeComponentData,
     onupdate: EventHandler<ToolBase64DecodeComponentData>,
 ) -> Element {
-    let decoded = BASE64_STANDARD.decode(&data.input);
-    let (output, input_valid) = decoded
-        .map(|v| match data.output_kind {
-            Base64DecodeOutputKind::Utf8 => String::from_utf8_lossy(&v).to_string(),
-            Base64DecodeOutputKind::Json => {
-                let s = String::from_utf8_lossy(&v).to_string();
-                serde_json::from_str(&s)
-                    .map(|v: serde_json::Value| {
-                        serde_json::to_string_pretty(&v).unwrap_or(s.clone())
-                    })
-                    .unwrap_or(s)
+    let text = Base64Text::new(&data.input);
+    let detected = text.detect_alphabet();
+    let decoded = match (data.auto, data.custom) {
+        (true, _) => decode_with(&text.symbols, detected).map_err(Into::into),
+        (false, true) => data.custom_alphabet.decode(&text.symbols),
+        (false, false) => decode_with(&text.symbols, data.alphabet).map_err(Into::into),
+    };
+    let (output, error, diagnostic) = match decoded {
+        Ok(v) => (format_decoded(&v, data.output_kind), String::new(), None),
+        Err(CustomAlphabetError::Decode(e)) => {
+            (String::new(), String::new(), Some(text.diagnose(&e)))
+        }
+        Err(e) => (String::new(), e.to_string(), None),
+    };
+    let input_valid = diagnostic.is_none();
+    let diagnostic = diagnostic.map(|Base64Diagnostic { message, offset }| {
+        let (before, at, after) = snippet(&data.input, offset);
+        // `setSelectionRange` counts UTF-16 code units.
+        let start = data
+            .input
+            .chars()
+            .take(offset)
+            .map(char::len_utf16)
+            .sum::<usize>();
+        let end = start + data.input.chars().nth(offset).map_or(0, char::len_utf16);
+        rsx! {
+            div { class: "d-flex gap-2 align-items-baseline small",
+                span { class: "text-danger text-nowrap", "{message}" }
+                span {
+                    class: "font-monospace text-break",
+                    role: "button",
+                    title: "Select in the input",
+                    onclick: move |_| select_input(start, end),
+                    "{before}"
+                    mark { "{at}" }
+                    "{after}"
+                }
+            }
+        }
+    });
+
+    let alphabets = alphabet_picker(
+        "ToolBase64DecodeComponent",
+        data.alphabet,
+        data.custom,
+        data.custom_alphabet.clone(),
+        {
+            clone!(data);
+            move |alphabet, custom, custom_alphabet| {
+                clone!(data);
+                onupdate.call(ToolBase64DecodeComponentData {
+                    alphabet,
+                    custom,
+                    custom_alphabet,
+                    ..data
+                });
             }
-            Base64DecodeOutputKind::SimpleHex => hex::encode(&v),
-            Base64DecodeOutputKind::PrettyHex => pretty_hex::pretty_hex(&v),
-        })
-        .map_or((String::new(), false), |v| (v, true));
+        },
+    );
 
-    let output_kinds = Base64DecodeOutputKind::iter().map(|v| {
+    let output_kinds = DecodedFormat::iter().map(|v| {
         let id = format!(
             "ToolBase64DecodeComponent-btn-radio-output-{}",
-            Base64DecodeOutputKindDiscriminants::from(v)
+            DecodedFormatDiscriminants::from(v)
         );
         clone!(data);
         rsx! {
@@ -93,8 +178,41 @@ pub fn ToolBase64DecodeComponent(
         div { class: "d-flex flex-column m-1",
             div { class: "d-flex mb-1",
                 h5 { "Input" }
+                div { class: "d-flex ms-auto gap-2 align-items-center",
+                    if let Some(media_type) = &text.media_type {
+                        span { class: "small text-body-secondary text-nowrap",
+                            if media_type.is_empty() { "Data URI" } else { "Data URI, {media_type}" }
+                        }
+                    }
+                    if data.auto {
+                        span { class: "small text-body-secondary text-nowrap", "{detected}" }
+                    } else {
+                        { alphabets }
+                    }
+                    div { class: "form-check form-switch mb-0 text-nowrap",
+                        input {
+                            class: "form-check-input",
+                            id: "ToolBase64DecodeComponent-auto",
+                            r#type: "checkbox",
+                            role: "switch",
+                            checked: data.auto,
+                            onchange: {
+                                clone!(data);
+                                move |_| {
+                                    clone!(data);
+                                    onupdate.call(ToolBase64DecodeComponentData{
+                                        auto: !data.auto,
+                                        ..data
+                                    });
+                                }
+                            },
+                        }
+                        label { class: "form-check-label", r#for: "ToolBase64DecodeComponent-auto", "Auto" }
+                    }
+                }
             }
             textarea {
+                "data-role": "input",
                 "autocorrect": "off",
                 "autocapitalize": "none",
                 class: format!("font-monospace form-control {}", if !input_valid { "border-danger" } else { "" }),
@@ -111,6 +229,10 @@ pub fn ToolBase64DecodeComponent(
                 },
                 { data.input.clone() }
             }
+            if !error.is_empty() {
+                span { class: "text-danger small", "{error}" }
+            }
+            { diagnostic }
 
             hr {}
 
@@ -123,6 +245,7 @@ pub fn ToolBase64DecodeComponent(
                 }
             }
             textarea {
+                "data-role": "output",
                 readonly: true,
                 class: "font-monospace form-control",
                 rows: "3",
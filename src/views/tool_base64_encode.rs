@@ -1,83 +1,75 @@
-use base64::engine::general_purpose::URL_SAFE;
-use base64::prelude::*;
 use dioxus::prelude::*;
 use strum::IntoEnumIterator;
 
-use crate::clone;
+use crate::{
+    clone,
+    core::{
+        InputKind, InputKindDiscriminants,
+        base64::{Base64Alphabet, CustomAlphabet, encode},
+    },
+    settings::Settings,
+    views::base64_alphabet::alphabet_picker,
+};
 
-#[derive(
-    Debug,
-    Clone,
-    Copy,
-    PartialEq,
-    strum_macros::Display,
-    strum_macros::EnumIter,
-    strum_macros::EnumDiscriminants,
-)]
-#[strum_discriminants(derive(strum_macros::Display))]
-enum Base64EncodeInputKind {
-    #[strum(to_string = "UTF-8")]
-    Utf8,
-    Hex,
-}
-
-#[derive(
-    Debug,
-    Clone,
-    Copy,
-    PartialEq,
-    strum_macros::Display,
-    strum_macros::EnumIter,
-    strum_macros::EnumDiscriminants,
-)]
-#[strum_discriminants(derive(strum_macros::Display))]
-enum Base64EncodeOutputKind {
-    Standard,
-
-    #[strum(to_string = "URL")]
-    UrlSafe,
-}
-
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct ToolBase64EncodeComponentData {
     input: String,
-    input_kind: Base64EncodeInputKind,
-    output_kind: Base64EncodeOutputKind,
+    input_kind: InputKind,
+    output_kind: Base64Alphabet,
+    custom: bool,
+    custom_alphabet: CustomAlphabet,
 }
 
 impl Default for ToolBase64EncodeComponentData {
     fn default() -> Self {
         Self {
             input: Default::default(),
-            input_kind: Base64EncodeInputKind::Utf8,
-            output_kind: Base64EncodeOutputKind::Standard,
+            input_kind: InputKind::Utf8,
+            output_kind: Base64Alphabet::Standard,
+            custom: false,
+            custom_alphabet: Default::default(),
         }
     }
 }
 
+impl ToolBase64EncodeComponentData {
+    /// Initial state using the kinds picked in the settings.
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            input_kind: settings.defaults.base64_encode_input,
+            output_kind: settings.defaults.base64_encode_alphabet,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_input(&mut self, input: String, input_kind: InputKind) {
+        self.input = input;
+        self.input_kind = input_kind;
+    }
+}
+
 #[component]
 pub fn ToolBase64EncodeComponent(
     data: ToolBase64EncodeComponentData,
     onupdate: EventHandler<ToolBase64EncodeComponentData>,
 ) -> Element {
-    let input = match data.input_kind {
-        Base64EncodeInputKind::Utf8 => Ok(data.input.as_bytes().to_vec()),
-        Base64EncodeInputKind::Hex => {
-            let input = data.input.replace([' ', '\t'], "");
-            hex::decode(&input)
-        }
+    let input = data.input_kind.decode(&data.input);
+    let input_valid = input.is_ok();
+    let output = input.map(|v| match data.custom {
+        true => data.custom_alphabet.encode(&v),
+        false => Ok(encode(&v, data.output_kind)),
+    });
+    let (output, error) = match output {
+        Ok(Ok(v)) => (v, String::new()),
+        Ok(Err(e)) => (String::new(), e.to_string()),
+        Err(_) => Default::default(),
     };
-    let (output, input_valid) = input
-        .map(|v| match data.output_kind {
-            Base64EncodeOutputKind::Standard => BASE64_STANDARD.encode(v),
-            Base64EncodeOutputKind::UrlSafe => URL_SAFE.encode(v),
-        })
-        .map_or((String::new(), false), |v| (v, true));
 
-    let input_kinds = Base64EncodeInputKind::iter().map(|v| {
+    let input_kinds = InputKind::iter().map(|v| {
         let id = format!(
             "ToolBase64EncodeComponent-btn-radio-input-{}",
-            Base64EncodeInputKindDiscriminants::from(v)
+            InputKindDiscriminants::from(v)
         );
         clone!(data);
         rsx! {
@@ -97,32 +89,24 @@ pub fn ToolBase64EncodeComponent(
             label { class: "btn btn-outline-primary", r#for: id, "{v}" }
         }
     });
-    let output_kinds = Base64EncodeOutputKind::iter().map(|v| {
-        let id = format!(
-            "ToolBase64EncodeComponent-btn-radio-output-{}",
-            Base64EncodeOutputKindDiscriminants::from(v)
-        );
-        clone!(data);
-        rsx! {
-            input {
-                checked: data.output_kind == v,
-                class: "btn-check",
-                id: id.clone(),
-                r#type: "radio",
-                onchange: {
-                    clone!(data);
-                    move |_| {
-                        clone!(data);
-                        onupdate.call(ToolBase64EncodeComponentData{
-                            output_kind: v,
-                            ..data
-                        });
-                    }
-                }
+    let output_kinds = alphabet_picker(
+        "ToolBase64EncodeComponent",
+        data.output_kind,
+        data.custom,
+        data.custom_alphabet.clone(),
+        {
+            clone!(data);
+            move |output_kind, custom, custom_alphabet| {
+                clone!(data);
+                onupdate.call(ToolBase64EncodeComponentData {
+                    output_kind,
+                    custom,
+                    custom_alphabet,
+                    ..data
+                });
             }
-            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
-        }
-    });
+        },
+    );
 
     rsx! {
         div { class: "d-flex flex-column m-1",
@@ -135,6 +119,7 @@ pub fn ToolBase64EncodeComponent(
                 }
             }
             textarea {
+                "data-role": "input",
                 "autocorrect": "off",
                 "autocapitalize": "none",
                 class: format!("font-monospace form-control {}", if !input_valid { "border-danger" } else { "" }),
@@ -156,18 +141,18 @@ pub fn ToolBase64EncodeComponent(
 
             div { class: "d-flex mb-1",
                 h5 { "Output" }
-                div {
-                    class: "btn-group ms-auto",
-                    role: "group",
-                    { output_kinds }
-                }
+                div { class: "ms-auto", { output_kinds } }
             }
             textarea {
+                "data-role": "output",
                 readonly: true,
                 class: "font-monospace form-control",
                 rows: "3",
                 { output }
             }
+            if !error.is_empty() {
+                span { class: "text-danger small", "{error}" }
+            }
         }
     }
 }
@@ -1,36 +1,810 @@
-use dioxus::prelude::*;
-use sha1::Digest;
+use std::{
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use dioxus::{html::HasFileData, prelude::*};
+use futures_util::StreamExt;
 use strum::IntoEnumIterator;
 
-use crate::clone;
+use super::enum_select::enum_select;
+use crate::{
+    actions::copy_text,
+    clone,
+    core::{
+        InputKind, InputKindDiscriminants,
+        hash::{
+            DigestFormat, HashAlgorithm, HmacKeyKind, HmacKeyKindDiscriminants, MultiHasher,
+            algorithms_with_size, digest_eq, hash_all, hash_reader, hmac, parse_digest,
+        },
+        manifest::{
+            ManifestFormat, ManifestProgress, VerifyResult, VerifyStatus, create_manifest,
+            verify_manifest,
+        },
+    },
+    settings::Settings,
+};
+
+/// How often a file being hashed reports its progress to the UI.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Longest SHAKE output the user can ask for.
+const MAX_XOF_SIZE: usize = 1024;
 
 #[derive(
     Debug,
     Clone,
     Copy,
     PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
     strum_macros::Display,
     strum_macros::EnumIter,
     strum_macros::EnumDiscriminants,
 )]
 #[strum_discriminants(derive(strum_macros::Display))]
-enum HashInputKind {
-    #[strum(to_string = "UTF-8")]
-    Utf8,
-    Hex,
+pub enum HashSource {
+    Text,
+    Files,
+    Manifest,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct ToolHashComponentData {
     input: String,
-    input_kind: HashInputKind,
+    input_kind: InputKind,
+    source: HashSource,
+    /// Published digest the hashes are compared with.
+    expected: String,
+    /// Compute HMACs of the text with `hmac_key` instead of plain hashes.
+    hmac: bool,
+    /// Secret, never written to the session file.
+    #[serde(skip)]
+    hmac_key: String,
+    hmac_key_kind: HmacKeyKind,
+    /// Bytes produced by the extendable-output functions.
+    xof_size: usize,
+    /// How the digests of the text and the files are written.
+    output_format: DigestFormat,
+    manifest_algorithm: HashAlgorithm,
+    manifest_format: ManifestFormat,
+    /// Directory the paths of `manifest` are relative to.
+    manifest_directory: String,
+    manifest: String,
 }
 
 impl Default for ToolHashComponentData {
     fn default() -> Self {
         Self {
             input: Default::default(),
-            input_kind: HashInputKind::Utf8,
+            input_kind: InputKind::Utf8,
+            source: HashSource::Text,
+            expected: Default::default(),
+            hmac: false,
+            hmac_key: Default::default(),
+            hmac_key_kind: HmacKeyKind::Utf8,
+            xof_size: 32,
+            output_format: DigestFormat::Hex,
+            manifest_algorithm: HashAlgorithm::Sha256,
+            manifest_format: ManifestFormat::Gnu,
+            manifest_directory: Default::default(),
+            manifest: Default::default(),
+        }
+    }
+}
+
+impl ToolHashComponentData {
+    /// Initial state using the kinds picked in the settings.
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            input_kind: settings.defaults.hash_input,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_source(&mut self, source: HashSource) {
+        self.source = source;
+    }
+}
+
+/// Size with a binary unit, e.g. `1.5 MiB`.
+fn format_size(bytes: f64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{size:.0} {}", UNITS[unit])
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+fn format_throughput(bytes: u64, elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs_f64();
+    if seconds <= 0.0 {
+        return String::new();
+    }
+    format!("{}/s", format_size(bytes as f64 / seconds))
+}
+
+/// Row class comparing `digest` with the expected one, when they are the same size.
+fn comparison_class(expected: Option<&[u8]>, digest: &[u8]) -> &'static str {
+    match expected {
+        Some(expected) if expected.len() == digest.len() => {
+            if digest_eq(expected, digest) {
+                "table-success"
+            } else {
+                "table-danger"
+            }
+        }
+        _ => "",
+    }
+}
+
+/// Cells of a digest written in `format`, and a button copying it.
+fn digest_cells(format: DigestFormat, algorithm: HashAlgorithm, digest: Option<&[u8]>) -> Element {
+    let formatted = digest.map(|v| format.format(algorithm, v));
+    match formatted {
+        Some(Some(text)) => rsx! {
+            td { class: "text-break", "{text}" }
+            td { class: "text-end",
+                button {
+                    class: "btn btn-outline-secondary btn-sm py-0",
+                    title: "Copy",
+                    onclick: move |_| copy_text(text.clone()),
+                    "⧉"
+                }
+            }
+        },
+        Some(None) => rsx! {
+            td { class: "text-body-secondary", "No {format} form" }
+            td {}
+        },
+        None => rsx! {
+            td {}
+            td {}
+        },
+    }
+}
+
+/// Whether any of `digests` is the expected one.
+fn comparison_verdict(expected: Option<&[u8]>, digests: &[(HashAlgorithm, Vec<u8>)]) -> Element {
+    let Some(expected) = expected else {
+        return rsx! {};
+    };
+    let candidates = digests
+        .iter()
+        .filter(|(_, digest)| digest.len() == expected.len())
+        .map(|(algorithm, _)| *algorithm)
+        .collect::<Vec<_>>();
+    let matched = digests
+        .iter()
+        .find(|(_, digest)| digest_eq(expected, digest));
+    match matched {
+        Some((algorithm, _)) => rsx! {
+            span { class: "text-success ms-2 align-self-center", "✓ Matches {algorithm}" }
+        },
+        None if candidates.is_empty() => rsx! {},
+        None => {
+            let candidates = candidates
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            rsx! {
+                span { class: "text-danger ms-2 align-self-center", "✗ Mismatch, none of {candidates} matches" }
+            }
+        }
+    }
+}
+
+/// Run `job` on a blocking thread, passing what it reports to `onprogress` at most every
+/// [`PROGRESS_INTERVAL`].
+async fn run_blocking<P, T>(
+    job: impl FnOnce(&mut dyn FnMut(P)) -> T + Send + 'static,
+    mut onprogress: impl FnMut(P),
+) -> Result<T, String>
+where
+    P: Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, mut receiver) = futures::channel::mpsc::unbounded();
+    let task = tokio::task::spawn_blocking(move || {
+        let mut reported = Instant::now();
+        job(&mut |progress| {
+            if reported.elapsed() >= PROGRESS_INTERVAL {
+                reported = Instant::now();
+                _ = sender.unbounded_send(progress);
+            }
+        })
+    });
+    // The sender is dropped, ending this loop, once the job returns.
+    while let Some(progress) = receiver.next().await {
+        onprogress(progress);
+    }
+    task.await.map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FileHashState {
+    Queued,
+    Hashing {
+        done: u64,
+        elapsed: Duration,
+    },
+    Done {
+        digests: Vec<(HashAlgorithm, Vec<u8>)>,
+        elapsed: Duration,
+    },
+    Failed(String),
+    Cancelled,
+}
+
+/// A file picked by the user and its hashing progress.
+#[derive(Debug, Clone)]
+struct FileHash {
+    id: usize,
+    path: PathBuf,
+    size: u64,
+    state: FileHashState,
+    cancel: Arc<AtomicBool>,
+}
+
+impl FileHash {
+    fn finished(&self) -> bool {
+        !matches!(
+            self.state,
+            FileHashState::Queued | FileHashState::Hashing { .. }
+        )
+    }
+}
+
+/// Hash the file `id` of `files` on a blocking thread, updating its state as it goes.
+async fn hash_file(
+    mut files: Signal<Vec<FileHash>>,
+    id: usize,
+    algorithms: Vec<HashAlgorithm>,
+    xof_size: usize,
+    cancel_all: Arc<AtomicBool>,
+) {
+    let Some((path, cancel)) = files
+        .peek()
+        .iter()
+        .find(|v| v.id == id)
+        .map(|v| (v.path.clone(), v.cancel.clone()))
+    else {
+        return;
+    };
+    let mut set_state = move |state: FileHashState| {
+        if let Some(file) = files.write().iter_mut().find(|v| v.id == id) {
+            file.state = state;
+        }
+    };
+
+    let started = Instant::now();
+    set_state(FileHashState::Hashing {
+        done: 0,
+        elapsed: Duration::ZERO,
+    });
+    let result = run_blocking(
+        {
+            clone!(algorithms);
+            move |report| {
+                let file = std::fs::File::open(&path)?;
+                let hasher = MultiHasher::with_xof_size(&algorithms, xof_size);
+                hash_reader(file, hasher, |done| {
+                    if cancel.load(Ordering::Relaxed) || cancel_all.load(Ordering::Relaxed) {
+                        return ControlFlow::Break(());
+                    }
+                    report(done);
+                    ControlFlow::Continue(())
+                })
+            }
+        },
+        |done| {
+            set_state(FileHashState::Hashing {
+                done,
+                elapsed: started.elapsed(),
+            })
+        },
+    )
+    .await;
+    set_state(match result {
+        Ok(Ok(Some(digests))) => FileHashState::Done {
+            digests: algorithms.into_iter().zip(digests).collect(),
+            elapsed: started.elapsed(),
+        },
+        Ok(Ok(None)) => FileHashState::Cancelled,
+        Ok(Err(e)) => FileHashState::Failed(e.to_string()),
+        Err(e) => FileHashState::Failed(e),
+    });
+}
+
+#[component]
+fn HashFilesComponent(
+    algorithms: Vec<HashAlgorithm>,
+    xof_size: usize,
+    expected: Option<Vec<u8>>,
+    format: DigestFormat,
+    onformatupdate: EventHandler<DigestFormat>,
+) -> Element {
+    let mut files = use_signal(Vec::<FileHash>::new);
+    let mut next_id = use_signal(|| 0);
+    // Stops the blocking threads when the tool is closed.
+    let cancel_all = use_hook(|| Arc::new(AtomicBool::new(false)));
+    use_drop({
+        clone!(cancel_all);
+        move || cancel_all.store(true, Ordering::Relaxed)
+    });
+
+    // Files keep the algorithms selected when they were added.
+    let add_files = {
+        clone!(cancel_all, algorithms);
+        move |paths: Vec<String>| {
+            let ids = paths
+                .into_iter()
+                .map(|path| {
+                    let id = next_id();
+                    next_id += 1;
+                    let path = PathBuf::from(path);
+                    let size = std::fs::metadata(&path)
+                        .map(|v| v.len())
+                        .unwrap_or_default();
+                    files.write().push(FileHash {
+                        id,
+                        path,
+                        size,
+                        state: FileHashState::Queued,
+                        cancel: Default::default(),
+                    });
+                    id
+                })
+                .collect::<Vec<_>>();
+            // One file at a time, they would only compete for the disk otherwise.
+            clone!(cancel_all, algorithms);
+            spawn(async move {
+                for id in ids {
+                    hash_file(files, id, algorithms.clone(), xof_size, cancel_all.clone()).await;
+                }
+            });
+        }
+    };
+
+    let files_read = files.read();
+    let rows = files_read.iter().rev().map(|file| {
+        let cancel = file.cancel.clone();
+        let status = match &file.state {
+            FileHashState::Queued => rsx! {
+                span { class: "text-body-secondary", "Queued" }
+            },
+            FileHashState::Hashing { done, elapsed } => {
+                let percent = (*done * 100).checked_div(file.size).unwrap_or(0).min(100);
+                rsx! {
+                    div { class: "d-flex align-items-center gap-2",
+                        div { class: "progress flex-grow-1", title: "{percent}%",
+                            div { class: "progress-bar progress-bar-striped progress-bar-animated", style: "width: {percent}%;" }
+                        }
+                        span { class: "small text-nowrap",
+                            "{format_size(*done as f64)} of {format_size(file.size as f64)}, {format_throughput(*done, *elapsed)}"
+                        }
+                    }
+                }
+            }
+            FileHashState::Done { digests, elapsed } => {
+                let digest_rows = digests.iter().map(|(name, digest)| {
+                    rsx! {
+                        tr { class: comparison_class(expected.as_deref(), digest),
+                            th { scope: "row", "{name}" }
+                            { digest_cells(format, *name, Some(digest)) }
+                        }
+                    }
+                });
+                rsx! {
+                    details { open: true,
+                        summary { class: "small",
+                            "{format_size(file.size as f64)} in {elapsed.as_secs_f64():.2} s, {format_throughput(file.size, *elapsed)}"
+                            { comparison_verdict(expected.as_deref(), digests) }
+                        }
+                        table { class: "table table-sm table-hover font-monospace selectable mb-0",
+                            tbody {
+                                { digest_rows }
+                            }
+                        }
+                    }
+                }
+            }
+            FileHashState::Failed(e) => rsx! {
+                span { class: "text-danger", "{e}" }
+            },
+            FileHashState::Cancelled => rsx! {
+                span { class: "text-body-secondary", "Cancelled" }
+            },
+        };
+
+        rsx! {
+            div { class: "card mb-2",
+                div { class: "card-header d-flex align-items-center gap-2 py-1",
+                    span { class: "fw-bold text-break font-monospace", "{file.path.display()}" }
+                    if !file.finished() {
+                        button {
+                            class: "btn btn-outline-danger btn-sm ms-auto",
+                            onclick: move |_| cancel.store(true, Ordering::Relaxed),
+                            "Cancel"
+                        }
+                    }
+                }
+                div { class: "card-body p-2", { status } }
+            }
+        }
+    });
+    let any_finished = files_read.iter().any(FileHash::finished);
+
+    rsx! {
+        div { class: "d-flex flex-column",
+            ondragover: move |e| e.prevent_default(),
+            ondrop: {
+                clone!(mut add_files);
+                move |e: Event<DragData>| {
+                    e.prevent_default();
+                    if let Some(files) = e.files() {
+                        add_files(files.files());
+                    }
+                }
+            },
+
+            div { class: "d-flex mb-1 gap-2",
+                input {
+                    class: "form-control form-control-sm w-auto",
+                    r#type: "file",
+                    multiple: true,
+                    onchange: {
+                        clone!(mut add_files);
+                        move |e: Event<FormData>| {
+                            if let Some(files) = e.files() {
+                                add_files(files.files());
+                            }
+                        }
+                    },
+                }
+                span { class: "small text-body-secondary align-self-center", "or drop files here" }
+                div { class: "ms-auto",
+                    { enum_select(format, move |v| onformatupdate.call(v)) }
+                }
+                button {
+                    class: "btn btn-outline-secondary btn-sm",
+                    disabled: !any_finished,
+                    onclick: move |_| files.write().retain(|v| !v.finished()),
+                    "Clear"
+                }
+            }
+            div { "data-role": "output",
+                { rows }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ManifestJob {
+    Idle,
+    Running {
+        progress: ManifestProgress,
+        started: Instant,
+    },
+    /// Files that couldn't be read while creating the manifest.
+    Created(Vec<String>),
+    Verified(Vec<VerifyResult>),
+    Failed(String),
+    Cancelled,
+}
+
+fn verify_badge_class(status: &VerifyStatus) -> &'static str {
+    match status {
+        VerifyStatus::Ok => "text-bg-success",
+        VerifyStatus::Missing => "text-bg-warning",
+        VerifyStatus::Improper => "text-bg-secondary",
+        VerifyStatus::Failed
+        | VerifyStatus::Unreadable(_)
+        | VerifyStatus::WrongLength(..)
+        | VerifyStatus::OutsideRoot => "text-bg-danger",
+    }
+}
+
+/// Create a manifest of a directory, or verify the files listed in one.
+#[component]
+fn HashManifestComponent(
+    data: ToolHashComponentData,
+    onupdate: EventHandler<ToolHashComponentData>,
+) -> Element {
+    let mut job = use_signal(|| ManifestJob::Idle);
+    // Only one job runs at a time, this stops it.
+    let cancel = use_hook(|| Arc::new(AtomicBool::new(false)));
+    use_drop({
+        clone!(cancel);
+        move || cancel.store(true, Ordering::Relaxed)
+    });
+    let running = matches!(*job.read(), ManifestJob::Running { .. });
+    // What the user changed during a run is kept, creating only fills in the manifest.
+    let mut latest = use_hook(|| CopyValue::new(data.clone()));
+    latest.set(data.clone());
+
+    let start = {
+        clone!(cancel);
+        move || {
+            cancel.store(false, Ordering::Relaxed);
+            let started = Instant::now();
+            job.set(ManifestJob::Running {
+                progress: Default::default(),
+                started,
+            });
+            let cancel = cancel.clone();
+            let report = move |progress: ManifestProgress,
+                               report: &mut dyn FnMut(ManifestProgress)| {
+                if cancel.load(Ordering::Relaxed) {
+                    return ControlFlow::Break(());
+                }
+                report(progress);
+                ControlFlow::Continue(())
+            };
+            let onprogress = move |progress| job.set(ManifestJob::Running { progress, started });
+            (report, onprogress)
+        }
+    };
+
+    let create = {
+        clone!(data, mut start);
+        move |_| {
+            let (report, onprogress) = start();
+            clone!(data);
+            spawn(async move {
+                let root = PathBuf::from(data.manifest_directory.trim());
+                let algorithm = data.manifest_algorithm;
+                let format = data.manifest_format;
+                let result = run_blocking(
+                    move |progress| {
+                        create_manifest(&root, algorithm, format, |v| report(v, progress))
+                    },
+                    onprogress,
+                )
+                .await;
+                match result {
+                    Ok(Ok(Some(manifest))) => {
+                        job.set(ManifestJob::Created(manifest.errors));
+                        onupdate.call(ToolHashComponentData {
+                            manifest: manifest.text,
+                            ..latest.cloned()
+                        });
+                    }
+                    Ok(Ok(None)) => job.set(ManifestJob::Cancelled),
+                    Ok(Err(e)) => job.set(ManifestJob::Failed(e.to_string())),
+                    Err(e) => job.set(ManifestJob::Failed(e)),
+                }
+            });
+        }
+    };
+
+    let verify = {
+        clone!(data, mut start);
+        move |_| {
+            let (report, onprogress) = start();
+            clone!(data);
+            spawn(async move {
+                let root = PathBuf::from(data.manifest_directory.trim());
+                let algorithm = data.manifest_algorithm;
+                let text = data.manifest.clone();
+                let result = run_blocking(
+                    move |progress| {
+                        verify_manifest(&text, &root, algorithm, |v| report(v, progress))
+                    },
+                    onprogress,
+                )
+                .await;
+                job.set(match result {
+                    Ok(Some(results)) => ManifestJob::Verified(results),
+                    Ok(None) => ManifestJob::Cancelled,
+                    Err(e) => ManifestJob::Failed(e),
+                });
+            });
+        }
+    };
+
+    let status = match &*job.read() {
+        ManifestJob::Idle => rsx! {},
+        ManifestJob::Running { progress, started } => {
+            let percent = (progress.files_done * 100)
+                .checked_div(progress.files)
+                .unwrap_or(0);
+            let cancel = cancel.clone();
+            rsx! {
+                div { class: "d-flex align-items-center gap-2 mb-2",
+                    div { class: "progress flex-grow-1", title: "{percent}%",
+                        div { class: "progress-bar progress-bar-striped progress-bar-animated", style: "width: {percent}%;" }
+                    }
+                    span { class: "small text-nowrap",
+                        "{progress.files_done} of {progress.files} files, {format_size(progress.bytes as f64)}, {format_throughput(progress.bytes, started.elapsed())}"
+                    }
+                    button {
+                        class: "btn btn-outline-danger btn-sm",
+                        onclick: move |_| cancel.store(true, Ordering::Relaxed),
+                        "Cancel"
+                    }
+                }
+            }
+        }
+        ManifestJob::Created(errors) => {
+            let errors = errors.iter().map(|e| {
+                rsx! {
+                    li { "{e}" }
+                }
+            });
+            rsx! {
+                if errors.len() > 0 {
+                    div { class: "alert alert-warning",
+                        "Left out of the manifest:"
+                        ul { class: "mb-0", { errors } }
+                    }
+                }
+            }
+        }
+        ManifestJob::Verified(results) => {
+            let count =
+                |f: fn(&VerifyStatus) -> bool| results.iter().filter(|v| f(&v.status)).count();
+            let ok = count(|v| *v == VerifyStatus::Ok);
+            let missing = count(|v| *v == VerifyStatus::Missing);
+            let failed = results.len() - ok - missing;
+            let rows = results.iter().map(|result| {
+                rsx! {
+                    tr {
+                        td { "{result.line}" }
+                        td { class: "text-break", "{result.path}" }
+                        td {
+                            span { class: "badge {verify_badge_class(&result.status)}", "{result.status}" }
+                        }
+                    }
+                }
+            });
+            rsx! {
+                div { class: "mb-1",
+                    span { class: "badge text-bg-success me-1", "{ok} OK" }
+                    span { class: "badge text-bg-danger me-1", "{failed} FAILED" }
+                    span { class: "badge text-bg-warning", "{missing} MISSING" }
+                }
+                table { class: "table table-sm table-hover font-monospace selectable",
+                    thead {
+                        tr {
+                            th { scope: "col", "Line" }
+                            th { scope: "col", "File" }
+                            th { scope: "col", "Status" }
+                        }
+                    }
+                    tbody {
+                        { rows }
+                    }
+                }
+            }
+        }
+        ManifestJob::Failed(e) => rsx! {
+            div { class: "text-danger mb-2", "{e}" }
+        },
+        ManifestJob::Cancelled => rsx! {
+            div { class: "text-body-secondary mb-2", "Cancelled" }
+        },
+    };
+
+    rsx! {
+        div { class: "d-flex flex-column",
+            div { class: "d-flex mb-1 gap-2 align-items-center",
+                {
+                    clone!(data);
+                    enum_select(data.manifest_algorithm, move |manifest_algorithm| {
+                        onupdate.call(ToolHashComponentData {
+                            manifest_algorithm,
+                            ..data.clone()
+                        })
+                    })
+                }
+                {
+                    clone!(data);
+                    enum_select(data.manifest_format, move |manifest_format| {
+                        onupdate.call(ToolHashComponentData {
+                            manifest_format,
+                            ..data.clone()
+                        })
+                    })
+                }
+                input {
+                    class: "form-control form-control-sm",
+                    r#type: "text",
+                    placeholder: "Directory",
+                    value: data.manifest_directory.clone(),
+                    oninput: {
+                        clone!(data);
+                        move |v: Event<FormData>| {
+                            clone!(data);
+                            onupdate.call(ToolHashComponentData{
+                                manifest_directory: v.value(),
+                                ..data
+                            });
+                        }
+                    },
+                }
+                button {
+                    class: "btn btn-outline-primary btn-sm text-nowrap",
+                    disabled: running || data.manifest_directory.trim().is_empty(),
+                    onclick: create,
+                    "Create"
+                }
+                button {
+                    class: "btn btn-outline-primary btn-sm text-nowrap",
+                    disabled: running || data.manifest_directory.trim().is_empty() || data.manifest.trim().is_empty(),
+                    onclick: verify,
+                    "Verify"
+                }
+            }
+            div { class: "d-flex mb-1 gap-2",
+                h6 { class: "align-self-center mb-0", "Manifest" }
+                input {
+                    class: "form-control form-control-sm ms-auto w-auto",
+                    r#type: "file",
+                    onchange: {
+                        clone!(data);
+                        move |e: Event<FormData>| {
+                            clone!(data);
+                            async move {
+                                let Some(files) = e.files() else {
+                                    return;
+                                };
+                                let Some(path) = files.files().into_iter().next() else {
+                                    return;
+                                };
+                                let Some(manifest) = files.read_file_to_string(&path).await else {
+                                    return;
+                                };
+                                // Paths in a manifest are relative to where it is.
+                                let manifest_directory = Path::new(&path)
+                                    .parent()
+                                    .map(|v| v.display().to_string())
+                                    .unwrap_or_default();
+                                onupdate.call(ToolHashComponentData{
+                                    manifest,
+                                    manifest_directory,
+                                    ..data
+                                });
+                            }
+                        }
+                    },
+                }
+            }
+            textarea {
+                "data-role": "output",
+                "autocorrect": "off",
+                "autocapitalize": "none",
+                class: "font-monospace form-control mb-2",
+                rows: "8",
+                placeholder: "Create a manifest from the directory, or paste or load one to verify",
+                oninput: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        onupdate.call(ToolHashComponentData{
+                            manifest: v.value(),
+                            ..data
+                        });
+                    }
+                },
+                { data.manifest.clone() }
+            }
+            { status }
         }
     }
 }
@@ -39,53 +813,177 @@ impl Default for ToolHashComponentData {
 pub fn ToolHashComponent(
     data: ToolHashComponentData,
     onupdate: EventHandler<ToolHashComponentData>,
+    /// Rows shown, shared by every session.
+    algorithms: Vec<HashAlgorithm>,
+    onalgorithmsupdate: EventHandler<Vec<HashAlgorithm>>,
 ) -> Element {
-    let input = match data.input_kind {
-        HashInputKind::Utf8 => Ok(data.input.as_bytes().to_vec()),
-        HashInputKind::Hex => {
-            let input = data.input.replace([' ', '\t'], "");
-            hex::decode(&input)
+    let key = data.hmac.then(|| data.hmac_key_kind.decode(&data.hmac_key));
+    let key_error = match &key {
+        Some(Err(e)) => e.to_string(),
+        _ => String::new(),
+    };
+    let input_valid = data.input_kind.decode(&data.input).is_ok();
+
+    // Hashing is only redone when the input changes, not on every render.
+    let text = data.input.clone();
+    let input_kind = data.input_kind;
+    let key = key.map(|v| v.ok());
+    let selected = algorithms.clone();
+    let xof_size = data.xof_size;
+    let digests = use_memo(use_reactive!(|(
+        text,
+        input_kind,
+        key,
+        selected,
+        xof_size,
+    )| {
+        let input = input_kind.decode(&text).ok()?;
+        match key {
+            None => Some(
+                selected
+                    .iter()
+                    .copied()
+                    .zip(hash_all(&selected, xof_size, &input))
+                    .collect::<Vec<_>>(),
+            ),
+            // A key that doesn't decode has no HMAC.
+            Some(key) => {
+                let key = key.as_ref()?;
+                Some(
+                    selected
+                        .iter()
+                        .filter_map(|&v| Some((v, hmac(v, key, &input)?)))
+                        .collect(),
+                )
+            }
+        }
+    }));
+
+    let expected = parse_digest(&data.expected);
+    let expected_valid = expected.is_some() || data.expected.trim().is_empty();
+    let expected_hint = match &expected {
+        Some((bytes, encoding)) => {
+            let candidates = algorithms_with_size(bytes.len())
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>();
+            if candidates.is_empty() {
+                format!(
+                    "{} bytes of {encoding}, no algorithm has this size",
+                    bytes.len()
+                )
+            } else {
+                format!(
+                    "{} bytes of {encoding}, {}",
+                    bytes.len(),
+                    candidates.join(" or ")
+                )
+            }
         }
+        None if !expected_valid => "Neither hex nor base64".to_string(),
+        None => String::new(),
     };
+    let expected = expected.map(|(bytes, _)| bytes);
+    let verdict = comparison_verdict(
+        expected.as_deref(),
+        digests.read().as_deref().unwrap_or_default(),
+    );
 
-    let hashers: Vec<(_, Box<dyn digest::DynDigest>)> = vec![
-        ("MD5", Box::new(md5::Md5::new())),
-        ("SHA1", Box::new(sha1::Sha1::new())),
-        ("SHA2-224", Box::new(sha2::Sha224::new())),
-        ("SHA2-256", Box::new(sha2::Sha256::new())),
-        ("SHA2-384", Box::new(sha2::Sha384::new())),
-        ("SHA2-512", Box::new(sha2::Sha512::new())),
-        ("SHA2-512/224", Box::new(sha2::Sha512_224::new())),
-        ("SHA2-512/256", Box::new(sha2::Sha512_256::new())),
-        ("SHA3-224", Box::new(sha3::Sha3_224::new())),
-        ("SHA3-256", Box::new(sha3::Sha3_256::new())),
-        ("SHA3-384", Box::new(sha3::Sha3_384::new())),
-        ("SHA3-512", Box::new(sha3::Sha3_512::new())),
-    ];
-    let hashe_rows = hashers.into_iter().map(|(name, mut hasher)| {
-        let hash_size_bytes = hasher.output_size();
-        let hash = input
+    // Algorithms without an HMAC have no row in HMAC mode.
+    let shown = if data.hmac {
+        algorithms
+            .iter()
+            .copied()
+            .filter(HashAlgorithm::has_hmac)
+            .collect::<Vec<_>>()
+    } else {
+        algorithms.clone()
+    };
+    let hashe_rows = shown.iter().map(|&name| {
+        let digest = digests.read().as_ref().and_then(|v| {
+            v.iter()
+                .find(|(algorithm, _)| *algorithm == name)
+                .map(|(_, digest)| digest.clone())
+        });
+        let hash_size_bytes = if name.is_xof() {
+            data.xof_size
+        } else {
+            name.output_size()
+        };
+        let class = digest
             .as_ref()
-            .map(|v| {
-                hasher.update(v);
-                hex::encode(hasher.finalize())
-            })
+            .map(|v| comparison_class(expected.as_deref(), v))
             .unwrap_or_default();
 
         rsx! {
-            tr {
+            tr { class,
                 th { scope: "row", "{name}" }
                 td { "{hash_size_bytes} ({hash_size_bytes*8})" }
-                td { "{hash}" }
+                { digest_cells(data.output_format, name, digest.as_deref()) }
+            }
+        }
+    });
+
+    let algorithm_group = |cryptographic: bool| {
+        HashAlgorithm::iter()
+            .filter(move |v| v.is_cryptographic() == cryptographic)
+            .map(|v| {
+                let id = format!("ToolHashComponent-algorithm-{v:?}");
+                let checked = algorithms.contains(&v);
+                clone!(algorithms);
+                rsx! {
+                    div { class: "form-check form-check-inline",
+                        input {
+                            class: "form-check-input",
+                            id: id.clone(),
+                            r#type: "checkbox",
+                            checked,
+                            onchange: move |_| {
+                                // Kept in catalogue order, whatever order they are ticked in.
+                                let selected = HashAlgorithm::iter()
+                                    .filter(|w| if *w == v { !checked } else { algorithms.contains(w) })
+                                    .collect();
+                                onalgorithmsupdate.call(selected);
+                            }
+                        }
+                        label { class: "form-check-label", r#for: id, "{v}" }
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let cryptographic_algorithms = algorithm_group(true);
+    let checksum_algorithms = algorithm_group(false);
+    let any_xof = algorithms.iter().any(HashAlgorithm::is_xof);
+
+    let sources = HashSource::iter().map(|v| {
+        let id = format!(
+            "ToolHashComponent-btn-radio-source-{}",
+            HashSourceDiscriminants::from(v)
+        );
+        clone!(data);
+        rsx! {
+            input {
+                checked: data.source == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| {
+                    clone!(data);
+                    onupdate.call(ToolHashComponentData{
+                        source: v,
+                        ..data
+                    });
+                }
             }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
         }
     });
-    let input_valid = input.is_ok();
 
-    let input_kinds = HashInputKind::iter().map(|v| {
+    let input_kinds = InputKind::iter().map(|v| {
         let id = format!(
             "ToolHashComponent-btn-radio-input-{}",
-            HashInputKindDiscriminants::from(v)
+            InputKindDiscriminants::from(v)
         );
         clone!(data);
         rsx! {
@@ -106,51 +1004,268 @@ pub fn ToolHashComponent(
         }
     });
 
+    let key_kinds = HmacKeyKind::iter().map(|v| {
+        let id = format!(
+            "ToolHashComponent-btn-radio-key-{}",
+            HmacKeyKindDiscriminants::from(v)
+        );
+        clone!(data);
+        rsx! {
+            input {
+                checked: data.hmac_key_kind == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| {
+                    clone!(data);
+                    onupdate.call(ToolHashComponentData{
+                        hmac_key_kind: v,
+                        ..data
+                    });
+                }
+            }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
+        }
+    });
+
     rsx! {
         div { class: "d-flex flex-column m-1",
             div { class: "d-flex mb-1",
                 h5 { "Input" }
                 div {
-                    class: "btn-group ms-auto",
+                    class: "btn-group ms-2",
                     role: "group",
-                    { input_kinds }
+                    { sources }
+                }
+                if data.source == HashSource::Text {
+                    div {
+                        class: "btn-group ms-auto",
+                        role: "group",
+                        { input_kinds }
+                    }
                 }
             }
-            textarea {
-                "autocorrect": "off",
-                "autocapitalize": "none",
-                class: format!("font-monospace form-control {}", if !input_valid { "border-danger" } else { "" }),
-                rows: "3",
-                oninput: {
-                    clone!(data);
-                    move |v: Event<FormData>| {
+            if data.source != HashSource::Manifest {
+                div { class: "d-flex mb-1 gap-2 align-items-center",
+                    h6 { class: "mb-0", "Expected" }
+                    input {
+                        class: format!("font-monospace form-control form-control-sm {}", if !expected_valid { "border-danger" } else { "" }),
+                        r#type: "text",
+                        placeholder: "Published hex or base64 digest",
+                        value: data.expected.clone(),
+                        oninput: {
+                            clone!(data);
+                            move |v: Event<FormData>| {
+                                clone!(data);
+                                onupdate.call(ToolHashComponentData{
+                                    expected: v.value(),
+                                    ..data
+                                });
+                            }
+                        },
+                    }
+                    span {
+                        class: format!("small text-nowrap {}", if !expected_valid { "text-danger" } else { "text-body-secondary" }),
+                        "{expected_hint}"
+                    }
+                }
+            }
+            if data.source == HashSource::Text {
+                textarea {
+                    "data-role": "input",
+                    "autocorrect": "off",
+                    "autocapitalize": "none",
+                    class: format!("font-monospace form-control {}", if !input_valid { "border-danger" } else { "" }),
+                    rows: "3",
+                    oninput: {
                         clone!(data);
-                        onupdate.call(ToolHashComponentData{
-                            input: v.value(),
-                            ..data
-                        });
+                        move |v: Event<FormData>| {
+                            clone!(data);
+                            onupdate.call(ToolHashComponentData{
+                                input: v.value(),
+                                ..data
+                            });
+                        }
+                    },
+                    { data.input.clone() }
+                }
+                div { class: "d-flex mt-1 gap-2 align-items-center",
+                    div { class: "form-check form-switch mb-0 text-nowrap",
+                        input {
+                            class: "form-check-input",
+                            id: "ToolHashComponent-hmac",
+                            r#type: "checkbox",
+                            role: "switch",
+                            checked: data.hmac,
+                            onchange: {
+                                clone!(data);
+                                move |_| {
+                                    clone!(data);
+                                    onupdate.call(ToolHashComponentData{
+                                        hmac: !data.hmac,
+                                        ..data
+                                    });
+                                }
+                            },
+                        }
+                        label { class: "form-check-label", r#for: "ToolHashComponent-hmac", "HMAC" }
                     }
-                },
-                { data.input.clone() }
+                    if data.hmac {
+                        input {
+                            class: format!("font-monospace form-control form-control-sm {}", if !key_error.is_empty() { "border-danger" } else { "" }),
+                            r#type: "text",
+                            placeholder: "Key",
+                            value: data.hmac_key.clone(),
+                            oninput: {
+                                clone!(data);
+                                move |v: Event<FormData>| {
+                                    clone!(data);
+                                    onupdate.call(ToolHashComponentData{
+                                        hmac_key: v.value(),
+                                        ..data
+                                    });
+                                }
+                            },
+                        }
+                        div {
+                            class: "btn-group btn-group-sm",
+                            role: "group",
+                            { key_kinds }
+                        }
+                    }
+                }
+                if !key_error.is_empty() {
+                    span { class: "text-danger small", "{key_error}" }
+                }
             }
+            if data.source != HashSource::Manifest {
+                details { class: "mt-1",
+                    summary { class: "small", "Algorithms ({algorithms.len()} of {HashAlgorithm::iter().count()})" }
+                    div { class: "small fw-bold mt-1", "Cryptographic" }
+                    div { { cryptographic_algorithms.into_iter() } }
+                    div { class: "small fw-bold mt-1", "Non-cryptographic" }
+                    div { { checksum_algorithms.into_iter() } }
+                    div { class: "d-flex mt-1 gap-2 align-items-center",
+                        button {
+                            class: "btn btn-outline-secondary btn-sm",
+                            onclick: move |_| onalgorithmsupdate.call(HashAlgorithm::defaults()),
+                            "Defaults"
+                        }
+                        button {
+                            class: "btn btn-outline-secondary btn-sm",
+                            onclick: move |_| onalgorithmsupdate.call(HashAlgorithm::iter().collect()),
+                            "All"
+                        }
+                        button {
+                            class: "btn btn-outline-secondary btn-sm",
+                            onclick: move |_| onalgorithmsupdate.call(vec![]),
+                            "None"
+                        }
+                        if any_xof {
+                            label { class: "small text-nowrap ms-auto", r#for: "ToolHashComponent-xof-size", "SHAKE output bytes" }
+                            input {
+                                class: "form-control form-control-sm w-auto",
+                                id: "ToolHashComponent-xof-size",
+                                r#type: "number",
+                                min: "1",
+                                max: "{MAX_XOF_SIZE}",
+                                value: "{data.xof_size}",
+                                oninput: {
+                                    clone!(data);
+                                    move |v: Event<FormData>| {
+                                        let Ok(xof_size) = v.value().parse::<usize>() else {
+                                            return;
+                                        };
+                                        clone!(data);
+                                        onupdate.call(ToolHashComponentData{
+                                            xof_size: xof_size.clamp(1, MAX_XOF_SIZE),
+                                            ..data
+                                        });
+                                    }
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+            if data.source == HashSource::Text {
 
-            hr {}
+                hr {}
 
-            div { class: "d-flex mb-1",
-                h5 { "Output" }
-            }
-            table { class: "table table-hover font-monospace selectable",
-                thead {
-                    tr {
-                        th { scope: "col", "Algorithm" }
-                        th { scope: "col", "Bytes (Bits)" }
-                        th { scope: "col", "Hash" }
+                div { class: "d-flex mb-1",
+                    h5 { "Output" }
+                    { verdict }
+                    div { class: "ms-auto",
+                        {
+                            clone!(data);
+                            enum_select(data.output_format, move |output_format| {
+                                onupdate.call(ToolHashComponentData {
+                                    output_format,
+                                    ..data.clone()
+                                })
+                            })
+                        }
+                    }
+                }
+                table { class: "table table-hover font-monospace selectable",
+                    "data-role": "output",
+                    thead {
+                        tr {
+                            th { scope: "col", "Algorithm" }
+                            th { scope: "col", "Bytes (Bits)" }
+                            th { scope: "col", if data.hmac { "HMAC" } else { "Hash" } }
+                            th { scope: "col" }
+                        }
+                    }
+                    tbody {
+                        { hashe_rows }
                     }
                 }
-                tbody {
-                    { hashe_rows }
+            }
+            // Kept mounted so that switching to another source doesn't stop the running jobs.
+            div { hidden: data.source != HashSource::Files,
+                HashFilesComponent {
+                    algorithms: algorithms.clone(),
+                    xof_size: data.xof_size,
+                    expected: expected.clone(),
+                    format: data.output_format,
+                    onformatupdate: {
+                        clone!(data);
+                        move |output_format| {
+                            onupdate.call(ToolHashComponentData {
+                                output_format,
+                                ..data.clone()
+                            })
+                        }
+                    },
                 }
             }
+            div { hidden: data.source != HashSource::Manifest,
+                HashManifestComponent { data: data.clone(), onupdate }
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Session;
+
+    #[test]
+    fn hmac_key_is_not_saved() {
+        let session = Session {
+            hash: ToolHashComponentData {
+                hmac: true,
+                hmac_key: "secret".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let json = serde_json::to_string_pretty(&session).unwrap();
+        assert!(!json.contains("secret"), "{json}");
+        let restored = serde_json::from_str::<Session>(&json).unwrap();
+        assert!(restored.hash.hmac);
+        assert_eq!(restored.hash.hmac_key, "");
+    }
+}
@@ -0,0 +1,397 @@
+use std::sync::Arc;
+
+use dioxus::{
+    html::{FileEngine, HasFileData},
+    prelude::*,
+};
+use strum::IntoEnumIterator;
+
+use crate::{
+    clone,
+    core::certificate::{
+        CertificateInfo, TrustStoreKind, TrustStoreKindDiscriminants, der_to_pem,
+        inspect_certificate, load_trust_anchors, parse_validation_time, read_certificates,
+        validate_chain,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ToolCertificateComponentData {
+    input: String,
+    trust_store_kind: TrustStoreKind,
+    /// PEM trust anchors pasted by the user.
+    trust_bundle: String,
+    /// Directory holding PEM or DER trust anchors, e.g. /etc/ssl/certs.
+    trust_directory: String,
+    /// Instant to validate at as `YYYY-MM-DDTHH:MM` local time, empty means now.
+    validation_time: String,
+    hostname: String,
+}
+
+impl Default for ToolCertificateComponentData {
+    fn default() -> Self {
+        Self {
+            input: Default::default(),
+            trust_store_kind: TrustStoreKind::Directory,
+            trust_bundle: Default::default(),
+            trust_directory: "/etc/ssl/certs".to_string(),
+            validation_time: Default::default(),
+            hostname: Default::default(),
+        }
+    }
+}
+
+impl ToolCertificateComponentData {
+    pub fn set_input(&mut self, input: String) {
+        self.input = input;
+    }
+}
+
+/// Turn the content of a dropped or picked file into text for the input box.
+fn file_to_input(content: Vec<u8>) -> String {
+    match String::from_utf8(content) {
+        Ok(text) => text,
+        Err(e) => der_to_pem(e.as_bytes()),
+    }
+}
+
+async fn read_files(files: Arc<dyn FileEngine>) -> String {
+    let mut input = String::new();
+    for file in files.files() {
+        if let Some(content) = files.read_file(&file).await {
+            input.push_str(&file_to_input(content));
+        }
+    }
+    input
+}
+
+#[component]
+pub fn ToolCertificateComponent(
+    data: ToolCertificateComponentData,
+    onupdate: EventHandler<ToolCertificateComponentData>,
+) -> Element {
+    let now = jiff::Timestamp::now();
+    let ders = (!data.input.trim().is_empty())
+        .then(|| read_certificates(data.input.as_bytes()))
+        .transpose()
+        .map(|v| v.unwrap_or_default());
+    let certificates = ders.as_ref().map(|ders| {
+        ders.iter()
+            .map(|der| inspect_certificate(der, now))
+            .collect::<Vec<_>>()
+    });
+    let input_valid = certificates
+        .as_ref()
+        .is_ok_and(|v| v.iter().all(|v| v.is_ok()));
+
+    let chain = match certificates {
+        Ok(certificates) => {
+            let items = certificates
+                .into_iter()
+                .enumerate()
+                .map(|(i, info)| match info {
+                    Ok(info) => rsx! {
+                        CertificateInfoComponent { index: i, info }
+                    },
+                    Err(e) => rsx! {
+                        div { class: "text-danger mb-2", "#{i} {e:#}" }
+                    },
+                });
+            rsx! {
+                { items }
+            }
+        }
+        Err(e) => rsx! {
+            div { class: "text-danger", "{e:#}" }
+        },
+    };
+
+    rsx! {
+        div { class: "d-flex flex-column m-1",
+            ondragover: move |e| e.prevent_default(),
+            ondrop: {
+                clone!(data);
+                move |e: Event<DragData>| {
+                    clone!(data);
+                    async move {
+                        e.prevent_default();
+                        if let Some(files) = e.files() {
+                            onupdate.call(ToolCertificateComponentData{
+                                input: read_files(files).await,
+                                ..data
+                            });
+                        }
+                    }
+                }
+            },
+
+            div { class: "d-flex mb-1",
+                h5 { "Input" }
+                input {
+                    class: "form-control form-control-sm ms-auto w-auto",
+                    r#type: "file",
+                    accept: ".pem,.crt,.cer,.der",
+                    multiple: true,
+                    onchange: {
+                        clone!(data);
+                        move |e: Event<FormData>| {
+                            clone!(data);
+                            async move {
+                                if let Some(files) = e.files() {
+                                    onupdate.call(ToolCertificateComponentData{
+                                        input: read_files(files).await,
+                                        ..data
+                                    });
+                                }
+                            }
+                        }
+                    },
+                }
+            }
+            textarea {
+                "data-role": "input",
+                "autocorrect": "off",
+                "autocapitalize": "none",
+                class: format!("font-monospace form-control {}", if !input_valid { "border-danger" } else { "" }),
+                rows: "5",
+                placeholder: "PEM or base64 DER, or drop a .crt/.cer/.der file",
+                oninput: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        onupdate.call(ToolCertificateComponentData{
+                            input: v.value(),
+                            ..data
+                        });
+                    }
+                },
+                { data.input.clone() }
+            }
+
+            hr {}
+
+            div { class: "d-flex mb-1",
+                h5 { "Output" }
+            }
+            { chain }
+
+            hr {}
+
+            ToolCertificateValidationComponent {
+                chain: ders.unwrap_or_default(),
+                data: data.clone(),
+                onupdate,
+            }
+        }
+    }
+}
+
+#[component]
+fn CertificateInfoComponent(index: usize, info: CertificateInfo) -> Element {
+    let rows = info.fields.into_iter().map(|field| {
+        rsx! {
+            tr {
+                th { scope: "row", class: "text-nowrap", "{field.name}" }
+                td {
+                    for value in field.values {
+                        div { "{value}" }
+                    }
+                }
+            }
+        }
+    });
+
+    rsx! {
+        details { class: "mb-2", open: true,
+            summary { class: "h6", "#{index} {info.title}" }
+            table { class: "table table-sm table-hover font-monospace selectable",
+                "data-role": "output",
+                tbody {
+                    { rows }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ToolCertificateValidationComponent(
+    chain: Vec<Vec<u8>>,
+    data: ToolCertificateComponentData,
+    onupdate: EventHandler<ToolCertificateComponentData>,
+) -> Element {
+    // Reading a whole directory of anchors is too slow to redo on every keystroke.
+    let trust_store_kind = data.trust_store_kind;
+    let trust_bundle = data.trust_bundle.clone();
+    let trust_directory = data.trust_directory.clone();
+    let anchors = use_memo(use_reactive!(|(
+        trust_store_kind,
+        trust_bundle,
+        trust_directory,
+    )| {
+        load_trust_anchors(trust_store_kind, &trust_bundle, &trust_directory)
+            .map_err(|e| format!("{e:#}"))
+    }));
+    let time = parse_validation_time(&data.validation_time);
+
+    let checks = match (&*anchors.read(), &time) {
+        _ if chain.is_empty() => Ok(vec![]),
+        (Err(e), _) => Err(e.clone()),
+        (_, Err(e)) => Err(format!("{e:#}")),
+        (Ok(anchors), Ok(time)) => {
+            validate_chain(&chain, anchors, *time, &data.hostname).map_err(|e| format!("{e:#}"))
+        }
+    };
+    let (checks, error) = match checks {
+        Ok(v) => (v, String::new()),
+        Err(e) => (vec![], e),
+    };
+    let verdict = (!checks.is_empty()).then(|| checks.iter().all(|v| v.result.is_ok()));
+    let anchor_count = anchors.read().as_ref().map(|v| v.len()).unwrap_or_default();
+
+    let rows = checks.into_iter().map(|v| {
+        let (class, icon, text) = match v.result {
+            Ok(text) => ("", "✅", text),
+            Err(text) => ("text-danger", "❌", text),
+        };
+        rsx! {
+            tr {
+                td { "{v.certificate}" }
+                th { scope: "row", "{v.check}" }
+                td { class, "{icon} {text}" }
+            }
+        }
+    });
+
+    let kinds = TrustStoreKind::iter().map(|v| {
+        let id = format!(
+            "ToolCertificateComponent-btn-radio-trust-{}",
+            TrustStoreKindDiscriminants::from(v)
+        );
+        clone!(data);
+        rsx! {
+            input {
+                checked: data.trust_store_kind == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| {
+                    clone!(data);
+                    onupdate.call(ToolCertificateComponentData{
+                        trust_store_kind: v,
+                        ..data
+                    });
+                }
+            }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
+        }
+    });
+
+    let trust_store = match data.trust_store_kind {
+        TrustStoreKind::Bundle => rsx! {
+            textarea {
+                "autocorrect": "off",
+                "autocapitalize": "none",
+                class: "font-monospace form-control",
+                rows: "3",
+                placeholder: "PEM trust anchors",
+                oninput: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        onupdate.call(ToolCertificateComponentData{
+                            trust_bundle: v.value(),
+                            ..data
+                        });
+                    }
+                },
+                { data.trust_bundle.clone() }
+            }
+        },
+        TrustStoreKind::Directory => rsx! {
+            input {
+                class: "font-monospace form-control",
+                r#type: "text",
+                value: data.trust_directory.clone(),
+                oninput: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        onupdate.call(ToolCertificateComponentData{
+                            trust_directory: v.value(),
+                            ..data
+                        });
+                    }
+                },
+            }
+        },
+    };
+
+    rsx! {
+        div { class: "d-flex mb-1",
+            h5 { "Validation" }
+            match verdict {
+                Some(true) => rsx! { span { class: "badge text-bg-success ms-2 align-self-center", "valid" } },
+                Some(false) => rsx! { span { class: "badge text-bg-danger ms-2 align-self-center", "invalid" } },
+                None => rsx! {},
+            }
+            div {
+                class: "btn-group ms-auto",
+                role: "group",
+                { kinds }
+            }
+        }
+        { trust_store }
+        div { class: "form-text mb-2", "{anchor_count} trust anchors" }
+
+        div { class: "d-flex mb-2",
+            div { class: "input-group me-2",
+                span { class: "input-group-text", "Time" }
+                input {
+                    class: format!("form-control {}", if time.is_err() { "border-danger" } else { "" }),
+                    r#type: "datetime-local",
+                    step: "1",
+                    value: data.validation_time.clone(),
+                    oninput: {
+                        clone!(data);
+                        move |v: Event<FormData>| {
+                            clone!(data);
+                            onupdate.call(ToolCertificateComponentData{
+                                validation_time: v.value(),
+                                ..data
+                            });
+                        }
+                    },
+                }
+            }
+            div { class: "input-group",
+                span { class: "input-group-text", "Hostname" }
+                input {
+                    class: "font-monospace form-control",
+                    r#type: "text",
+                    value: data.hostname.clone(),
+                    oninput: {
+                        clone!(data);
+                        move |v: Event<FormData>| {
+                            clone!(data);
+                            onupdate.call(ToolCertificateComponentData{
+                                hostname: v.value(),
+                                ..data
+                            });
+                        }
+                    },
+                }
+            }
+        }
+
+        if !error.is_empty() {
+            div { class: "text-danger", "{error}" }
+        }
+        table { class: "table table-sm table-hover font-monospace selectable",
+            tbody {
+                { rows }
+            }
+        }
+    }
+}
@@ -0,0 +1,553 @@
+use std::str::FromStr;
+
+use dioxus::prelude::*;
+use jiff::{Span, Timestamp, tz::TimeZone};
+use strum::IntoEnumIterator;
+
+use crate::{
+    clone,
+    core::datetime::{
+        DateTimeRoundMode, DateTimeUnit, add_rows, datetime_rows, difference_rows, parse_datetime,
+        parse_zoned,
+    },
+};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+pub enum DateTimeMode {
+    Convert,
+    Arithmetic,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+enum DateTimeOperation {
+    Add,
+    Subtract,
+    Difference,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ToolDateTimeComponentData {
+    mode: DateTimeMode,
+    input: String,
+    /// IANA time zones always shown in the output.
+    pinned_zones: Vec<String>,
+    zone_input: String,
+    operation: DateTimeOperation,
+    start: String,
+    end: String,
+    /// ISO 8601 (`P1DT2H`) or friendly (`1h30m`) duration.
+    span: String,
+    /// IANA time zone the arithmetic is done in, the system one if empty.
+    zone: String,
+    largest: DateTimeUnit,
+    smallest: DateTimeUnit,
+    round_mode: DateTimeRoundMode,
+}
+
+impl Default for ToolDateTimeComponentData {
+    fn default() -> Self {
+        Self {
+            mode: DateTimeMode::Convert,
+            input: Default::default(),
+            pinned_zones: Default::default(),
+            zone_input: Default::default(),
+            operation: DateTimeOperation::Add,
+            start: Default::default(),
+            end: Default::default(),
+            span: Default::default(),
+            zone: Default::default(),
+            largest: DateTimeUnit::Years,
+            smallest: DateTimeUnit::Nanoseconds,
+            round_mode: DateTimeRoundMode::HalfExpand,
+        }
+    }
+}
+
+impl ToolDateTimeComponentData {
+    pub fn set_mode(&mut self, mode: DateTimeMode) {
+        self.mode = mode;
+    }
+
+    pub fn set_input(&mut self, input: String) {
+        self.input = input;
+    }
+}
+
+#[component]
+pub fn ToolDateTimeComponent(
+    data: ToolDateTimeComponentData,
+    onupdate: EventHandler<ToolDateTimeComponentData>,
+) -> Element {
+    let modes = DateTimeMode::iter().map(|v| {
+        let id = format!(
+            "ToolDateTimeComponent-btn-radio-mode-{}",
+            DateTimeModeDiscriminants::from(v)
+        );
+        clone!(data);
+        rsx! {
+            input {
+                checked: data.mode == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| {
+                    clone!(data);
+                    onupdate.call(ToolDateTimeComponentData{
+                        mode: v,
+                        ..data
+                    });
+                }
+            }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
+        }
+    });
+
+    let zone_names = use_hook(|| jiff::tz::db().available().collect::<Vec<_>>());
+    let zone_options = zone_names.iter().map(|v| rsx! { option { value: "{v}" } });
+
+    let body = match data.mode {
+        DateTimeMode::Convert => rsx! {
+            ToolDateTimeConvertComponent { data: data.clone(), onupdate }
+        },
+        DateTimeMode::Arithmetic => rsx! {
+            ToolDateTimeArithmeticComponent { data: data.clone(), onupdate }
+        },
+    };
+
+    rsx! {
+        div { class: "d-flex flex-column m-1",
+            div { class: "d-flex mb-1",
+                div {
+                    class: "btn-group ms-auto",
+                    role: "group",
+                    { modes }
+                }
+            }
+            datalist { id: "ToolDateTimeComponent-zones", { zone_options } }
+            { body }
+        }
+    }
+}
+
+#[component]
+fn ToolDateTimeConvertComponent(
+    data: ToolDateTimeComponentData,
+    onupdate: EventHandler<ToolDateTimeComponentData>,
+) -> Element {
+    let parsed =
+        (!data.input.trim().is_empty()).then(|| parse_datetime(&data.input, &TimeZone::system()));
+    let rows = parsed
+        .as_ref()
+        .and_then(|v| v.as_ref().ok())
+        .map(|(zoned, _)| datetime_rows(zoned.timestamp(), &data.pinned_zones));
+    let input_valid =
+        parsed.as_ref().is_none_or(|v| v.is_ok()) && rows.as_ref().is_none_or(|v| v.is_ok());
+    let detected = match &parsed {
+        Some(Ok((_, format))) => format.to_string(),
+        Some(Err(e)) => format!("{e:#}"),
+        None => String::new(),
+    };
+    let zone_valid = TimeZone::get(data.zone_input.trim()).is_ok();
+
+    let rows = rows.and_then(|v| v.ok()).unwrap_or_default();
+    let rows = rows.into_iter().map(|(name, value)| {
+        let pinned = data.pinned_zones.contains(&name);
+        clone!(data);
+        rsx! {
+            tr {
+                th { scope: "row", class: "text-nowrap", "{name}" }
+                td { "{value}" }
+                td {
+                    if pinned {
+                        button {
+                            class: "btn btn-sm btn-outline-secondary",
+                            onclick: move |_| {
+                                clone!(data);
+                                let pinned_zones = data.pinned_zones.iter().filter(|v| **v != name).cloned().collect();
+                                onupdate.call(ToolDateTimeComponentData{
+                                    pinned_zones,
+                                    ..data
+                                });
+                            },
+                            "✕"
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "d-flex mb-1",
+            h5 { "Input" }
+            span { class: if input_valid { "badge text-bg-secondary ms-2 align-self-center" } else { "text-danger ms-2 align-self-center" },
+                "{detected}"
+            }
+            button {
+                class: "btn btn-outline-secondary btn-sm ms-auto",
+                onclick: {
+                    clone!(data);
+                    move |_| {
+                        clone!(data);
+                        onupdate.call(ToolDateTimeComponentData{
+                            input: Timestamp::now().as_second().to_string(),
+                            ..data
+                        });
+                    }
+                },
+                "Now"
+            }
+        }
+        input {
+            "data-role": "input",
+            "autocorrect": "off",
+            "autocapitalize": "none",
+            class: format!("font-monospace form-control {}", if !input_valid { "border-danger" } else { "" }),
+            r#type: "text",
+            placeholder: "Unix time, RFC 3339, RFC 2822, ISO 8601 week date or HTTP date",
+            value: data.input.clone(),
+            oninput: {
+                clone!(data);
+                move |v: Event<FormData>| {
+                    clone!(data);
+                    onupdate.call(ToolDateTimeComponentData{
+                        input: v.value(),
+                        ..data
+                    });
+                }
+            },
+        }
+
+        hr {}
+
+        div { class: "d-flex mb-1",
+            h5 { "Output" }
+            div { class: "input-group input-group-sm ms-auto w-auto",
+                input {
+                    class: "form-control",
+                    r#type: "text",
+                    list: "ToolDateTimeComponent-zones",
+                    placeholder: "IANA time zone",
+                    value: data.zone_input.clone(),
+                    oninput: {
+                        clone!(data);
+                        move |v: Event<FormData>| {
+                            clone!(data);
+                            onupdate.call(ToolDateTimeComponentData{
+                                zone_input: v.value(),
+                                ..data
+                            });
+                        }
+                    },
+                }
+                button {
+                    class: "btn btn-outline-primary",
+                    disabled: !zone_valid || data.pinned_zones.contains(&data.zone_input.trim().to_string()),
+                    onclick: {
+                        clone!(data);
+                        move |_| {
+                            clone!(mut data);
+                            data.pinned_zones.push(data.zone_input.trim().to_string());
+                            onupdate.call(ToolDateTimeComponentData{
+                                zone_input: String::new(),
+                                ..data
+                            });
+                        }
+                    },
+                    "Pin"
+                }
+            }
+        }
+        table { class: "table table-hover font-monospace selectable",
+            "data-role": "output",
+            tbody {
+                { rows }
+            }
+        }
+    }
+}
+
+#[component]
+fn ToolDateTimeArithmeticComponent(
+    data: ToolDateTimeComponentData,
+    onupdate: EventHandler<ToolDateTimeComponentData>,
+) -> Element {
+    let zone_valid = data.zone.trim().is_empty() || TimeZone::get(data.zone.trim()).is_ok();
+    let start = (!data.start.trim().is_empty()).then(|| parse_zoned(&data.start, &data.zone));
+    let start_valid = start.as_ref().is_none_or(|v| v.is_ok());
+    let is_difference = data.operation == DateTimeOperation::Difference;
+
+    let (second_valid, rows) = if is_difference {
+        let end = (!data.end.trim().is_empty()).then(|| parse_zoned(&data.end, &data.zone));
+        let end_valid = end.as_ref().is_none_or(|v| v.is_ok());
+        let rows = match (start, end) {
+            (Some(Ok(start)), Some(Ok(end))) => Some(difference_rows(
+                &start,
+                &end,
+                data.largest.into(),
+                data.smallest.into(),
+                data.round_mode.into(),
+            )),
+            _ => None,
+        };
+        (end_valid, rows)
+    } else {
+        let span = (!data.span.trim().is_empty()).then(|| Span::from_str(data.span.trim()));
+        let span_valid = span.as_ref().is_none_or(|v| v.is_ok());
+        let rows = match (start, span) {
+            (Some(Ok(start)), Some(Ok(span))) => Some(add_rows(
+                &start,
+                span,
+                data.operation == DateTimeOperation::Subtract,
+                data.smallest.into(),
+                data.round_mode.into(),
+            )),
+            _ => None,
+        };
+        (span_valid, rows)
+    };
+    let (rows, error) = match rows {
+        None => (vec![], String::new()),
+        Some(Ok(v)) => (v, String::new()),
+        Some(Err(e)) => (vec![], format!("{e:#}")),
+    };
+
+    let operations = DateTimeOperation::iter().map(|v| {
+        let id = format!(
+            "ToolDateTimeComponent-btn-radio-operation-{}",
+            DateTimeOperationDiscriminants::from(v)
+        );
+        clone!(data);
+        rsx! {
+            input {
+                checked: data.operation == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| {
+                    clone!(data);
+                    onupdate.call(ToolDateTimeComponentData{
+                        operation: v,
+                        ..data
+                    });
+                }
+            }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
+        }
+    });
+    let units = |selected: DateTimeUnit| {
+        DateTimeUnit::iter().map(move |v| {
+            rsx! {
+                option { value: "{v}", selected: v == selected, "{v}" }
+            }
+        })
+    };
+    let round_modes = DateTimeRoundMode::iter().map(|v| {
+        rsx! {
+            option { value: "{v}", selected: v == data.round_mode, "{v}" }
+        }
+    });
+    let rows = rows.into_iter().map(|(name, value)| {
+        rsx! {
+            tr {
+                th { scope: "row", class: "text-nowrap", "{name}" }
+                td { "{value}" }
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "d-flex mb-1",
+            h5 { "Start" }
+            div { class: "btn-group btn-group-sm ms-auto", role: "group",
+                { operations }
+            }
+        }
+        div { class: "input-group mb-2",
+            input {
+                "data-role": "input",
+                "autocorrect": "off",
+                "autocapitalize": "none",
+                class: format!("font-monospace form-control {}", if !start_valid { "border-danger" } else { "" }),
+                r#type: "text",
+                placeholder: "Unix time, RFC 3339, RFC 2822, ISO 8601 week date or HTTP date",
+                value: data.start.clone(),
+                oninput: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        onupdate.call(ToolDateTimeComponentData{
+                            start: v.value(),
+                            ..data
+                        });
+                    }
+                },
+            }
+            input {
+                class: format!("form-control flex-grow-0 w-auto {}", if !zone_valid { "border-danger" } else { "" }),
+                r#type: "text",
+                list: "ToolDateTimeComponent-zones",
+                placeholder: "Time zone (system)",
+                value: data.zone.clone(),
+                oninput: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        onupdate.call(ToolDateTimeComponentData{
+                            zone: v.value(),
+                            ..data
+                        });
+                    }
+                },
+            }
+            button {
+                class: "btn btn-outline-secondary",
+                onclick: {
+                    clone!(data);
+                    move |_| {
+                        clone!(data);
+                        onupdate.call(ToolDateTimeComponentData{
+                            start: Timestamp::now().to_string(),
+                            ..data
+                        });
+                    }
+                },
+                "Now"
+            }
+        }
+
+        if is_difference {
+            h5 { "End" }
+            input {
+                "autocorrect": "off",
+                "autocapitalize": "none",
+                class: format!("font-monospace form-control {}", if !second_valid { "border-danger" } else { "" }),
+                r#type: "text",
+                placeholder: "Unix time, RFC 3339, RFC 2822, ISO 8601 week date or HTTP date",
+                value: data.end.clone(),
+                oninput: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        onupdate.call(ToolDateTimeComponentData{
+                            end: v.value(),
+                            ..data
+                        });
+                    }
+                },
+            }
+        } else {
+            h5 { "Duration" }
+            input {
+                "autocorrect": "off",
+                "autocapitalize": "none",
+                class: format!("font-monospace form-control {}", if !second_valid { "border-danger" } else { "" }),
+                r#type: "text",
+                placeholder: "P1DT2H or 1h30m",
+                value: data.span.clone(),
+                oninput: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        onupdate.call(ToolDateTimeComponentData{
+                            span: v.value(),
+                            ..data
+                        });
+                    }
+                },
+            }
+        }
+
+        hr {}
+
+        div { class: "d-flex mb-1 gap-1",
+            h5 { "Output" }
+            span { class: "text-danger ms-2 me-auto align-self-center", "{error}" }
+            if is_difference {
+                select {
+                    class: "form-select form-select-sm w-auto",
+                    title: "Largest unit",
+                    onchange: {
+                        clone!(data);
+                        move |v: Event<FormData>| {
+                            clone!(data);
+                            let Ok(largest) = DateTimeUnit::from_str(&v.value()) else {
+                                return;
+                            };
+                            onupdate.call(ToolDateTimeComponentData{
+                                largest,
+                                ..data
+                            });
+                        }
+                    },
+                    { units(data.largest) }
+                }
+            }
+            select {
+                class: "form-select form-select-sm w-auto",
+                title: "Round to",
+                onchange: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        let Ok(smallest) = DateTimeUnit::from_str(&v.value()) else {
+                            return;
+                        };
+                        onupdate.call(ToolDateTimeComponentData{
+                            smallest,
+                            ..data
+                        });
+                    }
+                },
+                { units(data.smallest) }
+            }
+            select {
+                class: "form-select form-select-sm w-auto",
+                title: "Rounding mode",
+                onchange: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        let Ok(round_mode) = DateTimeRoundMode::from_str(&v.value()) else {
+                            return;
+                        };
+                        onupdate.call(ToolDateTimeComponentData{
+                            round_mode,
+                            ..data
+                        });
+                    }
+                },
+                { round_modes }
+            }
+        }
+        table { class: "table table-hover font-monospace selectable",
+            "data-role": "output",
+            tbody {
+                { rows }
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use dioxus::prelude::*;
+
+use crate::{
+    clone,
+    core::detect::{InputType, detect_input},
+    session::detected_tool,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ToolDetectComponentData {
+    input: String,
+}
+
+#[component]
+pub fn ToolDetectComponent(
+    data: ToolDetectComponentData,
+    onupdate: EventHandler<ToolDetectComponentData>,
+    onopen: EventHandler<(InputType, String)>,
+) -> Element {
+    let detections = detect_input(&data.input);
+    let nothing_found = detections.is_empty() && !data.input.trim().is_empty();
+
+    let rows = detections.into_iter().map(|detection| {
+        let input_type = detection.input_type;
+        let confidence = detection.confidence;
+        let open = detected_tool(input_type).map(|tool| {
+            clone!(data);
+            rsx! {
+                button {
+                    class: "btn btn-outline-primary btn-sm",
+                    onclick: move |_| onopen.call((input_type, data.input.trim().to_string())),
+                    "Open {tool}"
+                }
+            }
+        });
+
+        rsx! {
+            tr {
+                th { scope: "row", "{input_type}" }
+                td { class: "align-middle", style: "width: 120px;",
+                    div { class: "progress", title: "{confidence}%",
+                        div { class: "progress-bar", style: "width: {confidence}%;" }
+                    }
+                }
+                td { "{detection.detail}" }
+                td { class: "text-end", { open } }
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "d-flex flex-column m-1",
+            div { class: "d-flex mb-1",
+                h5 { "Input" }
+                if nothing_found {
+                    span { class: "text-danger ms-2 align-self-center", "Unrecognized input" }
+                }
+            }
+            textarea {
+                "data-role": "input",
+                "autocorrect": "off",
+                "autocapitalize": "none",
+                class: "font-monospace form-control",
+                rows: "5",
+                placeholder: "Paste a JWT, certificate, Base64, hex, timestamp, IP address, UUID, URL or JSON",
+                oninput: move |v: Event<FormData>| {
+                    onupdate.call(ToolDetectComponentData{
+                        input: v.value(),
+                    });
+                },
+                { data.input.clone() }
+            }
+
+            hr {}
+
+            div { class: "d-flex mb-1",
+                h5 { "Candidates" }
+            }
+            table { class: "table table-hover selectable",
+                tbody {
+                    { rows }
+                }
+            }
+        }
+    }
+}
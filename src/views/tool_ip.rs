@@ -0,0 +1,418 @@
+use anyhow::anyhow;
+use dioxus::prelude::*;
+use ipnet::IpNet;
+use strum::IntoEnumIterator;
+
+use crate::{
+    clone,
+    core::ip::{
+        IpSplitKind, IpSplitKindDiscriminants, classify_ip, ip_gaps, ip_overlaps, ip_rows,
+        lookup_ip, parse_ip_input, parse_ip_ranges, split_ip_range,
+    },
+};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+pub enum IpMode {
+    Address,
+    Planner,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+enum IpPlannerOperation {
+    Aggregate,
+
+    #[strum(to_string = "Overlaps & Gaps")]
+    OverlapsAndGaps,
+
+    Split,
+    Lookup,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ToolIpComponentData {
+    mode: IpMode,
+    input: String,
+    /// CIDR list used by the planner, one per line.
+    ranges: String,
+    operation: IpPlannerOperation,
+    split_kind: IpSplitKind,
+    split_value: String,
+    /// Address or CIDR searched for in `ranges`.
+    lookup: String,
+}
+
+impl Default for ToolIpComponentData {
+    fn default() -> Self {
+        Self {
+            mode: IpMode::Address,
+            input: Default::default(),
+            ranges: Default::default(),
+            operation: IpPlannerOperation::Aggregate,
+            split_kind: IpSplitKind::Count,
+            split_value: Default::default(),
+            lookup: Default::default(),
+        }
+    }
+}
+
+impl ToolIpComponentData {
+    pub fn set_mode(&mut self, mode: IpMode) {
+        self.mode = mode;
+    }
+
+    pub fn set_input(&mut self, input: String) {
+        self.input = input;
+    }
+}
+
+/// A titled list of results, shown one per line so it can be copied as is.
+#[derive(Debug, Clone, PartialEq)]
+struct IpPlannerSection {
+    title: String,
+    lines: Vec<String>,
+}
+
+fn ip_planner_sections(data: &ToolIpComponentData) -> anyhow::Result<Vec<IpPlannerSection>> {
+    let ranges = parse_ip_ranges(&data.ranges)?;
+    let section = |title: String, lines: Vec<String>| IpPlannerSection { title, lines };
+    let to_lines = |nets: Vec<IpNet>| nets.iter().map(IpNet::to_string).collect::<Vec<_>>();
+
+    Ok(match data.operation {
+        IpPlannerOperation::Aggregate => {
+            let aggregated = IpNet::aggregate(&ranges);
+            vec![section(
+                format!("Aggregated ({} → {})", ranges.len(), aggregated.len()),
+                to_lines(aggregated),
+            )]
+        }
+        IpPlannerOperation::OverlapsAndGaps => {
+            let overlaps = ip_overlaps(&ranges);
+            let gaps = ip_gaps(&ranges);
+            vec![
+                section(format!("Overlaps ({})", overlaps.len()), overlaps),
+                section(format!("Gaps ({})", gaps.len()), to_lines(gaps)),
+            ]
+        }
+        IpPlannerOperation::Split => {
+            let [parent] = ranges[..] else {
+                return Err(anyhow!("enter exactly one parent block to split"));
+            };
+            if data.split_value.trim().is_empty() {
+                return Ok(vec![]);
+            }
+            let subnets = split_ip_range(parent, data.split_kind, &data.split_value)?;
+            vec![section(
+                format!("Subnets of {parent} ({})", subnets.len()),
+                to_lines(subnets),
+            )]
+        }
+        IpPlannerOperation::Lookup => {
+            if data.lookup.trim().is_empty() {
+                return Ok(vec![]);
+            }
+            let (_, needle) = parse_ip_input(&data.lookup)?;
+            let matches = lookup_ip(&ranges, needle);
+            let title = match matches.len() {
+                0 => format!("{} is not in any range", data.lookup.trim()),
+                n => format!("{} is in {n} range(s)", data.lookup.trim()),
+            };
+            vec![section(title, to_lines(matches))]
+        }
+    })
+}
+
+#[component]
+pub fn ToolIpComponent(
+    data: ToolIpComponentData,
+    onupdate: EventHandler<ToolIpComponentData>,
+) -> Element {
+    let modes = IpMode::iter().map(|v| {
+        let id = format!(
+            "ToolIpComponent-btn-radio-mode-{}",
+            IpModeDiscriminants::from(v)
+        );
+        clone!(data);
+        rsx! {
+            input {
+                checked: data.mode == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| {
+                    clone!(data);
+                    onupdate.call(ToolIpComponentData{
+                        mode: v,
+                        ..data
+                    });
+                }
+            }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
+        }
+    });
+
+    let body = match data.mode {
+        IpMode::Address => rsx! {
+            ToolIpAddressComponent { data: data.clone(), onupdate }
+        },
+        IpMode::Planner => rsx! {
+            ToolIpPlannerComponent { data: data.clone(), onupdate }
+        },
+    };
+
+    rsx! {
+        div { class: "d-flex flex-column m-1",
+            div { class: "d-flex mb-1",
+                div {
+                    class: "btn-group ms-auto",
+                    role: "group",
+                    { modes }
+                }
+            }
+            { body }
+        }
+    }
+}
+
+#[component]
+fn ToolIpAddressComponent(
+    data: ToolIpComponentData,
+    onupdate: EventHandler<ToolIpComponentData>,
+) -> Element {
+    let parsed = (!data.input.trim().is_empty()).then(|| parse_ip_input(&data.input));
+    let input_valid = parsed.as_ref().is_none_or(|v| v.is_ok());
+    let error = match &parsed {
+        Some(Err(e)) => format!("{e:#}"),
+        _ => String::new(),
+    };
+    let (classes, rows) = match parsed {
+        Some(Ok((address, net))) => (classify_ip(address), ip_rows(address, net)),
+        _ => (vec![], vec![]),
+    };
+
+    let classes = classes.into_iter().map(|v| {
+        rsx! {
+            span { class: "badge text-bg-secondary ms-1 align-self-center", "{v}" }
+        }
+    });
+    let rows = rows.into_iter().map(|(name, value)| {
+        rsx! {
+            tr {
+                th { scope: "row", class: "text-nowrap", "{name}" }
+                td { "{value}" }
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "d-flex mb-1",
+            h5 { "Input" }
+            span { class: "text-danger ms-2 align-self-center", "{error}" }
+        }
+        input {
+            "data-role": "input",
+            "autocorrect": "off",
+            "autocapitalize": "none",
+            class: format!("font-monospace form-control {}", if !input_valid { "border-danger" } else { "" }),
+            r#type: "text",
+            placeholder: "192.168.1.10/24, 10.0.0.1 255.255.255.0 or 2001:db8::1/64",
+            value: data.input.clone(),
+            oninput: {
+                clone!(data);
+                move |v: Event<FormData>| {
+                    clone!(data);
+                    onupdate.call(ToolIpComponentData{
+                        input: v.value(),
+                        ..data
+                    });
+                }
+            },
+        }
+
+        hr {}
+
+        div { class: "d-flex mb-1",
+            h5 { "Output" }
+            { classes }
+        }
+        table { class: "table table-hover font-monospace selectable",
+            "data-role": "output",
+            tbody {
+                { rows }
+            }
+        }
+    }
+}
+
+#[component]
+fn ToolIpPlannerComponent(
+    data: ToolIpComponentData,
+    onupdate: EventHandler<ToolIpComponentData>,
+) -> Element {
+    let sections = ip_planner_sections(&data);
+    let (sections, error) = match sections {
+        Ok(v) => (v, String::new()),
+        Err(e) => (vec![], format!("{e:#}")),
+    };
+
+    let operations = IpPlannerOperation::iter().map(|v| {
+        let id = format!(
+            "ToolIpComponent-btn-radio-operation-{}",
+            IpPlannerOperationDiscriminants::from(v)
+        );
+        clone!(data);
+        rsx! {
+            input {
+                checked: data.operation == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| {
+                    clone!(data);
+                    onupdate.call(ToolIpComponentData{
+                        operation: v,
+                        ..data
+                    });
+                }
+            }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
+        }
+    });
+    let split_kinds = IpSplitKind::iter().map(|v| {
+        let id = format!(
+            "ToolIpComponent-btn-radio-split-{}",
+            IpSplitKindDiscriminants::from(v)
+        );
+        clone!(data);
+        rsx! {
+            input {
+                checked: data.split_kind == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| {
+                    clone!(data);
+                    onupdate.call(ToolIpComponentData{
+                        split_kind: v,
+                        ..data
+                    });
+                }
+            }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
+        }
+    });
+    let sections = sections.into_iter().map(|section| {
+        rsx! {
+            h6 { class: "mt-2", "{section.title}" }
+            textarea {
+                "data-role": "output",
+                readonly: true,
+                class: "font-monospace form-control",
+                rows: section.lines.len().clamp(1, 10).to_string(),
+                { section.lines.join("\n") }
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "d-flex mb-1",
+            h5 { "Ranges" }
+            div { class: "btn-group btn-group-sm ms-auto", role: "group",
+                { operations }
+            }
+        }
+        textarea {
+            "data-role": "input",
+            "autocorrect": "off",
+            "autocapitalize": "none",
+            class: format!("font-monospace form-control {}", if !error.is_empty() { "border-danger" } else { "" }),
+            rows: "8",
+            placeholder: "One CIDR per line, e.g.\n10.0.0.0/24\n10.0.1.0/24\n2001:db8::/48",
+            value: data.ranges.clone(),
+            oninput: {
+                clone!(data);
+                move |v: Event<FormData>| {
+                    clone!(data);
+                    onupdate.call(ToolIpComponentData{
+                        ranges: v.value(),
+                        ..data
+                    });
+                }
+            },
+        }
+
+        if data.operation == IpPlannerOperation::Split {
+            div { class: "d-flex mt-2",
+                div { class: "btn-group btn-group-sm", role: "group",
+                    { split_kinds }
+                }
+                input {
+                    class: "font-monospace form-control form-control-sm ms-2",
+                    r#type: "text",
+                    placeholder: if data.split_kind == IpSplitKind::Count { "4" } else { "/26" },
+                    value: data.split_value.clone(),
+                    oninput: {
+                        clone!(data);
+                        move |v: Event<FormData>| {
+                            clone!(data);
+                            onupdate.call(ToolIpComponentData{
+                                split_value: v.value(),
+                                ..data
+                            });
+                        }
+                    },
+                }
+            }
+        }
+        if data.operation == IpPlannerOperation::Lookup {
+            input {
+                "autocorrect": "off",
+                "autocapitalize": "none",
+                class: "font-monospace form-control mt-2",
+                r#type: "text",
+                placeholder: "Address or CIDR to look up",
+                value: data.lookup.clone(),
+                oninput: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        onupdate.call(ToolIpComponentData{
+                            lookup: v.value(),
+                            ..data
+                        });
+                    }
+                },
+            }
+        }
+
+        hr {}
+
+        div { class: "d-flex mb-1",
+            h5 { "Output" }
+            span { class: "text-danger ms-2 align-self-center", "{error}" }
+        }
+        { sections }
+    }
+}
@@ -0,0 +1,422 @@
+use std::str::FromStr;
+
+use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+
+use crate::{
+    clone,
+    core::jwt::{
+        DATE_CLAIMS, DEFAULT_HEADER, JwtAlgorithm, JwtVerification, decode_jwt, encode_jwt,
+        format_numeric_date, set_header_algorithm, verify_jwt,
+    },
+};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+pub enum JwtMode {
+    Decode,
+    Encode,
+}
+
+fn verification_badge_class(verification: JwtVerification) -> &'static str {
+    match verification {
+        JwtVerification::Valid => "text-bg-success",
+        JwtVerification::InvalidSignature => "text-bg-danger",
+        JwtVerification::Expired | JwtVerification::NotYetValid => "text-bg-warning",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ToolJwtComponentData {
+    input: String,
+    mode: JwtMode,
+    /// HMAC secret, public key or JWK used to verify `input`.
+    verify_key: String,
+    header: String,
+    payload: String,
+    /// HMAC secret, private key or JWK used to sign `header` and `payload`.
+    sign_key: String,
+}
+
+impl Default for ToolJwtComponentData {
+    fn default() -> Self {
+        Self {
+            input: Default::default(),
+            mode: JwtMode::Decode,
+            verify_key: Default::default(),
+            header: DEFAULT_HEADER.to_string(),
+            payload: "{}".to_string(),
+            sign_key: Default::default(),
+        }
+    }
+}
+
+impl ToolJwtComponentData {
+    pub fn set_mode(&mut self, mode: JwtMode) {
+        self.mode = mode;
+    }
+
+    pub fn set_input(&mut self, input: String) {
+        self.input = input;
+    }
+}
+
+fn is_json(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text).is_ok()
+}
+
+#[component]
+pub fn ToolJwtComponent(
+    data: ToolJwtComponentData,
+    onupdate: EventHandler<ToolJwtComponentData>,
+) -> Element {
+    let modes = JwtMode::iter().map(|v| {
+        let id = format!(
+            "ToolJwtComponent-btn-radio-mode-{}",
+            JwtModeDiscriminants::from(v)
+        );
+        clone!(data);
+        rsx! {
+            input {
+                checked: data.mode == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| {
+                    clone!(data);
+                    onupdate.call(ToolJwtComponentData{
+                        mode: v,
+                        ..data
+                    });
+                }
+            }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
+        }
+    });
+
+    let body = match data.mode {
+        JwtMode::Decode => rsx! {
+            ToolJwtDecodeComponent { data: data.clone(), onupdate }
+        },
+        JwtMode::Encode => rsx! {
+            ToolJwtEncodeComponent { data: data.clone(), onupdate }
+        },
+    };
+
+    rsx! {
+        div { class: "d-flex flex-column m-1",
+            div { class: "d-flex mb-1",
+                div {
+                    class: "btn-group ms-auto",
+                    role: "group",
+                    { modes }
+                }
+            }
+            { body }
+        }
+    }
+}
+
+#[component]
+fn ToolJwtDecodeComponent(
+    data: ToolJwtComponentData,
+    onupdate: EventHandler<ToolJwtComponentData>,
+) -> Element {
+    let decoded = decode_jwt(&data.input);
+    let input_empty = data.input.trim().is_empty();
+    let input_valid = input_empty || decoded.valid();
+
+    let claim_rows = decoded.claims.as_ref().map(|claims| {
+        DATE_CLAIMS
+            .iter()
+            .filter_map(|(claim, name)| claims.get(claim).map(|v| (claim, name, v)))
+            .map(|(claim, name, value)| {
+                let (text, valid) = match format_numeric_date(value) {
+                    Ok(v) => (v, true),
+                    Err(e) => (e, false),
+                };
+                rsx! {
+                    tr {
+                        th { scope: "row", "{claim}" }
+                        td { "{name}" }
+                        td { "{value}" }
+                        td { class: if !valid { "text-danger" } else { "" }, "{text}" }
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let segments = [
+        ("Header", &decoded.header, "5"),
+        ("Payload", &decoded.payload, "10"),
+        ("Signature", &decoded.signature, "2"),
+    ]
+    .into_iter()
+    .map(|(name, segment, rows)| {
+        let show_invalid = !input_empty && !segment.valid;
+        rsx! {
+            div { class: "d-flex mb-1",
+                h5 { "{name}" }
+            }
+            textarea {
+                "data-role": if name == "Payload" { "output" },
+                readonly: true,
+                class: format!("font-monospace form-control mb-2 {}", if show_invalid { "border-danger" } else { "" }),
+                rows,
+                { if input_empty { String::new() } else { segment.text.clone() } }
+            }
+        }
+    });
+
+    let verification = (!input_empty && !data.verify_key.is_empty())
+        .then(|| verify_jwt(&data.input, &data.verify_key, jiff::Timestamp::now()));
+    let verification_ui = match verification {
+        None => rsx! {},
+        Some(Ok(v)) => rsx! {
+            span { class: format!("badge {} ms-auto align-self-center", verification_badge_class(v)), "{v}" }
+        },
+        Some(Err(e)) => rsx! {
+            span { class: "text-danger ms-auto align-self-center", "{e:#}" }
+        },
+    };
+
+    rsx! {
+        div { class: "d-flex mb-1",
+            h5 { "Input" }
+            button {
+                class: "btn btn-outline-secondary btn-sm ms-auto",
+                disabled: !decoded.header.valid || !decoded.payload.valid,
+                onclick: {
+                    clone!(data, decoded);
+                    move |_| {
+                        clone!(data, decoded);
+                        onupdate.call(ToolJwtComponentData{
+                            mode: JwtMode::Encode,
+                            header: decoded.header.text,
+                            payload: decoded.payload.text,
+                            ..data
+                        });
+                    }
+                },
+                "Edit"
+            }
+        }
+        textarea {
+            "data-role": "input",
+            "autocorrect": "off",
+            "autocapitalize": "none",
+            class: format!("font-monospace form-control {}", if !input_valid { "border-danger" } else { "" }),
+            rows: "3",
+            oninput: {
+                clone!(data);
+                move |v: Event<FormData>| {
+                    clone!(data);
+                    onupdate.call(ToolJwtComponentData{
+                        input: v.value(),
+                        ..data
+                    });
+                }
+            },
+            { data.input.clone() }
+        }
+
+        hr {}
+
+        { segments }
+
+        if let Some(claim_rows) = claim_rows.filter(|v| !v.is_empty()) {
+            div { class: "d-flex mb-1",
+                h5 { "Dates" }
+            }
+            table { class: "table table-hover font-monospace selectable",
+                thead {
+                    tr {
+                        th { scope: "col", "Claim" }
+                        th { scope: "col", "Name" }
+                        th { scope: "col", "Value" }
+                        th { scope: "col", "Date" }
+                    }
+                }
+                tbody {
+                    { claim_rows.into_iter() }
+                }
+            }
+        }
+
+        hr {}
+
+        div { class: "d-flex mb-1",
+            h5 { "Verify" }
+            { verification_ui }
+        }
+        textarea {
+            "autocorrect": "off",
+            "autocapitalize": "none",
+            class: "font-monospace form-control",
+            rows: "3",
+            placeholder: "HMAC secret, PEM public key or JWK",
+            oninput: {
+                clone!(data);
+                move |v: Event<FormData>| {
+                    clone!(data);
+                    onupdate.call(ToolJwtComponentData{
+                        verify_key: v.value(),
+                        ..data
+                    });
+                }
+            },
+            { data.verify_key.clone() }
+        }
+    }
+}
+
+#[component]
+fn ToolJwtEncodeComponent(
+    data: ToolJwtComponentData,
+    onupdate: EventHandler<ToolJwtComponentData>,
+) -> Element {
+    let header_valid = is_json(&data.header);
+    let payload_valid = is_json(&data.payload);
+    let encoded = (!data.sign_key.is_empty())
+        .then(|| encode_jwt(&data.header, &data.payload, &data.sign_key));
+    let (output, error) = match encoded {
+        None => (String::new(), String::new()),
+        Some(Ok(v)) => (v, String::new()),
+        Some(Err(e)) => (String::new(), format!("{e:#}")),
+    };
+
+    let algorithms = JwtAlgorithm::iter().map(|v| {
+        rsx! {
+            option { value: "{v}", "{v}" }
+        }
+    });
+
+    rsx! {
+        div { class: "d-flex mb-1",
+            h5 { "Header" }
+            select {
+                class: "form-select form-select-sm ms-auto w-auto",
+                value: "",
+                onchange: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        let Ok(alg) = JwtAlgorithm::from_str(&v.value()) else {
+                            return;
+                        };
+                        onupdate.call(ToolJwtComponentData{
+                            header: set_header_algorithm(&data.header, alg),
+                            ..data
+                        });
+                    }
+                },
+                option { value: "", disabled: true, "Algorithm" }
+                { algorithms }
+            }
+        }
+        textarea {
+            "autocorrect": "off",
+            "autocapitalize": "none",
+            class: format!("font-monospace form-control mb-2 {}", if !header_valid { "border-danger" } else { "" }),
+            rows: "5",
+            oninput: {
+                clone!(data);
+                move |v: Event<FormData>| {
+                    clone!(data);
+                    onupdate.call(ToolJwtComponentData{
+                        header: v.value(),
+                        ..data
+                    });
+                }
+            },
+            { data.header.clone() }
+        }
+
+        div { class: "d-flex mb-1",
+            h5 { "Payload" }
+        }
+        textarea {
+            "data-role": "input",
+            "autocorrect": "off",
+            "autocapitalize": "none",
+            class: format!("font-monospace form-control mb-2 {}", if !payload_valid { "border-danger" } else { "" }),
+            rows: "10",
+            oninput: {
+                clone!(data);
+                move |v: Event<FormData>| {
+                    clone!(data);
+                    onupdate.call(ToolJwtComponentData{
+                        payload: v.value(),
+                        ..data
+                    });
+                }
+            },
+            { data.payload.clone() }
+        }
+
+        div { class: "d-flex mb-1",
+            h5 { "Key" }
+        }
+        textarea {
+            "autocorrect": "off",
+            "autocapitalize": "none",
+            class: "font-monospace form-control",
+            rows: "3",
+            placeholder: "HMAC secret, PEM private key or JWK",
+            oninput: {
+                clone!(data);
+                move |v: Event<FormData>| {
+                    clone!(data);
+                    onupdate.call(ToolJwtComponentData{
+                        sign_key: v.value(),
+                        ..data
+                    });
+                }
+            },
+            { data.sign_key.clone() }
+        }
+
+        hr {}
+
+        div { class: "d-flex mb-1",
+            h5 { "Output" }
+            span { class: "text-danger ms-auto align-self-center", "{error}" }
+            button {
+                class: "btn btn-outline-secondary btn-sm ms-2",
+                disabled: output.is_empty(),
+                onclick: {
+                    clone!(data, output);
+                    move |_| {
+                        clone!(data, output);
+                        onupdate.call(ToolJwtComponentData{
+                            mode: JwtMode::Decode,
+                            input: output,
+                            verify_key: data.sign_key.clone(),
+                            ..data
+                        });
+                    }
+                },
+                "Decode"
+            }
+        }
+        textarea {
+            "data-role": "output",
+            readonly: true,
+            class: "font-monospace form-control",
+            rows: "3",
+            { output.clone() }
+        }
+    }
+}
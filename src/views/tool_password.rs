@@ -0,0 +1,474 @@
+use std::time::{Duration, Instant};
+
+use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+
+use super::enum_select::enum_select;
+use crate::{
+    clone,
+    core::password::{
+        PasswordAlgorithm, PasswordParams, hash_password, parse_password_hash, verify_password,
+    },
+};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumDiscriminants,
+)]
+#[strum_discriminants(derive(strum_macros::Display))]
+pub enum PasswordMode {
+    Hash,
+    Verify,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ToolPasswordComponentData {
+    mode: PasswordMode,
+    password: String,
+    algorithm: PasswordAlgorithm,
+    params: PasswordParams,
+    /// Last hash made in the Hash mode.
+    output: String,
+    /// Stored hash checked in the Verify mode.
+    hash: String,
+}
+
+impl Default for ToolPasswordComponentData {
+    fn default() -> Self {
+        Self {
+            mode: PasswordMode::Hash,
+            password: Default::default(),
+            algorithm: PasswordAlgorithm::Argon2id,
+            params: Default::default(),
+            output: Default::default(),
+            hash: Default::default(),
+        }
+    }
+}
+
+/// Run `job` on a blocking thread, the hashes being slow on purpose.
+async fn run_timed<T: Send + 'static>(
+    job: impl FnOnce() -> T + Send + 'static,
+) -> Result<(T, Duration), String> {
+    let started = Instant::now();
+    tokio::task::spawn_blocking(job)
+        .await
+        .map(|v| (v, started.elapsed()))
+        .map_err(|e| e.to_string())
+}
+
+/// Password field, the main input of the tool when `main` is set.
+fn password_input(
+    data: &ToolPasswordComponentData,
+    onupdate: EventHandler<ToolPasswordComponentData>,
+    main: bool,
+) -> Element {
+    clone!(data);
+    rsx! {
+        input {
+            "data-role": if main { "input" },
+            class: "font-monospace form-control form-control-sm",
+            r#type: "text",
+            "autocorrect": "off",
+            "autocapitalize": "none",
+            placeholder: "Password",
+            value: data.password.clone(),
+            oninput: move |v: Event<FormData>| {
+                clone!(data);
+                onupdate.call(ToolPasswordComponentData{
+                    password: v.value(),
+                    ..data
+                });
+            },
+        }
+    }
+}
+
+#[component]
+pub fn ToolPasswordComponent(
+    data: ToolPasswordComponentData,
+    onupdate: EventHandler<ToolPasswordComponentData>,
+) -> Element {
+    let modes = PasswordMode::iter().map(|v| {
+        let id = format!(
+            "ToolPasswordComponent-btn-radio-mode-{}",
+            PasswordModeDiscriminants::from(v)
+        );
+        clone!(data);
+        rsx! {
+            input {
+                checked: data.mode == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| {
+                    clone!(data);
+                    onupdate.call(ToolPasswordComponentData{
+                        mode: v,
+                        ..data
+                    });
+                }
+            }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
+        }
+    });
+
+    let body = match data.mode {
+        PasswordMode::Hash => rsx! {
+            ToolPasswordHashComponent { data: data.clone(), onupdate }
+        },
+        PasswordMode::Verify => rsx! {
+            ToolPasswordVerifyComponent { data: data.clone(), onupdate }
+        },
+    };
+
+    rsx! {
+        div { class: "d-flex flex-column m-1",
+            div { class: "d-flex mb-1",
+                div {
+                    class: "btn-group ms-auto",
+                    role: "group",
+                    { modes }
+                }
+            }
+            { body }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum HashJob {
+    Idle,
+    Running,
+    Done(Duration),
+    Failed(String),
+}
+
+#[component]
+fn ToolPasswordHashComponent(
+    data: ToolPasswordComponentData,
+    onupdate: EventHandler<ToolPasswordComponentData>,
+) -> Element {
+    let mut job = use_signal(|| HashJob::Idle);
+    let running = *job.read() == HashJob::Running;
+    // What the user changed while hashing is kept, the job only fills in the output.
+    let mut latest = use_hook(|| CopyValue::new(data.clone()));
+    latest.set(data.clone());
+
+    let param = |label: &'static str, value: u32, set: fn(&mut PasswordParams, u32)| {
+        clone!(data);
+        rsx! {
+            label { class: "small text-nowrap", "{label}" }
+            input {
+                class: "form-control form-control-sm w-auto",
+                r#type: "number",
+                min: "0",
+                value: "{value}",
+                oninput: move |v: Event<FormData>| {
+                    let Ok(value) = v.value().parse() else {
+                        return;
+                    };
+                    let mut data = data.clone();
+                    set(&mut data.params, value);
+                    onupdate.call(data);
+                },
+            }
+        }
+    };
+    let params = &data.params;
+    let param_inputs = match data.algorithm {
+        PasswordAlgorithm::Bcrypt => {
+            vec![param("Cost", params.bcrypt_cost, |p, v| p.bcrypt_cost = v)]
+        }
+        PasswordAlgorithm::Argon2id | PasswordAlgorithm::Argon2i | PasswordAlgorithm::Argon2d => {
+            vec![
+                param("Memory (KiB)", params.argon2_memory, |p, v| {
+                    p.argon2_memory = v
+                }),
+                param("Iterations", params.argon2_iterations, |p, v| {
+                    p.argon2_iterations = v
+                }),
+                param("Parallelism", params.argon2_parallelism, |p, v| {
+                    p.argon2_parallelism = v
+                }),
+            ]
+        }
+        PasswordAlgorithm::Scrypt => vec![
+            param("log₂ N", params.scrypt_log_n, |p, v| p.scrypt_log_n = v),
+            param("r", params.scrypt_r, |p, v| p.scrypt_r = v),
+            param("p", params.scrypt_p, |p, v| p.scrypt_p = v),
+        ],
+        PasswordAlgorithm::Pbkdf2Sha256 | PasswordAlgorithm::Pbkdf2Sha512 => {
+            vec![param("Rounds", params.pbkdf2_rounds, |p, v| {
+                p.pbkdf2_rounds = v
+            })]
+        }
+        PasswordAlgorithm::Sha256Crypt | PasswordAlgorithm::Sha512Crypt => {
+            vec![param("Rounds", params.crypt_rounds, |p, v| {
+                p.crypt_rounds = v
+            })]
+        }
+    };
+    // bcrypt silently ignores the rest.
+    let truncated = data.algorithm == PasswordAlgorithm::Bcrypt && data.password.len() > 72;
+
+    let hash = {
+        clone!(data);
+        move |_| {
+            job.set(HashJob::Running);
+            clone!(data);
+            spawn(async move {
+                let (algorithm, params, password) =
+                    (data.algorithm, data.params.clone(), data.password.clone());
+                let result = run_timed(move || hash_password(algorithm, &params, &password)).await;
+                let latest = latest.cloned();
+                let changed = (latest.algorithm, &latest.params, &latest.password)
+                    != (data.algorithm, &data.params, &data.password);
+                match result {
+                    Ok(_) if changed => job.set(HashJob::Failed(
+                        "The password or parameters changed while hashing".to_string(),
+                    )),
+                    Ok((Ok(output), elapsed)) => {
+                        job.set(HashJob::Done(elapsed));
+                        onupdate.call(ToolPasswordComponentData { output, ..latest });
+                    }
+                    Ok((Err(e), _)) => job.set(HashJob::Failed(e.to_string())),
+                    Err(e) => job.set(HashJob::Failed(e)),
+                }
+            });
+        }
+    };
+
+    let status = match &*job.read() {
+        HashJob::Idle => rsx! {},
+        HashJob::Running => rsx! {
+            span { class: "spinner-border spinner-border-sm ms-2 align-self-center", role: "status" }
+        },
+        HashJob::Done(elapsed) => rsx! {
+            span { class: "small text-body-secondary ms-2 align-self-center", "in {elapsed.as_secs_f64():.2} s" }
+        },
+        HashJob::Failed(e) => rsx! {
+            span { class: "text-danger ms-2 align-self-center", "{e}" }
+        },
+    };
+
+    rsx! {
+        div { class: "d-flex mb-1",
+            h5 { "Password" }
+        }
+        { password_input(&data, onupdate, true) }
+        if truncated {
+            span { class: "text-warning small", "bcrypt only uses the first 72 bytes" }
+        }
+        div { class: "d-flex mt-1 gap-2 align-items-center flex-wrap",
+            {
+                clone!(data);
+                enum_select(data.algorithm, move |algorithm| {
+                    onupdate.call(ToolPasswordComponentData {
+                        algorithm,
+                        ..data.clone()
+                    })
+                })
+            }
+            { param_inputs.into_iter() }
+            button {
+                class: "btn btn-outline-secondary btn-sm",
+                onclick: {
+                    clone!(data);
+                    move |_| {
+                        clone!(data);
+                        onupdate.call(ToolPasswordComponentData{
+                            params: Default::default(),
+                            ..data
+                        });
+                    }
+                },
+                "Defaults"
+            }
+            button {
+                class: "btn btn-primary btn-sm ms-auto",
+                disabled: running,
+                onclick: hash,
+                "Hash"
+            }
+        }
+
+        hr {}
+
+        div { class: "d-flex mb-1",
+            h5 { "Output" }
+            { status }
+            button {
+                class: "btn btn-outline-secondary btn-sm ms-auto",
+                disabled: data.output.is_empty(),
+                onclick: {
+                    clone!(data);
+                    move |_| {
+                        clone!(data);
+                        onupdate.call(ToolPasswordComponentData{
+                            mode: PasswordMode::Verify,
+                            hash: data.output.clone(),
+                            ..data
+                        });
+                    }
+                },
+                "Verify"
+            }
+        }
+        textarea {
+            "data-role": "output",
+            readonly: true,
+            class: "font-monospace form-control",
+            rows: "3",
+            { data.output.clone() }
+        }
+    }
+}
+
+/// Outcome of a verification, with what was verified so that edits make it stale.
+#[derive(Debug, Clone, PartialEq)]
+struct Verification {
+    password: String,
+    hash: String,
+    result: Result<(bool, Duration), String>,
+}
+
+#[component]
+fn ToolPasswordVerifyComponent(
+    data: ToolPasswordComponentData,
+    onupdate: EventHandler<ToolPasswordComponentData>,
+) -> Element {
+    let mut verification = use_signal(|| None::<Verification>);
+    let mut running = use_signal(|| false);
+
+    let parsed = (!data.hash.trim().is_empty()).then(|| parse_password_hash(&data.hash));
+    let hash_valid = !matches!(parsed, Some(Err(_)));
+    let parsed_ui = match &parsed {
+        None => rsx! {},
+        Some(Err(e)) => rsx! {
+            span { class: "text-danger small", "{e}" }
+        },
+        Some(Ok(parsed)) => {
+            let params = parsed.params.iter().map(|(name, value)| {
+                rsx! {
+                    tr {
+                        th { scope: "row", "{name}" }
+                        td { "{value}" }
+                    }
+                }
+            });
+            rsx! {
+                table { class: "table table-hover font-monospace selectable mt-2",
+                    tbody {
+                        tr {
+                            th { scope: "row", "Algorithm" }
+                            td { "{parsed.algorithm}" }
+                        }
+                        { params }
+                        tr {
+                            th { scope: "row", "Salt" }
+                            td { class: "text-break", "{parsed.salt}" }
+                        }
+                        tr {
+                            th { scope: "row", "Hash" }
+                            td { class: "text-break", "{parsed.hash}" }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    let verify = {
+        clone!(data);
+        move |_| {
+            running.set(true);
+            clone!(data);
+            spawn(async move {
+                let (password, hash) = (data.password.clone(), data.hash.clone());
+                let result = run_timed(move || verify_password(&password, &hash)).await;
+                let result = match result {
+                    Ok((Ok(matched), elapsed)) => Ok((matched, elapsed)),
+                    Ok((Err(e), _)) => Err(e.to_string()),
+                    Err(e) => Err(e),
+                };
+                verification.set(Some(Verification {
+                    password: data.password,
+                    hash: data.hash,
+                    result,
+                }));
+                running.set(false);
+            });
+        }
+    };
+
+    let verdict = match &*verification.read() {
+        Some(v) if v.password == data.password && v.hash == data.hash => match &v.result {
+            Ok((true, elapsed)) => rsx! {
+                span { class: "badge text-bg-success ms-2 align-self-center", "Match" }
+                span { class: "small text-body-secondary ms-2 align-self-center", "in {elapsed.as_secs_f64():.2} s" }
+            },
+            Ok((false, elapsed)) => rsx! {
+                span { class: "badge text-bg-danger ms-2 align-self-center", "No match" }
+                span { class: "small text-body-secondary ms-2 align-self-center", "in {elapsed.as_secs_f64():.2} s" }
+            },
+            Err(e) => rsx! {
+                span { class: "text-danger ms-2 align-self-center", "{e}" }
+            },
+        },
+        _ => rsx! {},
+    };
+
+    rsx! {
+        div { class: "d-flex mb-1",
+            h5 { "Hash" }
+        }
+        textarea {
+            "data-role": "input",
+            "autocorrect": "off",
+            "autocapitalize": "none",
+            class: format!("font-monospace form-control {}", if !hash_valid { "border-danger" } else { "" }),
+            rows: "3",
+            placeholder: "$2b$…, $argon2id$…, $scrypt$…, $pbkdf2-sha256$…, $5$… or $6$…",
+            oninput: {
+                clone!(data);
+                move |v: Event<FormData>| {
+                    clone!(data);
+                    onupdate.call(ToolPasswordComponentData{
+                        hash: v.value(),
+                        ..data
+                    });
+                }
+            },
+            { data.hash.clone() }
+        }
+        { parsed_ui }
+
+        hr {}
+
+        div { class: "d-flex mb-1",
+            h5 { "Password" }
+            { verdict }
+            if running() {
+                span { class: "spinner-border spinner-border-sm ms-2 align-self-center", role: "status" }
+            }
+        }
+        div { class: "d-flex gap-2",
+            { password_input(&data, onupdate, false) }
+            button {
+                class: "btn btn-primary btn-sm",
+                disabled: running() || !matches!(parsed, Some(Ok(_))),
+                onclick: verify,
+                "Verify"
+            }
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+
+use super::enum_select::enum_select;
+use crate::{
+    clone,
+    core::{
+        InputKind, InputKindDiscriminants,
+        base64::Base64Alphabet,
+        hash::HashAlgorithm,
+        pipeline::{Operation, Recipe, preview, run_pipeline},
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ToolPipelineComponentData {
+    input: String,
+    input_kind: InputKind,
+    recipe: Recipe,
+    /// Recipe JSON being typed by the user that doesn't parse yet.
+    recipe_json: Option<String>,
+}
+
+impl Default for ToolPipelineComponentData {
+    fn default() -> Self {
+        Self {
+            input: Default::default(),
+            input_kind: InputKind::Utf8,
+            recipe: Default::default(),
+            recipe_json: None,
+        }
+    }
+}
+
+#[component]
+pub fn ToolPipelineComponent(
+    data: ToolPipelineComponentData,
+    onupdate: EventHandler<ToolPipelineComponentData>,
+    /// Recipes saved by the user, shared by every session.
+    recipes: Vec<Recipe>,
+    onrecipesupdate: EventHandler<Vec<Recipe>>,
+) -> Element {
+    let update_steps = {
+        clone!(data);
+        move |f: &dyn Fn(&mut Vec<Operation>)| {
+            let mut data = data.clone();
+            f(&mut data.recipe.steps);
+            data.recipe_json = None;
+            onupdate.call(data);
+        }
+    };
+
+    // Decompressing and hashing is only redone when the input or the steps change.
+    let text = data.input.clone();
+    let input_kind = data.input_kind;
+    let recipe_steps = data.recipe.steps.clone();
+    let pipeline = use_memo(use_reactive!(|(text, input_kind, recipe_steps)| {
+        let input = input_kind.decode(&text);
+        let results = run_pipeline(input.as_deref().unwrap_or_default(), &recipe_steps)
+            .into_iter()
+            .map(|v| v.map_err(|e| e.to_string()))
+            .collect::<Vec<_>>();
+        (input.is_ok(), results)
+    }));
+    let (input_valid, results) = pipeline();
+    let step_count = data.recipe.steps.len();
+
+    let input_kinds = InputKind::iter().map(|v| {
+        let id = format!(
+            "ToolPipelineComponent-btn-radio-input-{}",
+            InputKindDiscriminants::from(v)
+        );
+        clone!(data);
+        rsx! {
+            input {
+                checked: data.input_kind == v,
+                class: "btn-check",
+                id: id.clone(),
+                r#type: "radio",
+                onchange: move |_| {
+                    clone!(data);
+                    onupdate.call(ToolPipelineComponentData{
+                        input_kind: v,
+                        ..data
+                    });
+                }
+            }
+            label { class: "btn btn-outline-primary", r#for: id, "{v}" }
+        }
+    });
+
+    let steps = data.recipe.steps.iter().enumerate().map(|(i, step)| {
+        let parameter = match *step {
+            Operation::FromBase64 { alphabet } | Operation::ToBase64 { alphabet } => {
+                let decode = matches!(step, Operation::FromBase64 { .. });
+                clone!(update_steps);
+                enum_select(alphabet, move |alphabet: Base64Alphabet| {
+                    update_steps(&|v| {
+                        v[i] = if decode {
+                            Operation::FromBase64 { alphabet }
+                        } else {
+                            Operation::ToBase64 { alphabet }
+                        }
+                    })
+                })
+            }
+            Operation::Hash { algorithm } => {
+                clone!(update_steps);
+                enum_select(algorithm, move |algorithm: HashAlgorithm| {
+                    update_steps(&|v| v[i] = Operation::Hash { algorithm })
+                })
+            }
+            _ => rsx! {},
+        };
+        let result = match results.get(i) {
+            Some(Ok(bytes)) => rsx! {
+                textarea {
+                    "data-role": if i + 1 == step_count { "output" },
+                    readonly: true,
+                    class: "font-monospace form-control",
+                    rows: "3",
+                    { preview(bytes) }
+                }
+                div { class: "small text-body-secondary", "{bytes.len()} bytes" }
+            },
+            Some(Err(e)) => rsx! {
+                span { class: "text-danger", "{e}" }
+            },
+            None => rsx! {
+                span { class: "text-body-secondary", "Not run, a previous step failed" }
+            },
+        };
+
+        rsx! {
+            div { class: "card mb-2",
+                div { class: "card-header d-flex align-items-center gap-2 py-1",
+                    span { class: "fw-bold", "{i + 1}. {step.name()}" }
+                    { parameter }
+                    div { class: "btn-group btn-group-sm ms-auto", role: "group",
+                        button {
+                            class: "btn btn-outline-secondary",
+                            title: "Move up",
+                            disabled: i == 0,
+                            onclick: {
+                                clone!(update_steps);
+                                move |_| update_steps(&|v| v.swap(i - 1, i))
+                            },
+                            "↑"
+                        }
+                        button {
+                            class: "btn btn-outline-secondary",
+                            title: "Move down",
+                            disabled: i + 1 == step_count,
+                            onclick: {
+                                clone!(update_steps);
+                                move |_| update_steps(&|v| v.swap(i, i + 1))
+                            },
+                            "↓"
+                        }
+                        button {
+                            class: "btn btn-outline-danger",
+                            title: "Remove",
+                            onclick: {
+                                clone!(update_steps);
+                                move |_| update_steps(&|v| { v.remove(i); })
+                            },
+                            "✕"
+                        }
+                    }
+                }
+                div { class: "card-body p-2", { result } }
+            }
+        }
+    });
+
+    let operations = Operation::all().into_iter().enumerate().map(|(i, v)| {
+        rsx! {
+            option { value: "{i}", "{v.name()}" }
+        }
+    });
+
+    let saved_index = recipes.iter().position(|v| v.name == data.recipe.name);
+    let saved_recipes = recipes.iter().enumerate().map(|(i, v)| {
+        rsx! {
+            option { value: "{i}", selected: saved_index == Some(i), "{v.name}" }
+        }
+    });
+
+    let recipe_json = data
+        .recipe_json
+        .clone()
+        .unwrap_or_else(|| serde_json::to_string_pretty(&data.recipe).unwrap_or_default());
+    let recipe_json_valid = data.recipe_json.is_none();
+
+    rsx! {
+        div { class: "d-flex flex-column m-1",
+            div { class: "d-flex mb-1",
+                h5 { "Input" }
+                div {
+                    class: "btn-group ms-auto",
+                    role: "group",
+                    { input_kinds }
+                }
+            }
+            textarea {
+                "data-role": "input",
+                "autocorrect": "off",
+                "autocapitalize": "none",
+                class: format!("font-monospace form-control {}", if !input_valid { "border-danger" } else { "" }),
+                rows: "3",
+                oninput: {
+                    clone!(data);
+                    move |v: Event<FormData>| {
+                        clone!(data);
+                        onupdate.call(ToolPipelineComponentData{
+                            input: v.value(),
+                            ..data
+                        });
+                    }
+                },
+                { data.input.clone() }
+            }
+
+            hr {}
+
+            div { class: "d-flex mb-1 gap-2",
+                h5 { class: "text-nowrap", "Recipe" }
+                input {
+                    class: "form-control form-control-sm",
+                    r#type: "text",
+                    placeholder: "Name",
+                    value: data.recipe.name.clone(),
+                    oninput: {
+                        clone!(data);
+                        move |v: Event<FormData>| {
+                            let mut data = data.clone();
+                            data.recipe.name = v.value();
+                            data.recipe_json = None;
+                            onupdate.call(data);
+                        }
+                    },
+                }
+                button {
+                    class: "btn btn-outline-primary btn-sm",
+                    disabled: data.recipe.name.trim().is_empty(),
+                    onclick: {
+                        clone!(data, recipes);
+                        move |_| {
+                            let mut recipes = recipes.clone();
+                            match recipes.iter_mut().find(|v| v.name == data.recipe.name) {
+                                Some(saved) => *saved = data.recipe.clone(),
+                                None => recipes.push(data.recipe.clone()),
+                            }
+                            onrecipesupdate.call(recipes);
+                        }
+                    },
+                    "Save"
+                }
+                button {
+                    class: "btn btn-outline-danger btn-sm",
+                    disabled: saved_index.is_none(),
+                    onclick: {
+                        clone!(recipes);
+                        move |_| {
+                            let mut recipes = recipes.clone();
+                            if let Some(i) = saved_index {
+                                recipes.remove(i);
+                            }
+                            onrecipesupdate.call(recipes);
+                        }
+                    },
+                    "Delete"
+                }
+                select {
+                    class: "form-select form-select-sm w-auto",
+                    disabled: recipes.is_empty(),
+                    onchange: {
+                        clone!(data, recipes);
+                        move |v: Event<FormData>| {
+                            let Some(recipe) = v.value().parse::<usize>().ok().and_then(|i| recipes.get(i)) else {
+                                return;
+                            };
+                            onupdate.call(ToolPipelineComponentData{
+                                recipe: recipe.clone(),
+                                recipe_json: None,
+                                ..data.clone()
+                            });
+                        }
+                    },
+                    option { value: "", disabled: true, selected: saved_index.is_none(), "Load" }
+                    { saved_recipes }
+                }
+            }
+
+            { steps }
+
+            select {
+                class: "form-select mb-2",
+                value: "",
+                onchange: {
+                    clone!(update_steps);
+                    move |v: Event<FormData>| {
+                        let Some(operation) = v.value().parse::<usize>().ok().and_then(|i| Operation::all().get(i).copied()) else {
+                            return;
+                        };
+                        update_steps(&|v| v.push(operation))
+                    }
+                },
+                option { value: "", disabled: true, selected: true, "Add step" }
+                { operations }
+            }
+
+            details {
+                summary { class: "h6", "Recipe JSON" }
+                textarea {
+                    "autocorrect": "off",
+                    "autocapitalize": "none",
+                    class: format!("font-monospace form-control {}", if !recipe_json_valid { "border-danger" } else { "" }),
+                    rows: "8",
+                    oninput: {
+                        clone!(data);
+                        move |v: Event<FormData>| {
+                            let mut data = data.clone();
+                            match serde_json::from_str::<Recipe>(&v.value()) {
+                                Ok(recipe) => {
+                                    data.recipe = recipe;
+                                    data.recipe_json = None;
+                                }
+                                Err(_) => data.recipe_json = Some(v.value()),
+                            }
+                            onupdate.call(data);
+                        }
+                    },
+                    { recipe_json }
+                }
+            }
+        }
+    }
+}